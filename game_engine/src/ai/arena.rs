@@ -0,0 +1,427 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use crate::Player;
+use crate::ai::{Connect4AI, GomokuAI};
+use crate::games::{Connect4Game, GomokuGame, SeriesGame};
+use crate::net::LockstepGame;
+
+/// Games the arena can play: the rule engine plus a book of opening lines
+pub trait ArenaGame: LockstepGame + SeriesGame {
+    /// Opening lines in the `LockstepGame::apply_move` encoding, all legal from the start position
+    fn opening_book(&self) -> Vec<Vec<Vec<usize>>>;
+    
+    /// Games before deterministic players start repeating: every opening once with each color
+    fn distinct_games(&self) -> usize {
+        2 * self.opening_book().len().max(1)
+    }
+}
+
+impl ArenaGame for Connect4Game {
+    /// Every four-ply opening (no column can overflow, as boards have at least four rows)
+    fn opening_book(&self) -> Vec<Vec<Vec<usize>>> {
+        let cols = self.get_cols();
+        let mut book = vec![Vec::new()];
+        for _ in 0..4 {
+            book = book.into_iter()
+                .flat_map(|line: Vec<Vec<usize>>| (0..cols).map(move |column| [line.clone(), vec![vec![column]]].concat()))
+                .collect();
+        }
+        book
+    }
+}
+
+impl ArenaGame for GomokuGame {
+    /// Black in the center, White next to it (direct or diagonal),
+    /// then one more stone each on any free point within two of the center
+    fn opening_book(&self) -> Vec<Vec<Vec<usize>>> {
+        let near: Vec<(usize, usize)> = (5..=9).flat_map(|row| (5..=9).map(move |col| (row, col))).collect();
+        let mut book = Vec::new();
+        for white in [(7, 8), (8, 8)] {
+            for &black in &near {
+                for &second_white in &near {
+                    let stones = [(7, 7), white, black, second_white];
+                    let distinct = (1..stones.len()).all(|index| !stones[..index].contains(&stones[index]));
+                    if distinct {
+                        book.push(stones.iter().map(|&(row, col)| vec![row, col]).collect());
+                    }
+                }
+            }
+        }
+        book
+    }
+}
+
+/// A competitor: picks the move for the side to move
+pub trait ArenaPlayer<G> {
+    /// Move in the `LockstepGame::apply_move` encoding (None if the player has nothing to play)
+    fn choose_move(&self, game: &G) -> Option<Vec<usize>>;
+    
+    /// Called before every game, e.g. to drop caches that would grow over thousands of games
+    fn new_game(&self) {}
+}
+
+impl ArenaPlayer<Connect4Game> for Connect4AI {
+    fn choose_move(&self, game: &Connect4Game) -> Option<Vec<usize>> {
+        self.get_best_move_for_player(game, game.current_player()).map(|code| vec![code])
+    }
+    
+    fn new_game(&self) {
+        self.evaluator().clear_cache();
+    }
+}
+
+impl ArenaPlayer<GomokuGame> for GomokuAI {
+    fn choose_move(&self, game: &GomokuGame) -> Option<Vec<usize>> {
+        self.get_best_move(game).map(|(row, col)| vec![row, col])
+    }
+}
+
+/// Result of one game from participant A's point of view
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArenaOutcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl ArenaOutcome {
+    /// Points for A (win 1, draw ½)
+    pub fn score(self) -> f64 {
+        match self {
+            ArenaOutcome::Win => 1.0,
+            ArenaOutcome::Draw => 0.5,
+            ArenaOutcome::Loss => 0.0,
+        }
+    }
+}
+
+/// One finished arena game
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArenaGameRecord {
+    pub opening: usize,         // Index into the opening book
+    pub a_moves_first: bool,
+    pub moves: Vec<Vec<usize>>, // Every move including the opening line
+    pub winner: Option<Player>, // None for draws, including games stopped at the move limit
+    pub outcome: ArenaOutcome,
+}
+
+/// Win/draw/loss counts of participant A
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArenaStats {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl ArenaStats {
+    pub fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+    
+    pub fn add(&mut self, outcome: ArenaOutcome) {
+        match outcome {
+            ArenaOutcome::Win => self.wins += 1,
+            ArenaOutcome::Draw => self.draws += 1,
+            ArenaOutcome::Loss => self.losses += 1,
+        }
+    }
+    
+    /// Mean points per game of A (0.5 before the first game)
+    pub fn score(&self) -> f64 {
+        match self.games() {
+            0 => 0.5,
+            games => (self.wins as f64 + self.draws as f64 / 2.0) / games as f64,
+        }
+    }
+    
+    /// Variance of a single game's points around the mean score
+    fn variance(&self) -> f64 {
+        let games = self.games();
+        if games == 0 {
+            return 0.0;
+        }
+        let score = self.score();
+        let squares = self.wins as f64 * (1.0 - score).powi(2)
+            + self.draws as f64 * (0.5 - score).powi(2)
+            + self.losses as f64 * score.powi(2);
+        squares / games as f64
+    }
+    
+    /// Elo difference A − B implied by the score (infinite after only wins or only losses)
+    pub fn elo(&self) -> f64 {
+        score_to_elo(self.score())
+    }
+    
+    /// Confidence interval of the Elo difference for the normal quantile `z` (1.96 for 95%)
+    pub fn elo_interval(&self, z: f64) -> (f64, f64) {
+        let margin = z * (self.variance() / self.games().max(1) as f64).sqrt();
+        let score = self.score();
+        (score_to_elo((score - margin).max(0.0)), score_to_elo((score + margin).min(1.0)))
+    }
+    
+    /// Log-likelihood ratio of H1 (Elo difference `elo1`) against H0 (`elo0`), normal approximation
+    /// Zero while every game ended the same way, as the variance is still unknown
+    pub fn llr(&self, sprt: &Sprt) -> f64 {
+        let variance = self.variance();
+        if variance == 0.0 {
+            return 0.0;
+        }
+        let (score0, score1) = (elo_to_score(sprt.elo0), elo_to_score(sprt.elo1));
+        self.games() as f64 * (score1 - score0) * (2.0 * self.score() - score0 - score1) / (2.0 * variance)
+    }
+    
+    /// One-line report: counts, score and Elo with its 95% interval
+    pub fn summary(&self) -> String {
+        let (low, high) = self.elo_interval(1.96);
+        format!(
+            "Games {}: +{} ={} -{}, score {:.1}%, Elo {:+.1} [{:+.1}, {:+.1}]",
+            self.games(),
+            self.wins,
+            self.draws,
+            self.losses,
+            self.score() * 100.0,
+            self.elo(),
+            low,
+            high
+        )
+    }
+}
+
+/// Expected score of the stronger side for an Elo difference (logistic model)
+pub fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Elo difference for an expected score
+pub fn score_to_elo(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Sequential probability ratio test: H0 = A is `elo0` stronger, H1 = A is `elo1` stronger
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64, // Chance of accepting H1 when H0 holds
+    pub beta: f64,  // Chance of accepting H0 when H1 holds
+}
+
+impl Sprt {
+    pub fn new(elo0: f64, elo1: f64) -> Self {
+        Self { elo0, elo1, alpha: 0.05, beta: 0.05 }
+    }
+    
+    /// LLR bounds (lower accepts H0, upper accepts H1)
+    pub fn bounds(&self) -> (f64, f64) {
+        ((self.beta / (1.0 - self.alpha)).ln(), ((1.0 - self.beta) / self.alpha).ln())
+    }
+    
+    pub fn decide(&self, stats: &ArenaStats) -> SprtDecision {
+        let llr = stats.llr(self);
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            SprtDecision::AcceptH1
+        } else if llr <= lower {
+            SprtDecision::AcceptH0
+        } else {
+            SprtDecision::Continue
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SprtDecision {
+    Continue,
+    AcceptH0,
+    AcceptH1,
+}
+
+/// How many games to play and how
+#[derive(Clone, Debug)]
+pub struct ArenaConfig {
+    pub games: usize,       // Upper limit; games are played in pairs with swapped colors
+    pub seed: u64,          // Seeds the opening order, so runs are reproducible
+    pub max_moves: usize,   // Longer games are adjudicated as draws
+    pub sprt: Option<Sprt>, // Stop early once the test decides (checked after every pair)
+}
+
+impl Default for ArenaConfig {
+    fn default() -> Self {
+        Self { games: 100, seed: 0, max_moves: 300, sprt: None }
+    }
+}
+
+/// Final arena result
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArenaReport {
+    pub stats: ArenaStats,
+    pub sprt: Option<SprtDecision>,
+}
+
+/// Play A against B from `start` (rules as configured there)
+/// Openings are taken from the book in a seeded order, reshuffled after every pass, and each is played
+/// twice, A taking each color once; `on_game` sees every result
+pub fn run_arena<G, A, B>(
+    start: &G,
+    player_a: &A,
+    player_b: &B,
+    config: &ArenaConfig,
+    mut on_game: impl FnMut(&ArenaGameRecord, &ArenaStats),
+) -> ArenaReport
+where
+    G: ArenaGame,
+    A: ArenaPlayer<G>,
+    B: ArenaPlayer<G>,
+{
+    let book = start.opening_book();
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut order: Vec<usize> = (0..book.len()).collect();
+    let mut stats = ArenaStats::default();
+    
+    for index in 0..config.games {
+        let a_moves_first = index % 2 == 0;
+        let pair = index / 2;
+        if a_moves_first && !order.is_empty() && pair % order.len() == 0 {
+            order.shuffle(&mut rng);
+        }
+        let opening = if order.is_empty() { 0 } else { order[pair % order.len()] };
+        let line = book.get(opening).map(Vec::as_slice).unwrap_or_default();
+        let record = play_game(start, player_a, player_b, opening, line, a_moves_first, config.max_moves);
+        stats.add(record.outcome);
+        on_game(&record, &stats);
+        
+        let pair_done = !a_moves_first;
+        if let Some(sprt) = config.sprt.filter(|_| pair_done) {
+            let decision = sprt.decide(&stats);
+            if decision != SprtDecision::Continue {
+                return ArenaReport { stats, sprt: Some(decision) };
+            }
+        }
+    }
+    
+    ArenaReport { stats, sprt: config.sprt.map(|sprt| sprt.decide(&stats)) }
+}
+
+/// One game after the opening line; a player without a legal move loses
+fn play_game<G, A, B>(
+    start: &G,
+    player_a: &A,
+    player_b: &B,
+    opening: usize,
+    line: &[Vec<usize>],
+    a_moves_first: bool,
+    max_moves: usize,
+) -> ArenaGameRecord
+where
+    G: ArenaGame,
+    A: ArenaPlayer<G>,
+    B: ArenaPlayer<G>,
+{
+    let mut game = start.clone();
+    let first = game.first_color();
+    game.restart(first);
+    player_a.new_game();
+    player_b.new_game();
+    
+    let mut moves = Vec::new();
+    for game_move in line {
+        game.apply_move(game_move).expect("opening book lines are legal");
+        moves.push(game_move.clone());
+    }
+    
+    let a_color = if a_moves_first { first } else { first.opponent() };
+    let mut forfeit = None;
+    while game.final_result().is_none() && moves.len() < max_moves {
+        let mover = game.current_player();
+        let choice = if mover == a_color { player_a.choose_move(&game) } else { player_b.choose_move(&game) };
+        match choice {
+            Some(game_move) if game.apply_move(&game_move).is_ok() => moves.push(game_move),
+            _ => {
+                forfeit = Some(mover.opponent());
+                break;
+            },
+        }
+    }
+    
+    let winner = forfeit.or_else(|| game.final_result().and_then(|result| result.winner()));
+    let outcome = match winner {
+        Some(player) if player == a_color => ArenaOutcome::Win,
+        Some(_) => ArenaOutcome::Loss,
+        None => ArenaOutcome::Draw,
+    };
+    ArenaGameRecord { opening, a_moves_first, moves, winner, outcome }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_elo_and_interval() {
+        let stats = ArenaStats { wins: 60, draws: 30, losses: 10 };
+        assert_eq!(stats.games(), 100);
+        assert!((stats.score() - 0.75).abs() < 1e-9);
+        assert!((stats.elo() - 190.85).abs() < 0.01);
+        let (low, high) = stats.elo_interval(1.96);
+        assert!(low < stats.elo() && stats.elo() < high);
+        assert!((score_to_elo(elo_to_score(123.0)) - 123.0).abs() < 1e-9);
+        
+        let even = ArenaStats { wins: 10, draws: 0, losses: 10 };
+        assert_eq!(even.elo(), 0.0);
+        assert_eq!(ArenaStats { wins: 3, draws: 0, losses: 0 }.elo(), f64::INFINITY);
+    }
+    
+    #[test]
+    fn test_sprt_decisions() {
+        let sprt = Sprt::new(0.0, 50.0);
+        let (lower, upper) = sprt.bounds();
+        assert!((upper - 2.944).abs() < 0.001 && (lower + 2.944).abs() < 0.001);
+        
+        assert_eq!(sprt.decide(&ArenaStats { wins: 5, draws: 0, losses: 0 }), SprtDecision::Continue);
+        assert_eq!(sprt.decide(&ArenaStats { wins: 300, draws: 100, losses: 100 }), SprtDecision::AcceptH1);
+        assert_eq!(sprt.decide(&ArenaStats { wins: 100, draws: 100, losses: 300 }), SprtDecision::AcceptH0);
+    }
+    
+    #[test]
+    fn test_seeded_arena_is_reproducible() {
+        let strong = Connect4AI::with_difficulty(crate::ai::connect4_ai::AIDifficulty::Medium);
+        let mut weak = Connect4AI::new();
+        weak.set_difficulty(1);
+        let config = ArenaConfig { games: 4, seed: 7, ..ArenaConfig::default() };
+        
+        let mut records = Vec::new();
+        let report = run_arena(&Connect4Game::new(), &strong, &weak, &config, |record, _| records.push(record.clone()));
+        assert_eq!(report.stats.games(), 4);
+        assert_eq!(report.sprt, None);
+        assert_eq!(records[0].opening, records[1].opening); // Paired games share the opening
+        assert!(records[0].a_moves_first && !records[1].a_moves_first);
+        assert_eq!(records[0].moves[..4], Connect4Game::new().opening_book()[records[0].opening][..]);
+        
+        let mut replay = Vec::new();
+        run_arena(&Connect4Game::new(), &strong, &weak, &config, |record, _| replay.push(record.clone()));
+        assert_eq!(records, replay);
+    }
+    
+    #[test]
+    fn test_long_runs_keep_playing_distinct_games() {
+        let mut quick = Connect4AI::new();
+        quick.set_difficulty(1);
+        let mut deeper = Connect4AI::new();
+        deeper.set_difficulty(2);
+        let start = Connect4Game::new_with_size_internal(4, 4, 3).unwrap();
+        assert_eq!(start.opening_book().len(), 256);
+        assert_eq!(Connect4Game::new().opening_book().len(), 2401);
+        assert_eq!(GomokuGame::new().opening_book().len(), 1012);
+        
+        // More games than book lines: nothing is capped and no game repeats
+        let config = ArenaConfig { games: 300, seed: 3, ..ArenaConfig::default() };
+        let mut games = Vec::new();
+        let report = run_arena(&start, &quick, &deeper, &config, |record, _| games.push((record.a_moves_first, record.moves.clone())));
+        assert_eq!(report.stats.games(), 300);
+        
+        games.sort_unstable();
+        games.dedup();
+        assert_eq!(games.len(), 300);
+    }
+}
@@ -1,7 +1,12 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use crate::games::connect4::Connect4Game;
-use crate::Player;
+use crate::{GameError, Player};
+use crate::ai::evaluator_params::EvaluatorProfile;
 use crate::ai::pattern_evaluator::{PatternEvaluator, GamePhase};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 
 /// AI Strategy types for Stage 4 decision making
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -15,7 +20,7 @@ pub enum AIStrategy {
 
 /// AI Difficulty levels with variable Stage 4 strategies
 /// All difficulties use Stage 1-3 (Win/Block/Safe), but differ in Stage 4
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AIDifficulty {
     Easy,    // 50% Random, 30% Weak MCTS, 20% Medium MCTS
@@ -25,29 +30,31 @@ pub enum AIDifficulty {
 
 /// Connect4 AI implementation using Gemini's pattern-based evaluation
 /// Implements the "Stratege" layer of the Three-Layer Architecture
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct Connect4AI {
     evaluator: PatternEvaluator,
     max_depth: usize,
     ai_player: Player,
     difficulty: AIDifficulty,
+    profile: String,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Connect4AI {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         Self {
             evaluator: PatternEvaluator::new(),
             max_depth: 4, // Default depth for medium difficulty
             ai_player: Player::Red,
             difficulty: AIDifficulty::Medium,
+            profile: "default".to_string(),
         }
     }
     
     /// Create AI with specific difficulty level
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn with_difficulty(difficulty: AIDifficulty) -> Self {
         let default_depth = match difficulty {
             AIDifficulty::Easy => 2,
@@ -60,24 +67,31 @@ impl Connect4AI {
             max_depth: default_depth,
             ai_player: Player::Red,
             difficulty,
+            profile: "default".to_string(),
         }
     }
     
     /// Set the AI player (default: Red)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn set_ai_player(&mut self, player: Player) {
         self.ai_player = player;
     }
     
     /// Set search depth (higher = stronger but slower)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn set_difficulty(&mut self, depth: usize) {
         self.max_depth = depth.clamp(1, 12);
     }
     
+    /// Current search depth in plies
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_depth(&self) -> usize {
+        self.max_depth
+    }
+    
     /// Set AI difficulty level (Easy/Medium/Hard)
     /// This is the preferred way to set AI strength
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn set_difficulty_level(&mut self, difficulty: AIDifficulty) {
         self.difficulty = difficulty;
         self.max_depth = match difficulty {
@@ -88,13 +102,41 @@ impl Connect4AI {
     }
     
     /// Get current difficulty level
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_difficulty_level(&self) -> AIDifficulty {
         self.difficulty
     }
     
+    /// Switch to a built-in personality ("aggressive", "defensive", "positional", "child-friendly", ...)
+    /// The difficulty level and search depth are kept
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_profile(&mut self, name: &str) -> Result<(), GameError> {
+        self.apply_profile(&EvaluatorProfile::built_in(name)?);
+        Ok(())
+    }
+    
+    /// Load a personality from profile JSON (see `EvaluatorProfile::from_json`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn load_profile_json(&mut self, json: &str) -> Result<(), GameError> {
+        self.apply_profile(&EvaluatorProfile::from_json(json)?);
+        Ok(())
+    }
+    
+    /// Load a personality from the bytes of a profile JSON file
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn load_profile_bytes(&mut self, bytes: &[u8]) -> Result<(), GameError> {
+        self.apply_profile(&EvaluatorProfile::from_json_bytes(bytes)?);
+        Ok(())
+    }
+    
+    /// Name of the active personality
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_profile_name(&self) -> String {
+        self.profile.clone()
+    }
+    
     /// Get the best move for the current position
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_best_move(&self, game: &Connect4Game) -> Option<usize> {
         if game.is_game_over() || game.current_player() != self.ai_player {
             return None;
@@ -124,13 +166,13 @@ impl Connect4AI {
     }
     
     /// Get the evaluation score for the current position
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn evaluate_position(&self, game: &Connect4Game) -> i32 {
         self.evaluator.evaluate_with_phase(game, self.ai_player)
     }
     
     /// Get a quick move for time-constrained situations
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_quick_move(&self, game: &Connect4Game) -> Option<usize> {
         if game.is_game_over() || game.current_player() != self.ai_player {
             return None;
@@ -144,6 +186,23 @@ impl Connect4AI {
 
 // Non-WASM methods for internal use and testing
 impl Connect4AI {
+    /// Evaluator weights used by the search
+    pub fn evaluator(&self) -> &PatternEvaluator {
+        &self.evaluator
+    }
+    
+    /// Replace the evaluator (its cache is cleared, as cached scores depend on the weights)
+    pub fn set_evaluator(&mut self, evaluator: PatternEvaluator) {
+        evaluator.clear_cache();
+        self.evaluator = evaluator;
+    }
+    
+    /// Use the Connect4 weights of a profile
+    pub fn apply_profile(&mut self, profile: &EvaluatorProfile) {
+        self.set_evaluator(PatternEvaluator::from_params(&profile.params));
+        self.profile = profile.name.clone();
+    }
+    
     /// Choose Stage 4 strategy based on difficulty and weighted randomness
     pub fn choose_stage4_strategy(&self, _game: &Connect4Game) -> AIStrategy {
         use rand::Rng;
@@ -187,6 +246,18 @@ impl Connect4AI {
             },
         }
     }
+    
+    /// Deepest search that fits a clock's move budget (None if the budget does not limit the search)
+    pub fn depth_limit_for_budget(&self, budget_ms: u64) -> Option<usize> {
+        match budget_ms {
+            0..=99 => Some(2),
+            100..=499 => Some(4),
+            500..=1_999 => Some(6),
+            2_000..=4_999 => Some(8),
+            _ => None,
+        }
+    }
+    
     /// Measure memory usage for AI search at specific depth
     /// This helps us understand memory requirements for different search depths
     pub fn measure_memory_usage(&self, game: &Connect4Game, depth: usize) -> MemoryReport {
@@ -212,6 +283,96 @@ impl Connect4AI {
             search_time_ms: search_time.as_millis(),
         }
     }
+    
+    /// Iterative deepening search for the side to move, reporting every completed depth
+    /// Stops at the first limit reached, at a forced result or when no deeper search is possible;
+    /// returns the deepest completed iteration (None if the game is over or no iteration finished)
+    pub fn search(
+        &self,
+        game: &Connect4Game,
+        limits: &SearchLimits,
+        mut on_iteration: impl FnMut(&SearchInfo),
+    ) -> Option<SearchInfo> {
+        if game.is_game_over() {
+            return None;
+        }
+        
+        let mut ai = self.clone();
+        ai.ai_player = game.current_player();
+        
+        // Without PopOut every ply fills a cell, so the empty cells bound the useful depth
+        let cells = game.get_rows() * game.get_cols();
+        let horizon = if game.is_popout_enabled() { cells } else { cells - game.move_count() };
+        let max_depth = limits.depth.unwrap_or(usize::MAX).min(horizon).max(1);
+        
+        let mut state = SearchState::new(limits);
+        let mut best: Option<SearchInfo> = None;
+        for depth in 1..=max_depth {
+            let hint = best.as_ref().and_then(|info| info.pv.first().copied());
+            let mut pv = Vec::new();
+            let score = ai.search_node(game, depth, 0, i32::MIN, i32::MAX, true, hint, &mut state, &mut pv);
+            if state.aborted || pv.is_empty() {
+                break;
+            }
+            
+            let info = SearchInfo { depth, score, nodes: state.nodes, mate: ai.mate_along(game, &pv), pv };
+            on_iteration(&info);
+            let forced = info.mate.is_some();
+            best = Some(info);
+            if forced || state.out_of_time() {
+                break;
+            }
+        }
+        best
+    }
+}
+
+/// Limits for `Connect4AI::search` and `GomokuAI::search`; unset limits never end the search
+#[derive(Clone, Debug, Default)]
+pub struct SearchLimits {
+    pub depth: Option<usize>,
+    pub nodes: Option<u64>,
+    pub deadline: Option<Instant>,
+    pub stop: Option<Arc<AtomicBool>>, // Set from another thread to abort
+}
+
+/// One completed iteration of `Connect4AI::search`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchInfo {
+    pub depth: usize,
+    pub score: i32,        // From the point of view of the side to move
+    pub nodes: u64,        // Nodes searched so far, over all iterations
+    pub pv: Vec<usize>,    // Principal variation as move numbers (pops are `cols + column`)
+    pub mate: Option<i32>, // Own moves to a forced win (positive) or loss (negative) when the PV ends the game
+}
+
+/// Node counting and abort checks shared by one search
+pub(crate) struct SearchState<'a> {
+    limits: &'a SearchLimits,
+    nodes: u64,
+    pub(crate) aborted: bool,
+}
+
+impl<'a> SearchState<'a> {
+    pub(crate) fn new(limits: &'a SearchLimits) -> Self {
+        Self { limits, nodes: 0, aborted: false }
+    }
+    
+    /// Count a node; true once a limit is reached (the clock and stop flag are polled every 256 nodes and between depths)
+    pub(crate) fn tick(&mut self) -> bool {
+        self.nodes += 1;
+        let limits = self.limits;
+        self.aborted = self.aborted
+            || limits.nodes.is_some_and(|nodes| self.nodes > nodes)
+            || (self.nodes.is_multiple_of(256) && self.out_of_time());
+        self.aborted
+    }
+    
+    /// Whether the deadline has passed or the stop flag is set
+    fn out_of_time(&self) -> bool {
+        self.limits.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            || self.limits.stop.as_ref().is_some_and(|stop| stop.load(Ordering::Relaxed))
+    }
 }
 
 // Internal implementation
@@ -253,8 +414,8 @@ impl Connect4AI {
             let mut max_eval = i32::MIN;
             
             // Try moves in order of likelihood (center first)
-            for column in self.get_move_order() {
-                if !game.is_valid_move(column) {
+            for column in self.get_move_order(game) {
+                if !game.is_legal_move_code(column) {
                     continue;
                 }
                 
@@ -280,8 +441,8 @@ impl Connect4AI {
         } else {
             let mut min_eval = i32::MAX;
             
-            for column in self.get_move_order() {
-                if !game.is_valid_move(column) {
+            for column in self.get_move_order(game) {
+                if !game.is_legal_move_code(column) {
                     continue;
                 }
                 
@@ -325,8 +486,8 @@ impl Connect4AI {
             let mut max_eval = i32::MIN;
             
             // Try moves in order of likelihood (center first)
-            for column in self.get_move_order() {
-                if !game.is_valid_move(column) {
+            for column in self.get_move_order(game) {
+                if !game.is_legal_move_code(column) {
                     continue;
                 }
                 
@@ -351,8 +512,8 @@ impl Connect4AI {
         } else {
             let mut min_eval = i32::MAX;
             
-            for column in self.get_move_order() {
-                if !game.is_valid_move(column) {
+            for column in self.get_move_order(game) {
+                if !game.is_legal_move_code(column) {
                     continue;
                 }
                 
@@ -374,17 +535,105 @@ impl Connect4AI {
         }
     }
     
+    /// Minimax node of `search`: fills `pv` with the best line from here
+    /// Wins are scored by distance so the search prefers the quickest win and the slowest loss
+    #[allow(clippy::too_many_arguments)]
+    fn search_node(
+        &self,
+        game: &Connect4Game,
+        depth: usize,
+        ply: i32,
+        mut alpha: i32,
+        mut beta: i32,
+        maximizing: bool,
+        hint: Option<usize>,
+        state: &mut SearchState,
+        pv: &mut Vec<usize>,
+    ) -> i32 {
+        pv.clear();
+        if depth == 0 || game.is_game_over() {
+            let score = self.evaluator.evaluate_with_phase(game, self.ai_player);
+            return match game.winner() {
+                Some(winner) if winner == self.ai_player => score - ply,
+                Some(_) => score + ply,
+                None => score,
+            };
+        }
+        
+        let mut order = self.get_move_order(game);
+        if let Some(index) = hint.and_then(|first| order.iter().position(|&code| code == first)) {
+            let first = order.remove(index);
+            order.insert(0, first);
+        }
+        
+        let mut best = if maximizing { i32::MIN } else { i32::MAX };
+        let mut child_pv = Vec::new();
+        for column in order {
+            if !game.is_legal_move_code(column) {
+                continue;
+            }
+            if state.tick() {
+                return best;
+            }
+            
+            let game_copy = self.make_move_copy(game, column);
+            let eval = self.search_node(&game_copy, depth - 1, ply + 1, alpha, beta, !maximizing, None, state, &mut child_pv);
+            if state.aborted {
+                return best;
+            }
+            
+            if (maximizing && eval > best) || (!maximizing && eval < best) || pv.is_empty() {
+                best = eval;
+                pv.clear();
+                pv.push(column);
+                pv.extend_from_slice(&child_pv);
+            }
+            if maximizing {
+                alpha = alpha.max(eval);
+            } else {
+                beta = beta.min(eval);
+            }
+            if beta <= alpha {
+                break; // Alpha-beta pruning
+            }
+        }
+        
+        best
+    }
+    
+    /// Signed number of own moves until the PV ends the game with a win (None if it does not)
+    fn mate_along(&self, game: &Connect4Game, pv: &[usize]) -> Option<i32> {
+        let mut position = game.clone();
+        for &code in pv {
+            position = position.make_move_code_copy(code)?;
+        }
+        let own_moves = pv.len().div_ceil(2) as i32;
+        match position.winner()? {
+            winner if winner == self.ai_player => Some(own_moves),
+            _ => Some(-own_moves),
+        }
+    }
+    
     /// Get move ordering for better alpha-beta pruning
-    /// Center columns first, then work outward
-    fn get_move_order(&self) -> Vec<usize> {
-        vec![3, 2, 4, 1, 5, 0, 6] // Center-out ordering
+    /// Center columns first, then work outward (3, 2, 4, 1, 5, 0, 6 on the standard board)
+    /// With PopOut, pops follow the drops as move numbers `cols + column` in the same order
+    pub fn get_move_order(&self, game: &Connect4Game) -> Vec<usize> {
+        let cols = game.get_cols();
+        let mut order: Vec<usize> = (0..cols).collect();
+        order.sort_by_key(|&col| ((2 * col).abs_diff(cols - 1), col));
+        
+        if game.is_popout_enabled() {
+            let pops: Vec<usize> = order.iter().map(|&col| cols + col).collect();
+            order.extend(pops);
+        }
+        order
     }
     
-    /// Create a copy of the game with a move applied
-    /// FIXED: Use Connect4Game's built-in make_move_copy method for thread-safe copying
+    /// Create a copy of the game with a move (drop column or PopOut move number) applied
+    /// FIXED: Use Connect4Game's built-in make_move_code_copy method for thread-safe copying
     fn make_move_copy(&self, game: &Connect4Game, column: usize) -> Connect4Game {
         // Use the safe, built-in method that properly copies all game state
-        game.make_move_copy(column).unwrap_or_else(|| {
+        game.make_move_code_copy(column).unwrap_or_else(|| {
             // Fallback: return the original game if move is invalid
             // This should never happen in practice due to is_valid_move() checks
             game.clone()
@@ -392,10 +641,11 @@ impl Connect4AI {
     }
     
     /// Quick tactical analysis
+    /// Returns a move number (the column for drops, `cols + column` for PopOut pops)
     pub fn find_immediate_win(&self, game: &Connect4Game) -> Option<usize> {
-        // Check each column for immediate win
-        for column in 0..7 {
-            if game.is_valid_move(column) {
+        // Check each move for immediate win
+        for column in 0..2 * game.get_cols() {
+            if game.is_legal_move_code(column) {
                 let test_game = self.make_move_copy(game, column);
                 if test_game.winner() == Some(self.ai_player) {
                     return Some(column);
@@ -405,44 +655,50 @@ impl Connect4AI {
         None
     }
     
+    /// Move numbers that win at once for `player` (drops, plus pops under PopOut)
+    fn immediate_wins_for(&self, game: &Connect4Game, player: Player) -> Vec<usize> {
+        let as_player = game.create_hypothetical_state(player);
+        (0..2 * game.get_cols())
+            .filter(|&code| as_player.make_move_code_copy(code).is_some_and(|test_game| test_game.winner() == Some(player)))
+            .collect()
+    }
+    
     /// Find moves that block opponent's immediate win
+    /// A winning drop is blocked by dropping into its column; a winning pop by any move
+    /// (drop or pop, as a move number) after which that pop no longer wins
     pub fn find_blocking_moves(&self, game: &Connect4Game) -> Vec<usize> {
-        let mut blocking_moves = Vec::new();
         let opponent = self.ai_player.opponent();
-        
-        // For each column, check if opponent would win if they played there
-        for column in 0..7 {
-            if game.is_valid_move(column) {
-                // Check if opponent would win by playing in this column
-                if let Some(test_game) = game.make_move_as_player(column, opponent) {
-                    if test_game.winner() == Some(opponent) {
-                        blocking_moves.push(column);
+        let (mut blocking_moves, pops): (Vec<usize>, Vec<usize>) = self.immediate_wins_for(game, opponent)
+            .into_iter()
+            .partition(|&code| code < game.get_cols());
+        
+        if !pops.is_empty() {
+            let own_turn = game.create_hypothetical_state(self.ai_player);
+            for code in own_turn.get_legal_moves() {
+                if let Some(test_game) = own_turn.make_move_code_copy(code) {
+                    let defused = pops.iter().all(|&pop| {
+                        test_game.make_move_code_copy(pop).is_none_or(|reply| reply.winner() != Some(opponent))
+                    });
+                    if defused && test_game.winner() != Some(opponent) {
+                        blocking_moves.push(code);
                     }
                 }
             }
         }
         
+        blocking_moves.sort();
+        blocking_moves.dedup();
         blocking_moves
     }
     
     /// Find all winning moves for the opponent (for comprehensive threat detection)
+    /// Move numbers as played by the opponent, so pops are `cols + column`
     /// ENHANCED: Includes Zwickmühle (.Y.Y.) pattern detection
     pub fn find_opponent_winning_moves(&self, game: &Connect4Game) -> Vec<usize> {
-        let mut winning_moves = Vec::new();
         let opponent = self.ai_player.opponent();
         
         // Standard immediate win detection
-        for column in 0..7 {
-            if game.is_valid_move(column) {
-                // Create a hypothetical game where it's opponent's turn
-                let hypothetical_game = game.create_hypothetical_state(opponent);
-                if let Some(test_game) = hypothetical_game.make_move_copy(column) {
-                    if test_game.winner() == Some(opponent) {
-                        winning_moves.push(column);
-                    }
-                }
-            }
-        }
+        let mut winning_moves = self.immediate_wins_for(game, opponent);
         
         // CRITICAL: Zwickmühle detection (.Y.Y. patterns)
         let zwickmuehle_threats = self.find_zwickmuehle_threats(game, opponent);
@@ -460,6 +716,11 @@ impl Connect4AI {
     pub fn find_zwickmuehle_threats(&self, game: &Connect4Game, opponent: Player) -> Vec<usize> {
         let mut threat_columns = Vec::new();
         
+        // The .Y.Y. pattern only forms a double threat when four in a row wins
+        if game.get_win_length() != 4 {
+            return threat_columns;
+        }
+        
         // 1. HORIZONTAL Zwickmühle patterns (.Y.Y.)
         threat_columns.extend(self.find_horizontal_zwickmuehle(game, opponent));
         
@@ -478,8 +739,8 @@ impl Connect4AI {
     fn find_horizontal_zwickmuehle(&self, game: &Connect4Game, opponent: Player) -> Vec<usize> {
         let mut threat_columns = Vec::new();
         
-        for row in 0..6 {
-            for start_col in 0..4 { // Pattern needs 4 consecutive positions
+        for row in 0..game.get_rows() {
+            for start_col in 0..game.get_cols() - 3 { // Pattern needs 4 consecutive positions
                 // Check pattern: Empty, Opponent, Empty, Opponent
                 let positions = [
                     (row, start_col),
@@ -514,8 +775,8 @@ impl Connect4AI {
         let mut threat_columns = Vec::new();
         
         // Check ascending diagonals (/) - bottom-left to top-right
-        for start_row in 3..6 { // Must have room for 4 pieces diagonally
-            for start_col in 0..4 {
+        for start_row in 3..game.get_rows() { // Must have room for 4 pieces diagonally
+            for start_col in 0..game.get_cols() - 3 {
                 let positions = [
                     (start_row, start_col),
                     (start_row - 1, start_col + 1),
@@ -539,8 +800,8 @@ impl Connect4AI {
         }
         
         // Check descending diagonals (\) - top-left to bottom-right
-        for start_row in 0..3 { // Must have room for 4 pieces diagonally
-            for start_col in 0..4 {
+        for start_row in 0..game.get_rows() - 3 { // Must have room for 4 pieces diagonally
+            for start_col in 0..game.get_cols() - 3 {
                 let positions = [
                     (start_row, start_col),
                     (start_row + 1, start_col + 1),
@@ -592,7 +853,7 @@ impl Connect4AI {
             // 2. Column is not full (valid move)
             // 3. Piece would land in this row (correct column height)
             if game.get_cell(row, col) == 0 && game.is_valid_move(col) {
-                let expected_landing_row = 6 - 1 - game.get_column_height(col);
+                let expected_landing_row = game.get_rows() - 1 - game.get_column_height(col);
                 if expected_landing_row == row {
                     playable_cols.push(col);
                 }
@@ -643,6 +904,7 @@ impl Default for Connect4AI {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::games::connect4::Connect4Variant;
     
     #[test]
     fn test_ai_initialization() {
@@ -1001,6 +1263,43 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_move_order_follows_board_width() {
+        let ai = Connect4AI::new();
+        
+        assert_eq!(ai.get_move_order(&Connect4Game::new()), vec![3, 2, 4, 1, 5, 0, 6]);
+        assert_eq!(
+            ai.get_move_order(&Connect4Game::new_variant(Connect4Variant::Large8x7)),
+            vec![3, 4, 2, 5, 1, 6, 0, 7]
+        );
+        
+        // The AI must be able to use the outer columns of wide boards
+        let mut game = Connect4Game::new_variant(Connect4Variant::Large10x7);
+        for col in [9, 0, 8, 0, 7] {
+            game.make_move_internal(col).unwrap();
+        }
+        let mut red_ai = Connect4AI::new();
+        red_ai.set_ai_player(Player::Red);
+        assert_eq!(red_ai.find_blocking_moves(&game), vec![6]);
+    }
+    
+    #[test]
+    fn test_popout_extends_move_set() {
+        let ai = Connect4AI::new();
+        let mut game = Connect4Game::new();
+        game.set_popout_enabled(true);
+        assert_eq!(ai.get_move_order(&game), vec![3, 2, 4, 1, 5, 0, 6, 10, 9, 11, 8, 12, 7, 13]);
+        
+        game.make_move_internal(3).unwrap(); // Yellow
+        game.make_move_internal(3).unwrap(); // Red
+        
+        // Search over drops and pops must return a legal move number
+        let mut yellow_ai = Connect4AI::with_difficulty(AIDifficulty::Easy);
+        yellow_ai.set_ai_player(Player::Yellow);
+        let best = yellow_ai.get_best_move(&game).unwrap();
+        assert!(game.is_legal_move_code(best));
+    }
+    
     #[test]
     fn test_difficulty_move_quality() {
         let mut game = Connect4Game::new();
@@ -1037,4 +1336,50 @@ mod tests {
         assert!(game.is_valid_move(medium_move.unwrap()), "Medium AI move should be valid");
         assert!(game.is_valid_move(hard_move.unwrap()), "Hard AI move should be valid");
     }
+    
+    #[test]
+    fn test_search_reports_mate_and_respects_limits() {
+        let ai = Connect4AI::new();
+        let mut game = Connect4Game::new();
+        for column in [0, 0, 1, 1, 2, 2] {
+            game.make_move_internal(column).unwrap();
+        }
+        
+        let mut depths = Vec::new();
+        let info = ai.search(&game, &SearchLimits::default(), |info| depths.push(info.depth)).unwrap();
+        assert_eq!(info.pv, vec![3]);
+        assert_eq!(info.mate, Some(1));
+        assert_eq!(depths, vec![1]); // A forced win ends the deepening
+        
+        // Node and stop limits end the search early, keeping the last completed depth
+        let limits = SearchLimits { nodes: Some(200), ..SearchLimits::default() };
+        let info = ai.search(&Connect4Game::new(), &limits, |_| {}).unwrap();
+        assert!(info.depth >= 1 && info.nodes <= 200);
+        assert_eq!(info.pv.len(), info.depth);
+        
+        // A preset stop flag still yields the first depth but nothing deeper
+        let stop = Arc::new(AtomicBool::new(true));
+        let limits = SearchLimits { depth: Some(8), stop: Some(stop), ..SearchLimits::default() };
+        assert_eq!(ai.search(&Connect4Game::new(), &limits, |_| {}).map(|info| info.depth), Some(1));
+    }
+    
+    #[test]
+    fn test_profiles_change_weights_but_not_depth() {
+        let mut ai = Connect4AI::with_difficulty(AIDifficulty::Hard);
+        assert_eq!(ai.get_profile_name(), "default");
+        
+        ai.set_profile("positional").unwrap();
+        assert_eq!(ai.get_profile_name(), "positional");
+        assert_eq!(ai.evaluator().center_bonus, 20);
+        assert_eq!(ai.get_depth(), 6);
+        
+        ai.load_profile_bytes(br#"{"name": "Robo", "block_threat_bonus": 999}"#).unwrap();
+        assert_eq!(ai.get_profile_name(), "Robo");
+        assert_eq!(ai.evaluator().block_threat_bonus, 999);
+        assert_eq!(ai.evaluator().center_bonus, 5);
+        
+        assert!(ai.set_profile("grumpy").is_err());
+        assert!(ai.load_profile_json("{").is_err());
+        assert_eq!(ai.get_profile_name(), "Robo"); // Failed loads keep the previous profile
+    }
 }
\ No newline at end of file
@@ -0,0 +1,278 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::GameError;
+
+/// Tunable evaluation weights of the Connect4 and Gomoku AIs
+/// Stored as a flat JSON object of integers; keys that are left out keep their default
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvaluatorParams {
+    // PatternEvaluator (Connect4)
+    pub win_score: i32,
+    pub threat_3_score: i32,
+    pub potential_2_score: i32,
+    pub center_bonus: i32,
+    pub block_threat_bonus: i32,
+    
+    // GomokuAI
+    pub threat_weight: i32,
+    pub center_weight: i32,
+    pub defense_weight: i32, // Percent of the opponent's pattern score that counts against the AI
+}
+
+impl EvaluatorParams {
+    /// Every parameter name, as used in JSON
+    pub const NAMES: [&'static str; 8] = [
+        "win_score",
+        "threat_3_score",
+        "potential_2_score",
+        "center_bonus",
+        "block_threat_bonus",
+        "threat_weight",
+        "center_weight",
+        "defense_weight",
+    ];
+    
+    /// Parameters read by `PatternEvaluator`
+    pub const CONNECT4: [&'static str; 5] = ["win_score", "threat_3_score", "potential_2_score", "center_bonus", "block_threat_bonus"];
+    
+    /// Parameters read by `GomokuAI`
+    pub const GOMOKU: [&'static str; 3] = ["threat_weight", "center_weight", "defense_weight"];
+    
+    pub fn get(&self, name: &str) -> Option<i32> {
+        let value = match name {
+            "win_score" => self.win_score,
+            "threat_3_score" => self.threat_3_score,
+            "potential_2_score" => self.potential_2_score,
+            "center_bonus" => self.center_bonus,
+            "block_threat_bonus" => self.block_threat_bonus,
+            "threat_weight" => self.threat_weight,
+            "center_weight" => self.center_weight,
+            "defense_weight" => self.defense_weight,
+            _ => return None,
+        };
+        Some(value)
+    }
+    
+    pub fn set(&mut self, name: &str, value: i32) -> Result<(), GameError> {
+        let field = match name {
+            "win_score" => &mut self.win_score,
+            "threat_3_score" => &mut self.threat_3_score,
+            "potential_2_score" => &mut self.potential_2_score,
+            "center_bonus" => &mut self.center_bonus,
+            "block_threat_bonus" => &mut self.block_threat_bonus,
+            "threat_weight" => &mut self.threat_weight,
+            "center_weight" => &mut self.center_weight,
+            "defense_weight" => &mut self.defense_weight,
+            _ => return Err(GameError::UnknownParameter(name.to_string())),
+        };
+        *field = value;
+        Ok(())
+    }
+    
+    /// JSON object with one parameter per line
+    pub fn to_json(&self) -> String {
+        let fields: Vec<String> = Self::NAMES
+            .iter()
+            .map(|name| format!("  \"{}\": {}", name, self.get(name).unwrap_or_default()))
+            .collect();
+        format!("{{\n{}\n}}\n", fields.join(",\n"))
+    }
+    
+    /// Parse a flat JSON object of integer parameters on top of the defaults
+    /// (profile files are accepted too; their name is dropped)
+    pub fn from_json(text: &str) -> Result<Self, GameError> {
+        EvaluatorProfile::from_json(text).map(|profile| profile.params)
+    }
+    
+    /// Built-in parameter set by profile name
+    pub fn profile(name: &str) -> Result<Self, GameError> {
+        let (connect4, gomoku) = match name {
+            "default" => return Ok(Self::default()),
+            "aggressive" => ([10000, 250, 30, 5, 120], [1500, 10, 60]),
+            "defensive" => ([10000, 80, 10, 5, 400], [900, 10, 160]),
+            "positional" => ([10000, 100, 20, 20, 200], [1000, 40, 100]),
+            "child-friendly" => ([10000, 40, 5, 0, 30], [400, 0, 30]),
+            _ => return Err(GameError::UnknownProfile(name.to_string())),
+        };
+        let [win_score, threat_3_score, potential_2_score, center_bonus, block_threat_bonus] = connect4;
+        let [threat_weight, center_weight, defense_weight] = gomoku;
+        Ok(Self { win_score, threat_3_score, potential_2_score, center_bonus, block_threat_bonus, threat_weight, center_weight, defense_weight })
+    }
+}
+
+impl Default for EvaluatorParams {
+    /// The hand-picked weights of `PatternEvaluator::new()` and `GomokuAI::new()`
+    fn default() -> Self {
+        Self {
+            win_score: 10000,
+            threat_3_score: 100,
+            potential_2_score: 10,
+            center_bonus: 5,
+            block_threat_bonus: 200,
+            threat_weight: 1000,
+            center_weight: 10,
+            defense_weight: 100,
+        }
+    }
+}
+
+/// A named parameter set, i.e. an AI personality
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvaluatorProfile {
+    pub name: String,
+    pub params: EvaluatorParams,
+}
+
+impl EvaluatorProfile {
+    /// Names of the profiles built into the engine
+    pub const BUILT_IN: [&'static str; 5] = ["default", "aggressive", "defensive", "positional", "child-friendly"];
+    
+    pub fn built_in(name: &str) -> Result<Self, GameError> {
+        Ok(Self { name: name.to_string(), params: EvaluatorParams::profile(name)? })
+    }
+    
+    /// Profile from JSON: optional `"name"` (else the base's name, or "custom"), optional `"base"`
+    /// (a built-in profile to start from), and integer parameters overriding the base
+    pub fn from_json(text: &str) -> Result<Self, GameError> {
+        let pairs = parse_flat_object(text)?;
+        let mut profile = match pairs.iter().find(|(key, _)| key == "base") {
+            Some((_, JsonValue::Text(base))) => Self::built_in(base)?,
+            Some(_) => return Err(GameError::MalformedParameters),
+            None => Self { name: "custom".to_string(), params: EvaluatorParams::default() },
+        };
+        for (name, value) in pairs {
+            match (name.as_str(), value) {
+                ("base", _) => {},
+                ("name", JsonValue::Text(text)) => profile.name = text,
+                ("name", _) => return Err(GameError::MalformedParameters),
+                (_, JsonValue::Integer(value)) => {
+                    let value = i32::try_from(value).map_err(|_| GameError::MalformedParameters)?;
+                    profile.params.set(&name, value)?;
+                },
+                (_, JsonValue::Text(_)) => return Err(GameError::MalformedParameters),
+            }
+        }
+        Ok(profile)
+    }
+    
+    /// Profile from the bytes of a UTF-8 JSON file
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, GameError> {
+        let text = std::str::from_utf8(bytes).map_err(|_| GameError::MalformedParameters)?;
+        Self::from_json(text)
+    }
+    
+    /// JSON object with the name first, then one parameter per line
+    pub fn to_json(&self) -> String {
+        let params = self.params.to_json();
+        format!("{{\n  \"name\": \"{}\",{}", self.name, &params[1..])
+    }
+}
+
+/// Names of the built-in AI profiles, e.g. for a personality picker
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn ai_profile_names() -> Vec<String> {
+    EvaluatorProfile::BUILT_IN.iter().map(|name| name.to_string()).collect()
+}
+
+/// Value in a flat parameter object
+#[derive(Clone, Debug, PartialEq)]
+enum JsonValue {
+    Integer(i64),
+    Text(String),
+}
+
+/// `{"key": integer or "string", ...}` as (key, value) pairs in order (no nesting, no string escapes)
+fn parse_flat_object(text: &str) -> Result<Vec<(String, JsonValue)>, GameError> {
+    let mut rest = text.trim().strip_prefix('{').ok_or(GameError::MalformedParameters)?.trim_start();
+    let mut pairs = Vec::new();
+    if let Some(after) = rest.strip_prefix('}') {
+        return after.trim().is_empty().then_some(pairs).ok_or(GameError::MalformedParameters);
+    }
+    
+    loop {
+        let after_quote = rest.strip_prefix('"').ok_or(GameError::MalformedParameters)?;
+        let (key, after_key) = after_quote.split_once('"').ok_or(GameError::MalformedParameters)?;
+        rest = after_key.trim_start().strip_prefix(':').ok_or(GameError::MalformedParameters)?.trim_start();
+        
+        if let Some(after_quote) = rest.strip_prefix('"') {
+            let (value, after_value) = after_quote.split_once('"').ok_or(GameError::MalformedParameters)?;
+            pairs.push((key.to_string(), JsonValue::Text(value.to_string())));
+            rest = after_value.trim_start();
+        } else {
+            let digits = rest.char_indices()
+                .take_while(|&(index, ch)| ch.is_ascii_digit() || (index == 0 && ch == '-'))
+                .count();
+            let value = rest[..digits].parse::<i64>().map_err(|_| GameError::MalformedParameters)?;
+            pairs.push((key.to_string(), JsonValue::Integer(value)));
+            rest = rest[digits..].trim_start();
+        }
+        
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if let Some(after) = rest.strip_prefix('}') {
+            return after.trim().is_empty().then_some(pairs).ok_or(GameError::MalformedParameters);
+        } else {
+            return Err(GameError::MalformedParameters);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{GomokuAI, PatternEvaluator};
+    
+    #[test]
+    fn test_json_round_trip_and_errors() {
+        let params = EvaluatorParams { center_bonus: 7, threat_weight: -3, ..EvaluatorParams::default() };
+        assert_eq!(EvaluatorParams::from_json(&params.to_json()), Ok(params));
+        
+        // Missing keys keep their defaults
+        let partial = EvaluatorParams::from_json(r#"{ "center_weight": 12 }"#).unwrap();
+        assert_eq!(partial, EvaluatorParams { center_weight: 12, ..EvaluatorParams::default() });
+        assert_eq!(EvaluatorParams::from_json("{}"), Ok(EvaluatorParams::default()));
+        
+        assert_eq!(EvaluatorParams::from_json(r#"{"centre": 1}"#), Err(GameError::UnknownParameter("centre".to_string())));
+        for malformed in ["", "[]", r#"{"win_score": }"#, r#"{"win_score": 1.5}"#, r#"{"win_score": 1,}"#, r#"{"win_score": 1} x"#, r#"{"win_score": 99999999999}"#] {
+            assert_eq!(EvaluatorParams::from_json(malformed), Err(GameError::MalformedParameters), "{}", malformed);
+        }
+    }
+    
+    #[test]
+    fn test_defaults_match_evaluators() {
+        let params = EvaluatorParams::default();
+        let evaluator = PatternEvaluator::new();
+        let loaded = PatternEvaluator::from_params(&params);
+        assert_eq!((loaded.win_score, loaded.loss_score), (evaluator.win_score, evaluator.loss_score));
+        assert_eq!(loaded.block_threat_bonus, evaluator.block_threat_bonus);
+        
+        let game = crate::games::GomokuGame::new().make_move_copy(7, 7).unwrap();
+        let player = crate::Player::Black;
+        assert_eq!(GomokuAI::new().with_params(&params).evaluate_position(&game, player), GomokuAI::new().evaluate_position(&game, player));
+    }
+    
+    #[test]
+    fn test_profiles_from_names_json_and_bytes() {
+        for name in EvaluatorProfile::BUILT_IN {
+            assert_eq!(EvaluatorProfile::built_in(name).unwrap().name, name);
+        }
+        assert_eq!(EvaluatorParams::profile("default"), Ok(EvaluatorParams::default()));
+        assert_ne!(EvaluatorParams::profile("aggressive"), EvaluatorParams::profile("defensive"));
+        assert_eq!(EvaluatorParams::profile("grumpy"), Err(GameError::UnknownProfile("grumpy".to_string())));
+        assert_eq!(ai_profile_names().len(), EvaluatorProfile::BUILT_IN.len());
+        
+        // A named variation of a built-in profile
+        let profile = EvaluatorProfile::from_json(r#"{"name": "Grandpa", "base": "defensive", "center_bonus": 9}"#).unwrap();
+        let defensive = EvaluatorParams::profile("defensive").unwrap();
+        assert_eq!(profile, EvaluatorProfile { name: "Grandpa".to_string(), params: EvaluatorParams { center_bonus: 9, ..defensive } });
+        assert_eq!(EvaluatorProfile::from_json_bytes(profile.to_json().as_bytes()), Ok(profile));
+        
+        assert_eq!(EvaluatorProfile::from_json(r#"{"base": "positional"}"#).unwrap().name, "positional");
+        assert_eq!(EvaluatorProfile::from_json(r#"{"center_weight": 3}"#).unwrap().name, "custom");
+        assert_eq!(EvaluatorParams::from_json(r#"{"name": "x", "threat_weight": 5}"#).unwrap().threat_weight, 5);
+        assert_eq!(EvaluatorProfile::from_json(r#"{"base": "grumpy"}"#), Err(GameError::UnknownProfile("grumpy".to_string())));
+        assert_eq!(EvaluatorProfile::from_json(r#"{"name": 3}"#), Err(GameError::MalformedParameters));
+        assert_eq!(EvaluatorProfile::from_json(r#"{"win_score": "high"}"#), Err(GameError::MalformedParameters));
+        assert_eq!(EvaluatorProfile::from_json_bytes(&[b'{', 0xff, b'}']), Err(GameError::MalformedParameters));
+    }
+}
@@ -1,31 +1,72 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::ai::connect4_ai::{SearchLimits, SearchState};
+use crate::ai::evaluator_params::{EvaluatorParams, EvaluatorProfile};
 use crate::games::GomokuGame;
-use crate::{Player};
+use crate::games::gomoku_threats::ThreatKind;
+use crate::{GameError, Player};
 use crate::geometry::BoardGeometry;
 use std::cmp;
 
 /// Gomoku AI implementation using pattern-based evaluation
 /// Focuses on 5-in-a-row winning patterns and threat detection
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug)]
 pub struct GomokuAI {
     max_depth: usize,
     use_pattern_evaluation: bool,
     threat_weight: i32,
     center_weight: i32,
+    defense_weight: i32,
     opening_book: bool,
+    profile: String,
 }
 
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl GomokuAI {
     /// Create a new Gomoku AI with default settings
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         Self {
             max_depth: 4,           // 4-move lookahead for good performance
             use_pattern_evaluation: true,
             threat_weight: 1000,    // High weight for threats
             center_weight: 10,      // Moderate center control
+            defense_weight: 100,    // Opponent patterns count as much as our own
             opening_book: true,     // Use opening book for first moves
+            profile: "default".to_string(),
         }
     }
     
+    /// Switch to a built-in personality ("aggressive", "defensive", "positional", "child-friendly", ...)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_profile(&mut self, name: &str) -> Result<(), GameError> {
+        self.apply_profile(&EvaluatorProfile::built_in(name)?);
+        Ok(())
+    }
+    
+    /// Load a personality from profile JSON (see `EvaluatorProfile::from_json`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn load_profile_json(&mut self, json: &str) -> Result<(), GameError> {
+        self.apply_profile(&EvaluatorProfile::from_json(json)?);
+        Ok(())
+    }
+    
+    /// Load a personality from the bytes of a profile JSON file
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn load_profile_bytes(&mut self, bytes: &[u8]) -> Result<(), GameError> {
+        self.apply_profile(&EvaluatorProfile::from_json_bytes(bytes)?);
+        Ok(())
+    }
+    
+    /// Name of the active personality
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_profile_name(&self) -> String {
+        self.profile.clone()
+    }
+}
+
+impl GomokuAI {
     /// Create AI with custom depth
     pub fn new_with_depth(depth: usize) -> Self {
         let mut ai = Self::new();
@@ -33,6 +74,36 @@ impl GomokuAI {
         ai
     }
     
+    /// Deepest search that fits a clock's move budget (None if the budget does not limit the search)
+    pub fn depth_limit_for_budget(&self, budget_ms: u64) -> Option<usize> {
+        match budget_ms {
+            0..=299 => Some(1),
+            300..=1_499 => Some(2),
+            1_500..=4_999 => Some(3),
+            _ => None,
+        }
+    }
+    
+    /// Copy of this AI using the Gomoku weights of a parameter set
+    pub fn with_params(&self, params: &EvaluatorParams) -> Self {
+        Self {
+            threat_weight: params.threat_weight,
+            center_weight: params.center_weight,
+            defense_weight: params.defense_weight,
+            ..self.clone()
+        }
+    }
+    
+    /// Use the Gomoku weights of a profile (the search depth is kept)
+    pub fn apply_profile(&mut self, profile: &EvaluatorProfile) {
+        *self = Self { profile: profile.name.clone(), ..self.with_params(&profile.params) };
+    }
+    
+    /// Copy of this AI searching at most `limit` plies
+    pub fn with_depth_limit(&self, limit: usize) -> Self {
+        Self { max_depth: self.max_depth.min(limit.max(1)), ..self.clone() }
+    }
+    
     /// Get the best move for the current player
     pub fn get_best_move(&self, game: &GomokuGame) -> Option<(usize, usize)> {
         self.get_best_move_for_player(game, game.current_player())
@@ -40,6 +111,22 @@ impl GomokuAI {
     
     /// Get the best move for a specific player (bidirectional AI)
     pub fn get_best_move_for_player(&self, game: &GomokuGame, player: Player) -> Option<(usize, usize)> {
+        self.search_for_player(game, player, &SearchLimits::default())
+    }
+    
+    /// Best move for the current player within `limits` (the AI's own depth still caps the search)
+    /// With a deadline, stop flag or node limit the minimax deepens one ply at a time and returns
+    /// the move of the deepest completed iteration once a limit is hit
+    pub fn search(&self, game: &GomokuGame, limits: &SearchLimits) -> Option<(usize, usize)> {
+        self.search_for_player(game, game.current_player(), limits)
+    }
+    
+    fn search_for_player(&self, game: &GomokuGame, player: Player, limits: &SearchLimits) -> Option<(usize, usize)> {
+        // Misère flips "win" and "block", so it has its own stages
+        if game.is_misere_enabled() {
+            return self.get_misere_move(game, player, limits);
+        }
+        
         // Handle opening moves with opening book
         if self.opening_book && game.move_count() < 3 {
             return self.get_opening_move(game);
@@ -67,7 +154,42 @@ impl GomokuAI {
         }
         
         // Use minimax for strategic evaluation
-        self.minimax_search(game, player)
+        self.minimax_search(game, player, limits)
+    }
+    
+    /// Misère move selection (completing five loses)
+    /// STAGE 1: never complete an own five
+    /// STAGE 2: leave the opponent's completion points empty - only they can't play there
+    /// STAGE 3: avoid own fours, which create points we can never fill
+    /// STAGE 4: minimax on the inverted evaluation
+    fn get_misere_move(&self, game: &GomokuGame, player: Player, limits: &SearchLimits) -> Option<(usize, usize)> {
+        let opponent = player.opponent();
+        let candidates = self.generate_candidate_moves(game);
+        
+        let non_losing: Vec<(usize, usize)> = candidates
+            .iter()
+            .copied()
+            .filter(|&(row, col)| self.longest_line_through(game, player, row, col) < 5)
+            .collect();
+        if non_losing.is_empty() {
+            // Every move completes an own five - the game is lost anyway
+            return candidates.first().copied();
+        }
+        
+        let quiet = Self::prefer(non_losing, |row, col| self.longest_line_through(game, opponent, row, col) < 5);
+        let no_fours = Self::prefer(quiet, |row, col| !self.creates_four_in_row(game, player, row, col));
+        
+        self.minimax_search_candidates(game, player, no_fours, limits)
+    }
+    
+    /// Keep the moves matching `keep`, unless that would leave none
+    fn prefer(moves: Vec<(usize, usize)>, keep: impl Fn(usize, usize) -> bool) -> Vec<(usize, usize)> {
+        let filtered: Vec<(usize, usize)> = moves.iter().copied().filter(|&(row, col)| keep(row, col)).collect();
+        if filtered.is_empty() {
+            moves
+        } else {
+            filtered
+        }
     }
     
     /// Opening book for first few moves
@@ -98,45 +220,51 @@ impl GomokuAI {
         }
     }
     
-    /// Find immediate winning move (5-in-a-row)
+    /// Find immediate winning move (5-in-a-row) for either player
     fn find_winning_move(&self, game: &GomokuGame, player: Player) -> Option<(usize, usize)> {
-        for row in 0..15 {
-            for col in 0..15 {
-                if game.is_valid_move(row, col) {
-                    // Simulate the move
-                    if let Some(test_game) = game.make_move_copy(row, col) {
-                        if test_game.winner() == Some(player) {
-                            return Some((row, col));
-                        }
-                    }
-                }
-            }
-        }
-        None
+        self.find_move_creating(game, player, &[ThreatKind::Five])
     }
     
-    /// Find threat moves (4-in-a-row that can become 5)
+    /// Find threat moves: an open four first, otherwise a four that can become 5
     fn find_threat_move(&self, game: &GomokuGame, player: Player) -> Option<(usize, usize)> {
-        // Find threat moves by checking patterns
+        self.find_move_creating(game, player, &[ThreatKind::Five, ThreatKind::OpenFour, ThreatKind::Four])
+    }
+    
+    /// First move creating the strongest of the given threat kinds (kinds ordered strongest first)
+    fn find_move_creating(&self, game: &GomokuGame, player: Player, kinds: &[ThreatKind]) -> Option<(usize, usize)> {
+        let mut best: Option<(ThreatKind, (usize, usize))> = None;
         
-        // Check for 4-in-a-row patterns that can be extended
         for row in 0..15 {
             for col in 0..15 {
-                if game.is_valid_move(row, col) {
-                    // Check if this position completes a 4-in-a-row
-                    if self.creates_four_in_row(game, player, row, col) {
-                        return Some((row, col));
-                    }
+                if !game.is_valid_move(row, col) {
+                    continue;
+                }
+                let threats = game.get_threats_after_move(row, col, player);
+                let Some(kind) = threats.first().map(|threat| threat.kind()).filter(|kind| kinds.contains(kind)) else {
+                    continue;
+                };
+                // Under exact-five rules an overline is not a win
+                if kind == ThreatKind::Five && game.is_exact_five_enabled() && !game.completes_five(row, col, player) {
+                    continue;
+                }
+                if best.is_none_or(|(best_kind, _)| kind < best_kind) {
+                    best = Some((kind, (row, col)));
                 }
             }
         }
         
-        None
+        best.map(|(_, position)| position)
     }
     
     /// Check if a move creates a 4-in-a-row pattern (optimized)
     fn creates_four_in_row(&self, game: &GomokuGame, player: Player, row: usize, col: usize) -> bool {
+        self.longest_line_through(game, player, row, col) >= 4
+    }
+    
+    /// Longest unbroken line a stone of `player` at (row, col) would be part of
+    fn longest_line_through(&self, game: &GomokuGame, player: Player, row: usize, col: usize) -> usize {
         let directions = [(0, 1), (1, 0), (1, 1), (1, -1)]; // horizontal, vertical, diagonal
+        let mut longest = 0;
         
         // Get direct board access for efficiency
         let player_board = game.get_board_for_player(player);
@@ -183,43 +311,68 @@ impl GomokuAI {
                 }
             }
             
-            if count >= 4 {
-                return true;
-            }
+            longest = longest.max(count);
         }
         
-        false
+        longest
     }
     
     /// Minimax search with alpha-beta pruning
-    fn minimax_search(&self, game: &GomokuGame, player: Player) -> Option<(usize, usize)> {
-        let mut best_move = None;
-        let mut best_score = i32::MIN;
-        let alpha = i32::MIN;
-        let beta = i32::MAX;
-        
+    fn minimax_search(&self, game: &GomokuGame, player: Player, limits: &SearchLimits) -> Option<(usize, usize)> {
         // Generate candidate moves (prioritize center and adjacent moves)
         let candidates = self.generate_candidate_moves(game);
+        self.minimax_search_candidates(game, player, candidates, limits)
+    }
+    
+    /// Minimax search restricted to the given root moves
+    /// Without limits that can end it early, only the full depth is searched
+    fn minimax_search_candidates(&self, game: &GomokuGame, player: Player, mut candidates: Vec<(usize, usize)>,
+                                 limits: &SearchLimits) -> Option<(usize, usize)> {
+        let max_depth = limits.depth.map_or(self.max_depth, |depth| depth.min(self.max_depth)).max(1);
+        let interruptible = limits.deadline.is_some() || limits.stop.is_some() || limits.nodes.is_some();
+        let first_depth = if interruptible { 1 } else { max_depth };
+        let mut state = SearchState::new(limits);
+        let mut best_move = None;
         
-        for (row, col) in candidates {
-            if game.is_valid_move(row, col) {
+        for depth in first_depth..=max_depth {
+            let mut iteration_best = None;
+            let mut best_score = i32::MIN;
+            for &(row, col) in &candidates {
                 if let Some(test_game) = game.make_move_copy(row, col) {
-                    let score = self.minimax(&test_game, self.max_depth - 1, alpha, beta, false, player);
-                    
+                    let score = self.minimax(&test_game, depth - 1, i32::MIN, i32::MAX, false, player, &mut state);
+                    if state.aborted {
+                        break;
+                    }
                     if score > best_score {
                         best_score = score;
-                        best_move = Some((row, col));
+                        iteration_best = Some((row, col));
                     }
                 }
             }
+            
+            if state.aborted {
+                // An unfinished iteration only counts if no earlier one completed
+                return best_move.or(iteration_best).or_else(|| candidates.first().copied());
+            }
+            best_move = iteration_best;
+            
+            // Search the best move first in the next iteration
+            if let Some(best) = best_move {
+                candidates.retain(|&candidate| candidate != best);
+                candidates.insert(0, best);
+            }
         }
         
         best_move
     }
     
-    /// Minimax algorithm with alpha-beta pruning
+    /// Minimax algorithm with alpha-beta pruning (returns 0 once the search is aborted)
+    #[allow(clippy::too_many_arguments)]
     fn minimax(&self, game: &GomokuGame, depth: usize, mut alpha: i32, mut beta: i32, 
-               maximizing: bool, original_player: Player) -> i32 {
+               maximizing: bool, original_player: Player, state: &mut SearchState) -> i32 {
+        if state.tick() {
+            return 0;
+        }
         
         // Terminal conditions
         if depth == 0 || game.is_game_over() {
@@ -233,7 +386,7 @@ impl GomokuAI {
             for (row, col) in candidates.into_iter().take(20) { // Limit branching factor
                 if game.is_valid_move(row, col) {
                     if let Some(test_game) = game.make_move_copy(row, col) {
-                        let eval = self.minimax(&test_game, depth - 1, alpha, beta, false, original_player);
+                        let eval = self.minimax(&test_game, depth - 1, alpha, beta, false, original_player, state);
                         max_eval = cmp::max(max_eval, eval);
                         alpha = cmp::max(alpha, eval);
                         
@@ -252,7 +405,7 @@ impl GomokuAI {
             for (row, col) in candidates.into_iter().take(20) { // Limit branching factor
                 if game.is_valid_move(row, col) {
                     if let Some(test_game) = game.make_move_copy(row, col) {
-                        let eval = self.minimax(&test_game, depth - 1, alpha, beta, true, original_player);
+                        let eval = self.minimax(&test_game, depth - 1, alpha, beta, true, original_player, state);
                         min_eval = cmp::min(min_eval, eval);
                         beta = cmp::min(beta, eval);
                         
@@ -349,12 +502,17 @@ impl GomokuAI {
         
         // Evaluate patterns for both players
         score += self.evaluate_patterns(game, player);
-        score -= self.evaluate_patterns(game, player.opponent());
+        score -= self.evaluate_patterns(game, player.opponent()).saturating_mul(self.defense_weight) / 100;
         
         // Add center control bonus
         score += self.evaluate_center_control(game, player);
         
-        score
+        // Misère: own lines are liabilities, the opponent's are assets
+        if game.is_misere_enabled() {
+            -score
+        } else {
+            score
+        }
     }
     
     /// Evaluate patterns for a specific player (optimized with direct board access)
@@ -440,12 +598,13 @@ impl GomokuAI {
             
             consecutive += pos_count + neg_count;
             
-            // Score based on pattern strength
+            // Score based on pattern strength, scaled by the threat weight (a closed four scores it once)
+            let threat = self.threat_weight;
             match consecutive {
                 5 => score += 100000,        // Five in a row (win)
-                4 => score += if open_ends >= 1 { 10000 } else { 1000 }, // Four in a row
-                3 => score += if open_ends >= 2 { 1000 } else { 100 },   // Three in a row
-                2 => score += if open_ends >= 2 { 100 } else { 10 },     // Two in a row
+                4 => score += if open_ends >= 1 { threat * 10 } else { threat },       // Four in a row
+                3 => score += if open_ends >= 2 { threat } else { threat / 10 },       // Three in a row
+                2 => score += if open_ends >= 2 { threat / 10 } else { threat / 100 }, // Two in a row
                 _ => {}
             }
         }
@@ -487,35 +646,21 @@ impl GomokuAI {
         score
     }
     
-    /// Get threat level for a position
+    /// Get threat level for a position, based on the strongest threat the move creates
+    /// 5 = five, 4 = four or double three, 3 = open/split three, 2 = closed three or two, 1 = basic move
     pub fn get_threat_level(&self, game: &GomokuGame, player: Player, row: usize, col: usize) -> u8 {
         if !game.is_valid_move(row, col) {
             return 0;
         }
         
-        // Check if this creates a winning move
-        if let Some(test_game) = game.make_move_copy(row, col) {
-            if test_game.winner() == Some(player) {
-                return 5; // Immediate win
-            }
-        }
-        
-        // Check if this creates a 4-in-a-row threat
-        if self.creates_four_in_row(game, player, row, col) {
-            return 4; // Strong threat
-        }
-        
-        // Check for 3-in-a-row patterns
-        if self.creates_three_in_row(game, player, row, col) {
-            return 3; // Moderate threat
+        let threats = game.get_threats_after_move(row, col, player);
+        match threats.first().map(|threat| threat.kind()) {
+            Some(ThreatKind::Five) => 5, // Immediate win
+            Some(ThreatKind::OpenFour | ThreatKind::Four | ThreatKind::DoubleThree) => 4, // Strong threat
+            Some(ThreatKind::OpenThree | ThreatKind::SplitThree) => 3, // Moderate threat
+            None if self.creates_three_in_row(game, player, row, col) || self.creates_two_in_row(game, player, row, col) => 2,
+            None => 1, // Basic move
         }
-        
-        // Check for 2-in-a-row patterns
-        if self.creates_two_in_row(game, player, row, col) {
-            return 2; // Weak threat
-        }
-        
-        1 // Basic move
     }
     
     /// Check if move creates 3-in-a-row (optimized)
@@ -662,6 +807,61 @@ mod tests {
         assert_eq!(move1, Some((7, 7)));
     }
     
+    #[test]
+    fn test_misere_stages() {
+        let ai = GomokuAI::new_with_depth(2);
+        let mut game = GomokuGame::new();
+        game.set_misere_enabled(true);
+        for col in 5..9 {
+            game.make_move_internal(7, col).unwrap(); // Black
+            game.make_move_internal(8, col).unwrap(); // White
+        }
+        
+        // Both open fours: Black must not complete its own, nor fill White's poison points
+        let (row, col) = ai.get_best_move(&game).unwrap();
+        assert!(![(7, 4), (7, 9), (8, 4), (8, 9)].contains(&(row, col)));
+        assert_eq!(game.make_move_internal(row, col), Ok(false));
+        
+        // Misère scoring inverts the objective
+        let misere_score = ai.evaluate_position(&game, Player::White);
+        game.set_misere_enabled(false);
+        assert_eq!(ai.evaluate_position(&game, Player::White), -misere_score);
+    }
+    
+    #[test]
+    fn test_blocks_opponent_four() {
+        let ai = GomokuAI::new();
+        let mut game = GomokuGame::new();
+        for (row, col) in [(0, 0), (7, 4), (0, 2), (7, 5), (0, 4), (7, 6), (14, 14), (7, 7)] {
+            game.make_move_internal(row, col).unwrap();
+        }
+        
+        // White's open four can't be stopped, but Black must still cover a completion point
+        let (row, col) = ai.get_best_move(&game).unwrap();
+        assert!([(7, 3), (7, 8)].contains(&(row, col)));
+    }
+    
+    #[test]
+    fn test_search_stops_on_limits() {
+        use std::sync::Arc;
+        use std::sync::atomic::AtomicBool;
+        
+        let ai = GomokuAI::new();
+        let mut game = GomokuGame::new();
+        for (row, col) in [(7, 7), (8, 8), (11, 3), (3, 11)] {
+            game.make_move_internal(row, col).unwrap();
+        }
+        
+        // A preset stop flag ends the first iteration early, which still yields a legal move
+        let limits = SearchLimits { stop: Some(Arc::new(AtomicBool::new(true))), ..SearchLimits::default() };
+        let (row, col) = ai.search(&game, &limits).unwrap();
+        assert!(game.is_valid_move(row, col));
+        
+        // A shallow depth limit searches like an AI of that depth
+        let limits = SearchLimits { depth: Some(1), ..SearchLimits::default() };
+        assert_eq!(ai.search(&game, &limits), GomokuAI::new_with_depth(1).get_best_move(&game));
+    }
+    
     #[test]
     fn test_winning_move_detection() {
         let ai = GomokuAI::new();
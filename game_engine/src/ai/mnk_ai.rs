@@ -0,0 +1,241 @@
+use crate::games::MnkGame;
+use crate::Player;
+
+/// Score of a won position (the remaining search depth is added so faster wins rank higher)
+const WIN_SCORE: i32 = 1_000_000;
+
+/// Shared AI for all m,n,k configurations (tic-tac-toe, Connect-k, Gomoku variants)
+/// Scores the geometry's k-length lines and searches with alpha-beta negamax
+#[derive(Clone, Debug)]
+pub struct MnkAI {
+    max_depth: usize,
+    max_candidates: usize, // Branching limit per node for large boards
+}
+
+impl MnkAI {
+    /// Create a new AI with default settings
+    pub fn new() -> Self {
+        Self {
+            max_depth: 4,
+            max_candidates: 12,
+        }
+    }
+    
+    /// Create AI with custom depth
+    pub fn new_with_depth(depth: usize) -> Self {
+        let mut ai = Self::new();
+        ai.max_depth = depth.max(1);
+        ai
+    }
+    
+    /// Get the best move (cell index) for the current player
+    pub fn get_best_move(&self, game: &MnkGame) -> Option<usize> {
+        if game.is_game_over() {
+            return None;
+        }
+        let player = game.current_player();
+        let legal_moves = game.get_legal_moves();
+        
+        // STAGE 1: Immediate win
+        if let Some(&index) = legal_moves.iter().find(|&&index| game.would_complete_line(player, index)) {
+            return Some(index);
+        }
+        
+        // STAGE 2: Block the opponent's immediate win
+        let opponent = player.opponent();
+        if let Some(&index) = legal_moves.iter().find(|&&index| game.would_complete_line(opponent, index)) {
+            return Some(index);
+        }
+        
+        // STAGE 3: Alpha-beta search on a scratch copy
+        let mut scratch = game.clone();
+        let mut best_move = None;
+        let mut alpha = -WIN_SCORE * 2;
+        let beta = WIN_SCORE * 2;
+        for index in self.generate_candidate_moves(game) {
+            if scratch.make_move_index(index).is_err() {
+                continue;
+            }
+            let score = -self.negamax(&mut scratch, opponent, self.max_depth - 1, -beta, -alpha);
+            scratch.undo_move();
+            
+            if best_move.is_none() || score > alpha {
+                alpha = score;
+                best_move = Some(index);
+            }
+        }
+        
+        best_move
+    }
+    
+    /// Negamax with alpha-beta pruning; scores are from the view of `to_move`
+    fn negamax(&self, game: &mut MnkGame, to_move: Player, depth: usize, mut alpha: i32, beta: i32) -> i32 {
+        if let Some(winner) = game.winner() {
+            let score = WIN_SCORE + depth as i32;
+            return if winner == to_move { score } else { -score };
+        }
+        if game.is_draw() {
+            return 0;
+        }
+        if depth == 0 {
+            return self.evaluate_position(game, to_move);
+        }
+        
+        let mut best = -WIN_SCORE * 2;
+        for index in self.generate_candidate_moves(game) {
+            if game.make_move_index(index).is_err() {
+                continue;
+            }
+            let score = -self.negamax(game, to_move.opponent(), depth - 1, -beta, -alpha);
+            game.undo_move();
+            
+            best = best.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break; // Alpha-beta pruning
+            }
+        }
+        
+        best
+    }
+    
+    /// Evaluate position for a specific player using the pre-computed k-length lines
+    pub fn evaluate_position(&self, game: &MnkGame, player: Player) -> i32 {
+        if let Some(winner) = game.winner() {
+            return if winner == player { WIN_SCORE } else { -WIN_SCORE };
+        }
+        
+        let win_length = game.get_win_length() as u32;
+        let own_board = game.get_board_for_player(player);
+        let opponent_board = game.get_board_for_player(player.opponent());
+        let mut score = 0;
+        
+        for line in game.geometry().get_winning_lines(game.get_win_length()) {
+            let own = own_board.count_set_bits_in_mask(line);
+            let opponent = opponent_board.count_set_bits_in_mask(line);
+            
+            // Only lines still open for one side count
+            if own > 0 && opponent == 0 {
+                score += Self::line_value(win_length - own);
+            } else if opponent > 0 && own == 0 {
+                score -= Self::line_value(win_length - opponent);
+            }
+        }
+        
+        score
+    }
+    
+    /// Value of an open line by pieces still missing
+    fn line_value(missing: u32) -> i32 {
+        match missing {
+            0 => WIN_SCORE, // Impossible (game would be over)
+            1 => 1000,      // Threat
+            2 => 100,       // Building potential
+            3 => 10,
+            _ => 1,
+        }
+    }
+    
+    /// Candidate moves ordered by promise, limited to `max_candidates`
+    /// Free placement only considers cells near existing pieces
+    fn generate_candidate_moves(&self, game: &MnkGame) -> Vec<usize> {
+        let cols = game.get_cols();
+        let center = (game.get_rows() as i32 / 2, cols as i32 / 2);
+        let near_stones = !game.is_gravity_enabled() && game.move_count() > 0;
+        
+        let mut candidates: Vec<(i32, usize)> = game
+            .get_legal_moves()
+            .into_iter()
+            .filter(|&index| !near_stones || self.has_neighbor(game, index, 2))
+            .map(|index| {
+                let (row, col) = ((index / cols) as i32, (index % cols) as i32);
+                let center_distance = (row - center.0).abs() + (col - center.1).abs();
+                (self.move_priority(game, index) * 8 - center_distance, index)
+            })
+            .collect();
+        
+        candidates.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        candidates.truncate(self.max_candidates);
+        candidates.into_iter().map(|(_, index)| index).collect()
+    }
+    
+    /// Quick move ordering score: open lines of both players running through the cell
+    fn move_priority(&self, game: &MnkGame, index: usize) -> i32 {
+        let win_length = game.get_win_length() as u32;
+        let player = game.current_player();
+        let own_board = game.get_board_for_player(player);
+        let opponent_board = game.get_board_for_player(player.opponent());
+        
+        game.geometry()
+            .get_winning_lines(game.get_win_length())
+            .iter()
+            .filter(|line| line.get_bit(index))
+            .map(|line| {
+                let own = own_board.count_set_bits_in_mask(line);
+                let opponent = opponent_board.count_set_bits_in_mask(line);
+                match (own, opponent) {
+                    (0, 0) => 1,
+                    (own, 0) => Self::line_value(win_length - own).min(1000),
+                    (0, opponent) => Self::line_value(win_length - opponent).min(1000),
+                    _ => 0,
+                }
+            })
+            .sum()
+    }
+    
+    /// Check if any piece lies within `distance` cells (Chebyshev) of the index
+    fn has_neighbor(&self, game: &MnkGame, index: usize, distance: i32) -> bool {
+        let (rows, cols) = (game.get_rows() as i32, game.get_cols() as i32);
+        let (row, col) = (index as i32 / cols, index as i32 % cols);
+        
+        for dr in -distance..=distance {
+            for dc in -distance..=distance {
+                let (r, c) = (row + dr, col + dc);
+                if (dr != 0 || dc != 0) && (0..rows).contains(&r) && (0..cols).contains(&c)
+                    && game.is_occupied((r * cols + c) as usize)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Default for MnkAI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::mnk::MnkVariant;
+    
+    #[test]
+    fn test_win_and_block_stages() {
+        let ai = MnkAI::new();
+        let mut game = MnkGame::new_variant(MnkVariant::Connect4);
+        for col in [0, 6, 1, 6, 2] {
+            game.drop_piece_internal(col).unwrap();
+        }
+        
+        // Red must block the bottom row at column 3
+        assert_eq!(ai.get_best_move(&game), Some(5 * 7 + 3));
+        game.drop_piece_internal(5).unwrap();
+        // Yellow wins in column 3
+        assert_eq!(ai.get_best_move(&game), Some(5 * 7 + 3));
+    }
+    
+    #[test]
+    fn test_perfect_tic_tac_toe_is_a_draw() {
+        let mut game = MnkGame::new_variant(MnkVariant::TicTacToe);
+        game.set_ai_depth(9);
+        while !game.is_game_over() {
+            let ai_move = game.get_ai_move();
+            game.make_move_internal(ai_move[0], ai_move[1]).unwrap();
+        }
+        assert!(game.is_draw());
+    }
+}
@@ -1,6 +1,10 @@
+pub mod arena;
 pub mod connect4_ai;
+pub mod evaluator_params;
 pub mod gomoku_ai;
+pub mod mnk_ai;
 pub mod pattern_evaluator;
+pub mod tuning;
 pub mod test_data;
 pub mod gemini_test_cases;
 pub mod gomoku_test_data;
@@ -12,9 +16,13 @@ pub mod advanced_gomoku_scenarios;
 pub mod quick_gemini_test;
 pub mod connect4_multi_test_example;
 
+pub use arena::{run_arena, ArenaConfig, ArenaReport, ArenaStats, Sprt, SprtDecision};
 pub use connect4_ai::Connect4AI;
+pub use evaluator_params::{ai_profile_names, EvaluatorParams, EvaluatorProfile};
 pub use gomoku_ai::GomokuAI;
+pub use mnk_ai::MnkAI;
 pub use pattern_evaluator::PatternEvaluator;
+pub use tuning::{tune, labeled_positions, LabeledPosition, TunableGame, TuningReport};
 pub use test_data::{AITestCase, parse_board_from_ascii, parse_connect4_board_from_ascii, game_to_ascii, test_ai_case, test_ai_case_xor, parse_ascii_to_boards, extract_move_from_boards};
 pub use gemini_test_cases::{get_gemini_test_cases, run_all_gemini_tests};
 pub use gomoku_test_data::{GomokuAITestCase, GomokuAIMultiTestCase, GomokuTestType, parse_gomoku_ascii_to_boards, extract_gomoku_move_from_boards, gomoku_game_to_ascii, parse_gomoku_board_from_ascii, is_valid_gomoku_state, test_gomoku_ai_case_xor, test_gomoku_ai_multi_case, test_gomoku_ai_case_full_simulation};
@@ -1,6 +1,7 @@
-use crate::data::BitPackedBoard;
-use crate::geometry::{Connect4Grid, PatternProvider, BoardGeometry};
+use crate::data::DynamicBitPackedBoard;
+use crate::geometry::{DynamicQuadraticGrid, BoardGeometry};
 use crate::games::connect4::Connect4Game;
+use crate::ai::evaluator_params::EvaluatorParams;
 use crate::Player;
 
 use std::cell::RefCell;
@@ -38,6 +39,19 @@ impl PatternEvaluator {
         }
     }
     
+    /// Evaluator with the Connect4 weights of a parameter set (the loss score mirrors the win score)
+    pub fn from_params(params: &EvaluatorParams) -> Self {
+        Self {
+            win_score: params.win_score,
+            loss_score: -params.win_score,
+            threat_3_score: params.threat_3_score,
+            potential_2_score: params.potential_2_score,
+            center_bonus: params.center_bonus,
+            block_threat_bonus: params.block_threat_bonus,
+            ..Self::new()
+        }
+    }
+    
     /// Clear the evaluation cache (call when game resets)
     pub fn clear_cache(&self) {
         self.position_cache.borrow_mut().clear();
@@ -54,8 +68,8 @@ impl PatternEvaluator {
         
         // Hash board state using direct bit operations
         // This is much faster than 42 get_cell() calls (6x7)
-        for row in 0..6 {
-            for col in 0..7 {
+        for row in 0..game.get_rows() as i32 {
+            for col in 0..game.get_cols() as i32 {
                 if let Some(index) = game.geometry().to_index((row, col)) {
                     let cell_value = if yellow_board.get_bit(index) {
                         1u64 // Yellow
@@ -69,8 +83,9 @@ impl PatternEvaluator {
             }
         }
         
-        // Include player in hash
+        // Include player and rule set in hash
         hash = hash.wrapping_mul(2).wrapping_add(player as u64);
+        hash = hash.wrapping_mul(2).wrapping_add(game.is_misere_enabled() as u64);
         
         hash
     }
@@ -92,7 +107,8 @@ impl PatternEvaluator {
         
         // Get pre-computed patterns from the geometry layer
         let geometry = game.geometry();
-        let winning_lines = geometry.get_winning_lines(4);
+        let win_length = game.get_win_length() as u32;
+        let winning_lines = geometry.get_winning_lines(game.get_win_length());
         
         // Check for immediate wins/losses
         if let Some(winner) = game.winner() {
@@ -110,49 +126,53 @@ impl PatternEvaluator {
             
             // Only evaluate lines that aren't blocked by opponent
             if ai_pieces > 0 && opponent_pieces == 0 {
-                score += self.evaluate_line_value(ai_pieces);
+                score += self.evaluate_line_value(win_length - ai_pieces);
             } else if opponent_pieces > 0 && ai_pieces == 0 {
-                score -= self.evaluate_opponent_line_value(opponent_pieces);
+                score -= self.evaluate_opponent_line_value(win_length - opponent_pieces);
             }
         }
         
         // Add positional bonuses using pre-computed masks
         score += self.evaluate_positional_bonus(ai_board, opponent_board, geometry);
         
+        // Misère: own lines are liabilities, the opponent's are assets
+        if game.is_misere_enabled() {
+            score = -score;
+        }
+        
         // Cache the result
         self.position_cache.borrow_mut().insert(position_hash, score);
         
         score
     }
     
-    /// Evaluate the value of AI's line based on piece count
-    fn evaluate_line_value(&self, piece_count: u32) -> i32 {
-        match piece_count {
-            4 => self.win_score,      // Impossible (game would be over)
-            3 => self.threat_3_score, // Strong threat
+    /// Evaluate the value of AI's line based on pieces still missing for a win
+    /// (on the standard board: 0 missing = 4 pieces, 1 missing = 3 pieces, ...)
+    fn evaluate_line_value(&self, missing: u32) -> i32 {
+        match missing {
+            0 => self.win_score,      // Impossible (game would be over)
+            1 => self.threat_3_score, // Strong threat
             2 => self.potential_2_score, // Building potential
-            1 => 1,                   // Basic presence
-            _ => 0,
+            _ => 1,                   // Basic presence
         }
     }
     
-    /// Evaluate the threat level of opponent's line
-    fn evaluate_opponent_line_value(&self, piece_count: u32) -> i32 {
-        match piece_count {
-            4 => -self.loss_score,    // Impossible (game would be over)
-            3 => self.block_threat_bonus, // Must block this!
+    /// Evaluate the threat level of opponent's line based on pieces still missing
+    fn evaluate_opponent_line_value(&self, missing: u32) -> i32 {
+        match missing {
+            0 => -self.loss_score,    // Impossible (game would be over)
+            1 => self.block_threat_bonus, // Must block this!
             2 => self.potential_2_score * 2, // Significant threat
-            1 => 2,                   // Minor threat
-            _ => 0,
+            _ => 2,                   // Minor threat
         }
     }
     
     /// Evaluate positional advantages using pre-computed masks
     fn evaluate_positional_bonus(
         &self,
-        ai_board: &BitPackedBoard<6, 7, 2>,
-        opponent_board: &BitPackedBoard<6, 7, 2>,
-        geometry: &Connect4Grid,
+        ai_board: &DynamicBitPackedBoard,
+        opponent_board: &DynamicBitPackedBoard,
+        geometry: &DynamicQuadraticGrid,
     ) -> i32 {
         let mut bonus = 0;
         
@@ -176,19 +196,20 @@ impl PatternEvaluator {
         let ai_board = game.get_board_for_player(ai_player);
         let opponent_board = game.get_board_for_player(ai_player.opponent());
         let geometry = game.geometry();
-        let winning_lines = geometry.get_winning_lines(4);
+        let winning_lines = geometry.get_winning_lines(game.get_win_length());
+        let threat_pieces = game.get_win_length() as u32 - 1;
         
         let mut ai_threats = 0;
         let mut opponent_threats = 0;
         
-        // Count open 3-in-a-row lines (threats)
+        // Count open 3-in-a-row lines (threats, one piece short of a win)
         for line_mask in winning_lines {
             let ai_pieces = ai_board.count_set_bits_in_mask(line_mask);
             let opponent_pieces = opponent_board.count_set_bits_in_mask(line_mask);
             
-            if ai_pieces == 3 && opponent_pieces == 0 {
+            if ai_pieces == threat_pieces && opponent_pieces == 0 {
                 ai_threats += 1;
-            } else if opponent_pieces == 3 && ai_pieces == 0 {
+            } else if opponent_pieces == threat_pieces && ai_pieces == 0 {
                 opponent_threats += 1;
             }
         }
@@ -201,6 +222,9 @@ impl PatternEvaluator {
         if opponent_threats >= 2 {
             fork_score -= opponent_threats * opponent_threats * 75; // Higher penalty
         }
+        if game.is_misere_enabled() {
+            fork_score = -fork_score;
+        }
         
         // Cache the result
         self.fork_cache.borrow_mut().insert(position_hash, fork_score);
@@ -210,11 +234,13 @@ impl PatternEvaluator {
     
     /// Game phase awareness for strategic adaptation
     pub fn get_game_phase(&self, game: &Connect4Game) -> GamePhase {
-        let move_count = game.move_count();
+        // Phase boundaries scale with the board (10 and 30 moves on the standard 42 cells)
+        let move_count = game.move_count() * 42;
+        let cells = game.get_rows() * game.get_cols();
         
-        if move_count < 10 {
+        if move_count < 10 * cells {
             GamePhase::Opening
-        } else if move_count < 30 {
+        } else if move_count < 30 * cells {
             GamePhase::Middle
         } else {
             GamePhase::Endgame
@@ -249,8 +275,9 @@ impl PatternEvaluator {
         let geometry = game.geometry();
         let center_mask = geometry.get_center_mask();
         
-        // Extra bonus for center control in opening
-        ai_board.count_set_bits_in_mask(center_mask) as i32 * 10
+        // Extra bonus for center control in opening (central discs are a liability in misère)
+        let center_bonus = ai_board.count_set_bits_in_mask(center_mask) as i32 * 10;
+        if game.is_misere_enabled() { -center_bonus } else { center_bonus }
     }
 }
 
@@ -296,4 +323,36 @@ mod tests {
         // Yellow should have higher score due to center control
         assert!(yellow_score > red_score);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_misere_inverts_objective() {
+        let mut game = Connect4Game::new();
+        for col in [3, 0, 3, 0] {
+            game.make_move(col).unwrap();
+        }
+        let evaluator = PatternEvaluator::new();
+        let normal = evaluator.evaluate_position(&game, Player::Yellow);
+        
+        game.set_misere_enabled(true);
+        let misere = evaluator.evaluate_position(&game, Player::Yellow);
+        assert!(normal > 0);
+        assert_eq!(misere, -normal);
+    }
+    
+    #[test]
+    fn test_phase_boundaries_on_standard_board() {
+        let evaluator = PatternEvaluator::new();
+        let mut game = Connect4Game::new();
+        while game.move_count() < 30 {
+            // Lowest column that doesn't end the game
+            let col = (0..7).find(|&col| game.make_move_copy(col).is_some_and(|next| !next.is_game_over())).unwrap();
+            game.make_move(col).unwrap();
+            let expected = match game.move_count() {
+                0..=9 => GamePhase::Opening,
+                10..=29 => GamePhase::Middle,
+                _ => GamePhase::Endgame,
+            };
+            assert_eq!(evaluator.get_game_phase(&game), expected, "after {} moves", game.move_count());
+        }
+    }
+}
@@ -1,4 +1,6 @@
 
+#[cfg(test)]
+use crate::ai::{get_gemini_gomoku_test_cases, parse_gomoku_ascii_to_boards, extract_gomoku_move_from_boards};
 #[cfg(test)]
 #[test]
 fn test_quick_gemini_validation() {
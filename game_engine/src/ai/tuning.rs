@@ -0,0 +1,206 @@
+use crate::Player;
+use crate::ai::arena::{ArenaGame, ArenaGameRecord};
+use crate::ai::evaluator_params::EvaluatorParams;
+use crate::ai::{GomokuAI, PatternEvaluator};
+use crate::games::{Connect4Game, GomokuGame};
+
+/// Games whose static evaluation depends on an `EvaluatorParams` set
+pub trait TunableGame: ArenaGame {
+    /// Names of the parameters the evaluation reads
+    fn tunable_params() -> &'static [&'static str];
+    
+    /// Static evaluation of every position from the first color's point of view
+    fn evaluate_all(positions: &[LabeledPosition<Self>], params: &EvaluatorParams) -> Vec<i32>;
+}
+
+impl TunableGame for Connect4Game {
+    /// `EvaluatorParams::CONNECT4` without `win_score`: it only scores finished games, which are never labeled
+    fn tunable_params() -> &'static [&'static str] {
+        &["threat_3_score", "potential_2_score", "center_bonus", "block_threat_bonus"]
+    }
+    
+    fn evaluate_all(positions: &[LabeledPosition<Self>], params: &EvaluatorParams) -> Vec<i32> {
+        let evaluator = PatternEvaluator::from_params(params);
+        positions.iter().map(|position| evaluator.evaluate_with_phase(&position.game, Player::Yellow)).collect()
+    }
+}
+
+impl TunableGame for GomokuGame {
+    fn tunable_params() -> &'static [&'static str] {
+        &EvaluatorParams::GOMOKU
+    }
+    
+    fn evaluate_all(positions: &[LabeledPosition<Self>], params: &EvaluatorParams) -> Vec<i32> {
+        let ai = GomokuAI::new().with_params(params);
+        positions.iter().map(|position| ai.evaluate_position(&position.game, Player::Black)).collect()
+    }
+}
+
+/// A position from a finished game, labeled with that game's result
+#[derive(Clone, Debug)]
+pub struct LabeledPosition<G> {
+    pub game: G,
+    pub result: f64, // For the first color: 1 win, 0.5 draw, 0 loss
+}
+
+/// Every non-terminal position (after at least one move) of the recorded games
+pub fn labeled_positions<G: ArenaGame>(start: &G, records: &[ArenaGameRecord]) -> Vec<LabeledPosition<G>> {
+    let first = start.first_color();
+    let mut positions = Vec::new();
+    for record in records {
+        let result = match record.winner {
+            Some(player) if player == first => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+        
+        let mut game = start.clone();
+        game.restart(first);
+        for game_move in &record.moves {
+            if game.apply_move(game_move).is_err() {
+                break;
+            }
+            if game.final_result().is_some() {
+                break;
+            }
+            positions.push(LabeledPosition { game: game.clone(), result });
+        }
+    }
+    positions
+}
+
+/// Expected score for an evaluation: 1 / (1 + e^(-scale·eval))
+pub fn win_probability(eval: i32, scale: f64) -> f64 {
+    1.0 / (1.0 + (-scale * eval as f64).exp())
+}
+
+/// Mean squared difference between game results and predicted scores
+pub fn prediction_error<G>(positions: &[LabeledPosition<G>], evals: &[i32], scale: f64) -> f64 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = positions.iter()
+        .zip(evals)
+        .map(|(position, &eval)| (position.result - win_probability(eval, scale)).powi(2))
+        .sum();
+    total / positions.len() as f64
+}
+
+/// Scale that best maps evaluations to results (ternary search over log10 scale in [-8, 0])
+pub fn fit_scale<G>(positions: &[LabeledPosition<G>], evals: &[i32]) -> f64 {
+    let error = |exponent: f64| prediction_error(positions, evals, 10f64.powf(exponent));
+    let (mut low, mut high) = (-8.0, 0.0);
+    for _ in 0..60 {
+        let left = low + (high - low) / 3.0;
+        let right = high - (high - low) / 3.0;
+        if error(left) < error(right) {
+            high = right;
+        } else {
+            low = left;
+        }
+    }
+    10f64.powf((low + high) / 2.0)
+}
+
+/// Outcome of a tuning run
+#[derive(Clone, Debug, PartialEq)]
+pub struct TuningReport {
+    pub params: EvaluatorParams,
+    pub scale: f64,          // Fitted once for the starting parameters, then held fixed
+    pub initial_error: f64,
+    pub final_error: f64,
+    pub passes: usize,
+}
+
+/// Texel-style local search: nudge each tunable parameter up or down while the prediction error drops
+/// Steps start at a quarter of each value and halve whenever a full pass finds no improvement
+pub fn tune<G: TunableGame>(positions: &[LabeledPosition<G>], start: &EvaluatorParams, max_passes: usize) -> TuningReport {
+    let names = G::tunable_params();
+    let mut params = *start;
+    let scale = fit_scale(positions, &G::evaluate_all(positions, &params));
+    let error_of = |params: &EvaluatorParams| prediction_error(positions, &G::evaluate_all(positions, params), scale);
+    
+    let initial_error = error_of(&params);
+    let mut best_error = initial_error;
+    let mut steps: Vec<i32> = names.iter()
+        .map(|name| (params.get(name).unwrap_or_default().abs() / 4).max(1))
+        .collect();
+    
+    let mut passes = 0;
+    while passes < max_passes {
+        passes += 1;
+        let mut improved = false;
+        for (name, step) in names.iter().zip(&steps) {
+            let current = params.get(name).unwrap_or_default();
+            for candidate in [current.saturating_add(*step), current.saturating_sub(*step)] {
+                let mut trial = params;
+                if trial.set(name, candidate).is_err() {
+                    continue;
+                }
+                let error = error_of(&trial);
+                if error < best_error {
+                    params = trial;
+                    best_error = error;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        
+        if !improved {
+            if steps.iter().all(|&step| step == 1) {
+                break;
+            }
+            for step in steps.iter_mut() {
+                *step = (*step / 2).max(1);
+            }
+        }
+    }
+    
+    TuningReport { params, scale, initial_error, final_error: best_error, passes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::arena::ArenaOutcome;
+    
+    #[test]
+    fn test_scale_fit_and_error() {
+        let positions: Vec<LabeledPosition<()>> = [1.0, 1.0, 0.0, 0.5]
+            .iter()
+            .map(|&result| LabeledPosition { game: (), result })
+            .collect();
+        let evals = [400, 300, -400, 0];
+        let scale = fit_scale(&positions, &evals);
+        assert!(scale > 1e-3);
+        assert!(prediction_error(&positions, &evals, scale) < prediction_error(&positions, &evals, 1e-5));
+        assert_eq!(win_probability(0, scale), 0.5);
+        assert_eq!(prediction_error::<()>(&[], &[], 1.0), 0.0);
+    }
+    
+    #[test]
+    fn test_labels_and_tuning_lower_the_error() {
+        // Yellow builds the bottom row while Red stacks on the edge
+        let record = |winner| ArenaGameRecord {
+            opening: 0,
+            a_moves_first: true,
+            moves: vec![vec![3], vec![0], vec![4], vec![0], vec![5], vec![0], vec![6]],
+            winner,
+            outcome: ArenaOutcome::Win,
+        };
+        let start = Connect4Game::new();
+        let positions = labeled_positions(&start, &[record(Some(Player::Yellow)), record(None)]);
+        assert_eq!(positions.len(), 12); // The winning move ends each game
+        assert_eq!(positions[0].result, 1.0);
+        assert_eq!(positions[6].result, 0.5);
+        
+        let start_params = EvaluatorParams { threat_3_score: 1, ..EvaluatorParams::default() };
+        let report = tune(&positions, &start_params, 20);
+        assert!(report.final_error < report.initial_error);
+        assert!(report.params.threat_3_score > 1); // Yellow's open threats predict the wins
+        assert!(report.passes <= 20);
+        assert_eq!(report.params.win_score, start_params.win_score);
+        assert_eq!(report.params.threat_weight, start_params.threat_weight); // Gomoku weights are untouched
+    }
+}
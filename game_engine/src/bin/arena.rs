@@ -0,0 +1,187 @@
+//! Self-play arena: seeded games between two AI configurations with Elo and SPRT reporting
+//!
+//! Usage: arena <connect4|gomoku> [--a SPEC] [--b SPEC] [--games N] [--seed S] [--max-moves N]
+//!              [--sprt ELO0,ELO1] [--alpha A] [--beta B] [--progress N]
+//!
+//! SPEC is a comma-separated list of key=value settings, e.g. `depth=6,center=8`:
+//! - both games: `depth`
+//! - Connect4 evaluator weights: `win`, `threat3`, `potential2`, `center`, `block`
+//!
+//! Scores and Elo are reported for A against B.
+
+use std::process::ExitCode;
+use game_engine::ai::arena::{run_arena, ArenaConfig, ArenaGame, ArenaPlayer, ArenaStats, Sprt, SprtDecision};
+use game_engine::{Connect4AI, Connect4Game, GomokuAI, GomokuGame, PatternEvaluator};
+
+const USAGE: &str = "Usage: arena <connect4|gomoku> [--a SPEC] [--b SPEC] [--games N] [--seed S] [--max-moves N] [--sprt ELO0,ELO1] [--alpha A] [--beta B] [--progress N]";
+
+struct Options {
+    game: String,
+    spec_a: String,
+    spec_b: String,
+    config: ArenaConfig,
+    progress: usize,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        game: String::new(),
+        spec_a: String::new(),
+        spec_b: String::new(),
+        config: ArenaConfig::default(),
+        progress: 10,
+    };
+    let (mut alpha, mut beta) = (None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--a" => options.spec_a = value()?,
+            "--b" => options.spec_b = value()?,
+            "--games" => options.config.games = parse_number(&value()?)?,
+            "--seed" => options.config.seed = parse_number(&value()?)?,
+            "--max-moves" => options.config.max_moves = parse_number(&value()?)?,
+            "--progress" => options.progress = parse_number(&value()?)?,
+            "--sprt" => {
+                let bounds = value()?;
+                let (elo0, elo1) = bounds.split_once(',').ok_or("SPRT bounds are written ELO0,ELO1")?;
+                options.config.sprt = Some(Sprt::new(parse_number(elo0)?, parse_number(elo1)?));
+            },
+            "--alpha" => alpha = Some(parse_number(&value()?)?),
+            "--beta" => beta = Some(parse_number(&value()?)?),
+            game if options.game.is_empty() && !game.starts_with('-') => options.game = game.to_string(),
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+    if let Some(sprt) = options.config.sprt.as_mut() {
+        sprt.alpha = alpha.unwrap_or(sprt.alpha);
+        sprt.beta = beta.unwrap_or(sprt.beta);
+    }
+    Ok(options)
+}
+
+fn parse_number<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.trim().parse().map_err(|_| format!("`{}` is not a valid number", text))
+}
+
+/// `key=value` pairs of a player spec
+fn spec_settings(spec: &str) -> Result<Vec<(&str, &str)>, String> {
+    spec.split(',')
+        .filter(|setting| !setting.trim().is_empty())
+        .map(|setting| setting.split_once('=').map(|(key, value)| (key.trim(), value.trim())).ok_or_else(|| format!("Expected key=value, got `{}`", setting)))
+        .collect()
+}
+
+fn connect4_player(spec: &str) -> Result<Connect4AI, String> {
+    let mut ai = Connect4AI::new();
+    let mut evaluator = PatternEvaluator::new();
+    for (key, value) in spec_settings(spec)? {
+        match key {
+            "depth" => ai.set_difficulty(parse_number(value)?),
+            "win" => {
+                evaluator.win_score = parse_number(value)?;
+                evaluator.loss_score = -evaluator.win_score;
+            },
+            "threat3" => evaluator.threat_3_score = parse_number(value)?,
+            "potential2" => evaluator.potential_2_score = parse_number(value)?,
+            "center" => evaluator.center_bonus = parse_number(value)?,
+            "block" => evaluator.block_threat_bonus = parse_number(value)?,
+            _ => return Err(format!("Unknown Connect4 setting `{}`", key)),
+        }
+    }
+    ai.set_evaluator(evaluator);
+    Ok(ai)
+}
+
+fn gomoku_player(spec: &str) -> Result<GomokuAI, String> {
+    let mut ai = GomokuAI::new();
+    for (key, value) in spec_settings(spec)? {
+        match key {
+            "depth" => ai = GomokuAI::new_with_depth(parse_number(value)?),
+            _ => return Err(format!("Unknown Gomoku setting `{}`", key)),
+        }
+    }
+    Ok(ai)
+}
+
+fn sprt_line(sprt: &Sprt, stats: &ArenaStats, decision: SprtDecision) -> String {
+    let (lower, upper) = sprt.bounds();
+    let verdict = match decision {
+        SprtDecision::Continue => "no decision yet",
+        SprtDecision::AcceptH0 => "H0 accepted",
+        SprtDecision::AcceptH1 => "H1 accepted",
+    };
+    format!(
+        "SPRT elo0={} elo1={} alpha={} beta={}: LLR {:.2} [{:.2}, {:.2}], {}",
+        sprt.elo0, sprt.elo1, sprt.alpha, sprt.beta, stats.llr(sprt), lower, upper, verdict
+    )
+}
+
+fn arena<G, P>(start: G, player_a: P, player_b: P, options: &Options) -> Vec<String>
+where
+    G: ArenaGame,
+    P: ArenaPlayer<G>,
+{
+    if options.config.games > start.distinct_games() {
+        eprintln!("Openings repeat after {} games, so later games may duplicate earlier ones", start.distinct_games());
+    }
+    let report = run_arena(&start, &player_a, &player_b, &options.config, |_, stats| {
+        if options.progress > 0 && stats.games() % options.progress == 0 {
+            println!("{}", stats.summary());
+        }
+    });
+    let mut lines = vec![format!("Final: {}", report.stats.summary())];
+    if let (Some(sprt), Some(decision)) = (options.config.sprt, report.sprt) {
+        lines.push(sprt_line(&sprt, &report.stats, decision));
+    }
+    lines
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = parse_options(&args).and_then(|options| {
+        let lines = match options.game.as_str() {
+            "connect4" => arena(Connect4Game::new(), connect4_player(&options.spec_a)?, connect4_player(&options.spec_b)?, &options),
+            "gomoku" => arena(GomokuGame::new(), gomoku_player(&options.spec_a)?, gomoku_player(&options.spec_b)?, &options),
+            _ => return Err(USAGE.to_string()),
+        };
+        Ok(lines)
+    });
+    
+    match result {
+        Ok(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+            ExitCode::SUCCESS
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+    
+    #[test]
+    fn test_options_and_player_specs() {
+        let options = parse_options(&args("connect4 --a depth=6,center=8 --games 200 --sprt 0,20 --alpha 0.1")).unwrap();
+        assert_eq!(options.game, "connect4");
+        assert_eq!(options.config.games, 200);
+        assert_eq!(options.config.sprt, Some(Sprt { elo0: 0.0, elo1: 20.0, alpha: 0.1, beta: 0.05 }));
+        
+        let ai = connect4_player(&options.spec_a).unwrap();
+        assert_eq!(ai.get_depth(), 6);
+        assert_eq!(ai.evaluator().center_bonus, 8);
+        assert!(connect4_player("depth").is_err());
+        assert!(gomoku_player("center=3").is_err());
+        assert!(parse_options(&args("gomoku --sprt 5")).is_err());
+    }
+}
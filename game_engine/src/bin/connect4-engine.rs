@@ -0,0 +1,388 @@
+//! Connect4 engine speaking a UCI-like line protocol on stdin/stdout, for tournament managers
+//!
+//! uci                                  -> id lines, options, `uciok`
+//! isready                              -> `readyok`
+//! setoption name <option> value <v>    -> Rows, Columns, WinLength, PopOut, Misere, Difficulty (easy|medium|hard);
+//!                                         board options reset the position
+//! ucinewgame                           -> back to the start position
+//! position startpos [moves <moves>]    -> also `position <moves>`
+//! go [depth N] [nodes N] [movetime MS] [wtime MS] [btime MS] [winc MS] [binc MS] [infinite]
+//!                                      -> `info depth .. score cp|mate .. nodes .. time .. nps .. pv ..` per depth,
+//!                                         then `bestmove <move>`; plain `go` searches to the difficulty depth
+//! stop                                 -> end the search now, `bestmove` follows
+//! d                                    -> print the board
+//! quit
+//!
+//! Moves are 1-based columns, `p4` pops from column 4 (PopOut). On boards with at most nine columns
+//! a move list may be one string such as `4453`. `w` is the first player (Yellow), `b` the second.
+
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use game_engine::ai::connect4_ai::{AIDifficulty, SearchInfo, SearchLimits};
+use game_engine::ai::Connect4AI;
+use game_engine::Connect4Game;
+use game_engine::games::connect4::{MAX_BOARD_SIDE, MIN_BOARD_SIDE, MIN_WIN_LENGTH};
+
+/// Moves the remaining clock time is spread over
+const MOVES_TO_GO: u64 = 20;
+
+/// Search running on its own thread
+struct RunningSearch {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+/// Board options set with `setoption`
+#[derive(Clone, Copy)]
+struct BoardConfig {
+    rows: usize,
+    cols: usize,
+    win_length: usize,
+    popout: bool,
+    misere: bool,
+}
+
+impl BoardConfig {
+    /// Position after playing `moves` from the start
+    fn position(&self, moves: &[usize]) -> Result<Connect4Game, String> {
+        let mut game = Connect4Game::new_with_size(self.rows, self.cols, self.win_length).map_err(String::from)?;
+        game.set_popout_enabled(self.popout);
+        game.set_misere_enabled(self.misere);
+        for &code in moves {
+            game.make_move_code(code).map_err(|e| format!("{} in `{}`", e, format_move(code, self.cols)))?;
+        }
+        Ok(game)
+    }
+}
+
+/// Protocol state: board options, the current position and the running search
+struct Engine<W: Write + Send + 'static> {
+    out: Arc<Mutex<W>>,
+    config: BoardConfig,
+    difficulty: AIDifficulty,
+    moves: Vec<usize>,
+    game: Connect4Game,
+    search: Option<RunningSearch>,
+}
+
+impl<W: Write + Send + 'static> Engine<W> {
+    fn new(out: W) -> Self {
+        Self {
+            out: Arc::new(Mutex::new(out)),
+            config: BoardConfig { rows: 6, cols: 7, win_length: 4, popout: false, misere: false },
+            difficulty: AIDifficulty::Medium,
+            moves: Vec::new(),
+            game: Connect4Game::new(),
+            search: None,
+        }
+    }
+    
+    /// Handle one input line; false once the manager sent `quit`
+    fn handle(&mut self, line: &str) -> bool {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = tokens.split_first() else {
+            return true;
+        };
+        let result = match command {
+            "uci" => {
+                self.send(&format!("id name LogicCastle Connect4 {}", env!("CARGO_PKG_VERSION")));
+                // Same bounds as Connect4Game::new_with_size (the win length may not exceed the longer side)
+                self.send(&format!("option name Rows type spin default 6 min {} max {}", MIN_BOARD_SIDE, MAX_BOARD_SIDE));
+                self.send(&format!("option name Columns type spin default 7 min {} max {}", MIN_BOARD_SIDE, MAX_BOARD_SIDE));
+                self.send(&format!("option name WinLength type spin default 4 min {} max {}", MIN_WIN_LENGTH, MAX_BOARD_SIDE));
+                self.send("option name PopOut type check default false");
+                self.send("option name Misere type check default false");
+                self.send("option name Difficulty type combo default medium var easy var medium var hard");
+                self.send("uciok");
+                Ok(())
+            },
+            "isready" => {
+                self.send("readyok");
+                Ok(())
+            },
+            "setoption" => self.set_option(args),
+            "ucinewgame" => self.new_game(),
+            "position" => self.set_position(args),
+            "go" => self.go(args),
+            "stop" => {
+                self.stop();
+                Ok(())
+            },
+            "d" => {
+                let board = self.game.board_string();
+                for row in board.lines() {
+                    self.send(row);
+                }
+                Ok(())
+            },
+            "quit" => {
+                self.stop();
+                return false;
+            },
+            _ => Err(format!("unknown command `{}`", command)),
+        };
+        if let Err(message) = result {
+            self.send(&format!("info string error: {}", message));
+        }
+        true
+    }
+    
+    fn send(&self, line: &str) {
+        send(&self.out, line);
+    }
+    
+    /// setoption name <name> value <value>
+    fn set_option(&mut self, args: &[&str]) -> Result<(), String> {
+        let (name, value) = match args {
+            ["name", name, "value", value] => (name.to_ascii_lowercase(), value.to_ascii_lowercase()),
+            _ => return Err("expected `setoption name <option> value <value>`".to_string()),
+        };
+        let number = || value.parse::<usize>().map_err(|_| format!("`{}` is not a number", value));
+        let flag = || value.parse::<bool>().map_err(|_| format!("`{}` is not true or false", value));
+        let mut config = self.config;
+        match name.as_str() {
+            "rows" => config.rows = number()?,
+            "columns" => config.cols = number()?,
+            "winlength" => config.win_length = number()?,
+            "popout" => config.popout = flag()?,
+            "misere" => config.misere = flag()?,
+            "difficulty" => {
+                self.difficulty = match value.as_str() {
+                    "easy" => AIDifficulty::Easy,
+                    "medium" => AIDifficulty::Medium,
+                    "hard" => AIDifficulty::Hard,
+                    _ => return Err(format!("unknown difficulty `{}`", value)),
+                };
+                return Ok(());
+            },
+            _ => return Err(format!("unknown option `{}`", name)),
+        }
+        let game = config.position(&[])?;
+        self.stop();
+        self.config = config;
+        self.moves.clear();
+        self.game = game;
+        Ok(())
+    }
+    
+    fn new_game(&mut self) -> Result<(), String> {
+        self.stop();
+        self.moves.clear();
+        self.game = self.config.position(&[])?;
+        Ok(())
+    }
+    
+    /// position startpos [moves ...] or position <moves>; the position is unchanged on errors
+    fn set_position(&mut self, args: &[&str]) -> Result<(), String> {
+        let moves = match args {
+            ["startpos"] => &[][..],
+            ["startpos", "moves", moves @ ..] => moves,
+            moves => moves,
+        };
+        let moves = parse_moves(moves, self.config.cols)?;
+        let game = self.config.position(&moves)?;
+        self.stop();
+        self.moves = moves;
+        self.game = game;
+        Ok(())
+    }
+    
+    /// go: search the current position on a separate thread
+    fn go(&mut self, args: &[&str]) -> Result<(), String> {
+        self.stop();
+        if self.game.is_game_over() {
+            return Err("the game is already over".to_string());
+        }
+        
+        let mut limits = SearchLimits::default();
+        let mut clock = [None, None]; // Remaining time of the first and second player
+        let mut increment = [0, 0];
+        let mut infinite = false;
+        let mut tokens = args.iter();
+        while let Some(&key) = tokens.next() {
+            if key == "infinite" {
+                infinite = true;
+                continue;
+            }
+            let value = tokens.next()
+                .and_then(|value| value.parse::<u64>().ok())
+                .ok_or_else(|| format!("`go {}` needs a number", key))?;
+            match key {
+                "depth" => limits.depth = Some(value as usize),
+                "nodes" => limits.nodes = Some(value),
+                "movetime" => limits.deadline = Some(Instant::now() + Duration::from_millis(value)),
+                "wtime" => clock[0] = Some(value),
+                "btime" => clock[1] = Some(value),
+                "winc" => increment[0] = value,
+                "binc" => increment[1] = value,
+                _ => return Err(format!("unknown go parameter `{}`", key)),
+            }
+        }
+        
+        // Clock time: an even share of what is left plus most of the increment
+        let side = self.game.move_count() % 2;
+        if let (None, Some(time)) = (limits.deadline, clock[side]) {
+            let budget = (time / MOVES_TO_GO + increment[side] * 3 / 4).min(time / 2);
+            limits.deadline = Some(Instant::now() + Duration::from_millis(budget));
+        }
+        
+        let ai = Connect4AI::with_difficulty(self.difficulty);
+        if !infinite && limits.depth.is_none() && limits.nodes.is_none() && limits.deadline.is_none() {
+            limits.depth = Some(ai.get_depth());
+        }
+        
+        let stop = Arc::new(AtomicBool::new(false));
+        limits.stop = Some(stop.clone());
+        let game = self.game.clone();
+        let cols = self.config.cols;
+        let out = self.out.clone();
+        let handle = thread::spawn(move || {
+            let started = Instant::now();
+            let best = ai.search(&game, &limits, |info| send(&out, &info_line(info, started.elapsed(), cols)));
+            let best_move = best
+                .and_then(|info| info.pv.first().copied())
+                .or_else(|| ai.get_move_order(&game).into_iter().find(|&code| game.is_legal_move_code(code)));
+            match best_move {
+                Some(code) => send(&out, &format!("bestmove {}", format_move(code, cols))),
+                None => send(&out, "bestmove (none)"),
+            }
+        });
+        self.search = Some(RunningSearch { stop, handle });
+        Ok(())
+    }
+    
+    /// Abort the running search and wait for its `bestmove`
+    fn stop(&mut self) {
+        if let Some(search) = self.search.take() {
+            search.stop.store(true, Ordering::Relaxed);
+            let _ = search.handle.join();
+        }
+    }
+}
+
+fn send<W: Write>(out: &Mutex<W>, line: &str) {
+    let mut out = out.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let _ = writeln!(out, "{}", line).and_then(|()| out.flush());
+}
+
+fn info_line(info: &SearchInfo, elapsed: Duration, cols: usize) -> String {
+    let score = match info.mate {
+        Some(moves) => format!("mate {}", moves),
+        None => format!("cp {}", info.score),
+    };
+    let millis = elapsed.as_millis() as u64;
+    let nps = info.nodes * 1000 / millis.max(1);
+    let pv: Vec<String> = info.pv.iter().map(|&code| format_move(code, cols)).collect();
+    format!("info depth {} score {} nodes {} time {} nps {} pv {}", info.depth, score, info.nodes, millis, nps, pv.join(" "))
+}
+
+/// 1-based column, `p` prefix for pops
+fn format_move(code: usize, cols: usize) -> String {
+    if code >= cols { format!("p{}", code - cols + 1) } else { (code + 1).to_string() }
+}
+
+/// Move numbers from `4 4 p3` or, with at most nine columns, `44p3`
+fn parse_moves(tokens: &[&str], cols: usize) -> Result<Vec<usize>, String> {
+    let mut codes = Vec::new();
+    for token in tokens {
+        let parts: Vec<String> = if cols <= 9 {
+            let mut parts = Vec::new();
+            let mut pending = String::new();
+            for ch in token.chars() {
+                pending.push(ch);
+                if ch != 'p' {
+                    parts.push(std::mem::take(&mut pending));
+                }
+            }
+            parts.push(pending);
+            parts.into_iter().filter(|part| !part.is_empty()).collect()
+        } else {
+            vec![token.to_string()]
+        };
+        for part in parts {
+            let (pop, column) = match part.strip_prefix('p') {
+                Some(column) => (true, column),
+                None => (false, part.as_str()),
+            };
+            let column = column.parse::<usize>().ok()
+                .and_then(|column| column.checked_sub(1))
+                .filter(|&column| column < cols)
+                .ok_or_else(|| format!("bad move `{}`", part))?;
+            codes.push(if pop { cols + column } else { column });
+        }
+    }
+    Ok(codes)
+}
+
+fn main() {
+    let mut engine = Engine::new(io::stdout());
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if !engine.handle(&line) {
+            return;
+        }
+    }
+    engine.stop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn output(engine: &Engine<Vec<u8>>) -> Vec<String> {
+        let out = engine.out.lock().unwrap();
+        String::from_utf8_lossy(&out).lines().map(String::from).collect()
+    }
+    
+    #[test]
+    fn test_move_strings() {
+        assert_eq!(parse_moves(&["4453"], 7), Ok(vec![3, 3, 4, 2]));
+        assert_eq!(parse_moves(&["12"], 7), Ok(vec![0, 1])); // Two moves on narrow boards
+        assert_eq!(parse_moves(&["4", "p4"], 7), Ok(vec![3, 10]));
+        assert_eq!(parse_moves(&["10", "p10"], 10), Ok(vec![9, 19]));
+        assert!(parse_moves(&["8"], 7).is_err());
+        assert_eq!(format_move(10, 7), "p4");
+    }
+    
+    #[test]
+    fn test_go_finds_win_with_mate_score() {
+        let mut engine = Engine::new(Vec::new());
+        assert!(engine.handle("position startpos moves 112233"));
+        assert!(engine.handle("go depth 6"));
+        engine.stop();
+        let lines = output(&engine);
+        assert!(lines.iter().any(|line| line.starts_with("info depth 1 score mate 1 ") && line.ends_with("pv 4")), "{:?}", lines);
+        assert_eq!(lines.last().map(String::as_str), Some("bestmove 4"));
+    }
+    
+    #[test]
+    fn test_options_and_errors() {
+        let mut engine = Engine::new(Vec::new());
+        engine.handle("uci");
+        assert!(output(&engine).contains(&"option name Columns type spin default 7 min 4 max 16".to_string()));
+        engine.handle("setoption name Columns value 16");
+        engine.handle("position startpos moves 16");
+        assert_eq!(engine.game.get_cols(), 16);
+        assert_eq!(engine.game.move_count(), 1);
+        
+        engine.handle("setoption name Columns value 9");
+        engine.handle("setoption name PopOut value true");
+        engine.handle("position 5 5 p5");
+        assert_eq!(engine.game.get_cols(), 9);
+        assert_eq!(engine.game.move_count(), 3);
+        engine.handle("position startpos moves 4 0");
+        assert_eq!(engine.game.move_count(), 3); // Rejected, the previous position stays
+        engine.handle("go nodes 50");
+        engine.handle("stop");
+        engine.handle("bogus");
+        let lines = output(&engine);
+        assert!(lines.iter().any(|line| line.starts_with("bestmove ")));
+        assert!(lines.contains(&"info string error: bad move `0`".to_string()));
+        assert_eq!(lines.last().map(String::as_str), Some("info string error: unknown command `bogus`"));
+    }
+}
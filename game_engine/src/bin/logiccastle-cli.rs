@@ -0,0 +1,615 @@
+//! Terminal front end: play Connect4, Gomoku, L-Game and Trio against the engine's AIs
+//!
+//! Usage: logiccastle-cli <connect4|gomoku|lgame|trio> [--ai none|first|second|both] [--difficulty 1-4]
+//!                        [--max-moves N] [--load FILE]
+//!
+//! `--difficulty` sets the Connect4 AI level (1 easy, 2 medium, 3-4 hard), the Gomoku search depth
+//! (difficulty + 2 plies) or the Trio puzzle difficulty; the L-Game AI has no levels and rejects it.
+//!
+//! Cells are written as column letter + row number, counted from the top-left as printed (`h8`).
+//! Moves: Connect4 `4` (drop, 1-based column) or `p4` (PopOut); Gomoku `h8`;
+//! L-Game `a1:3` (anchor cell and orientation 0-7), optionally followed by a neutral move `d1-b3`;
+//! Trio `a1 b1 c1` (three cells in a line).
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+use game_engine::{Connect4Game, GomokuAI, GomokuGame, LGame, TrioGame};
+use game_engine::ai::connect4_ai::AIDifficulty;
+use game_engine::games::SeriesGame;
+use game_engine::net::LockstepGame;
+
+/// One game as seen by the terminal: rendering, notation and AI moves
+/// Moves use the lockstep protocol encoding (see `LockstepGame::apply_move`)
+trait TerminalGame: Clone {
+    const NAME: &'static str;
+    
+    fn render(&self) -> String;
+    fn parse_move(&self, text: &str) -> Result<Vec<usize>, String>;
+    fn format_move(&self, game_move: &[usize]) -> String;
+    fn apply(&mut self, game_move: &[usize]) -> Result<(), String>;
+    fn ai_move(&self) -> Option<Vec<usize>>;
+    /// Side to move: 0 for the first color, 1 for the second
+    fn side_to_move(&self) -> usize;
+    /// Final result text once the game is over
+    fn outcome(&self) -> Option<String>;
+    
+    /// Extra setup line for saved games (None if the starting position is always the same)
+    fn setup(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Side index for the games that implement the lockstep and series traits
+fn side_of<G: LockstepGame + SeriesGame>(game: &G) -> usize {
+    if game.current_player() == game.first_color() { 0 } else { 1 }
+}
+
+/// Cell notation: column letter + 1-based row from the top
+fn parse_cell(text: &str, rows: usize, cols: usize) -> Option<(usize, usize)> {
+    let mut chars = text.chars();
+    let letter = chars.next()?.to_ascii_lowercase();
+    if !letter.is_ascii_lowercase() {
+        return None;
+    }
+    let col = (letter as u8 - b'a') as usize;
+    let row = chars.as_str().parse::<usize>().ok()?.checked_sub(1)?;
+    (row < rows && col < cols).then_some((row, col))
+}
+
+fn format_cell(row: usize, col: usize) -> String {
+    format!("{}{}", (b'a' + col as u8) as char, row + 1)
+}
+
+/// Prefix every row of a `board_string` with its number and add a column letter footer
+fn label_grid(board: &str, cell_width: usize) -> String {
+    let mut result = String::new();
+    let mut cols = 0;
+    for (row, line) in board.lines().enumerate() {
+        cols = line.len() / cell_width;
+        let cells: Vec<String> = (0..cols).map(|col| format!("{:>2}", &line[col * cell_width..(col + 1) * cell_width].trim())).collect();
+        result.push_str(&format!("{:>2} {}\n", row + 1, cells.join(" ")));
+    }
+    let letters: Vec<String> = (0..cols).map(|col| format!("{:>2}", (b'a' + col as u8) as char)).collect();
+    result.push_str(&format!("   {}\n", letters.join(" ")));
+    result
+}
+
+impl TerminalGame for Connect4Game {
+    const NAME: &'static str = "connect4";
+    
+    fn render(&self) -> String {
+        let mut result = self.board_string();
+        result.push_str(&(1..=self.get_cols()).map(|col| (col % 10).to_string()).collect::<String>());
+        result.push('\n');
+        result
+    }
+    
+    fn parse_move(&self, text: &str) -> Result<Vec<usize>, String> {
+        let (pop, column) = match text.strip_prefix('p') {
+            Some(column) => (true, column),
+            None => (false, text),
+        };
+        let column = column.parse::<usize>().ok().and_then(|column| column.checked_sub(1)).ok_or("Expected a column number like 4 or p4")?;
+        Ok(vec![if pop { self.get_cols() + column } else { column }])
+    }
+    
+    fn format_move(&self, game_move: &[usize]) -> String {
+        let code = game_move[0];
+        if code >= self.get_cols() { format!("p{}", code - self.get_cols() + 1) } else { (code + 1).to_string() }
+    }
+    
+    fn apply(&mut self, game_move: &[usize]) -> Result<(), String> {
+        self.apply_move(game_move).map(|_| ()).map_err(String::from)
+    }
+    
+    fn ai_move(&self) -> Option<Vec<usize>> {
+        self.get_ai_move().map(|code| vec![code])
+    }
+    
+    fn side_to_move(&self) -> usize {
+        side_of(self)
+    }
+    
+    fn outcome(&self) -> Option<String> {
+        self.final_result().map(|result| result.summary())
+    }
+}
+
+impl TerminalGame for GomokuGame {
+    const NAME: &'static str = "gomoku";
+    
+    fn render(&self) -> String {
+        label_grid(&self.board_string(), 1)
+    }
+    
+    fn parse_move(&self, text: &str) -> Result<Vec<usize>, String> {
+        let (row, col) = parse_cell(text, 15, 15).ok_or("Expected a cell like h8")?;
+        Ok(vec![row, col])
+    }
+    
+    fn format_move(&self, game_move: &[usize]) -> String {
+        format_cell(game_move[0], game_move[1])
+    }
+    
+    fn apply(&mut self, game_move: &[usize]) -> Result<(), String> {
+        self.apply_move(game_move).map(|_| ()).map_err(String::from)
+    }
+    
+    fn ai_move(&self) -> Option<Vec<usize>> {
+        Some(self.get_ai_move()).filter(|game_move| !game_move.is_empty())
+    }
+    
+    fn side_to_move(&self) -> usize {
+        side_of(self)
+    }
+    
+    fn outcome(&self) -> Option<String> {
+        self.final_result().map(|result| result.summary())
+    }
+}
+
+impl TerminalGame for LGame {
+    const NAME: &'static str = "lgame";
+    
+    fn render(&self) -> String {
+        label_grid(&self.board_string(), 1)
+    }
+    
+    fn parse_move(&self, text: &str) -> Result<Vec<usize>, String> {
+        const USAGE: &str = "Expected an L-move like a1:3, optionally followed by a neutral move like d1-b3";
+        let mut parts = text.split_whitespace();
+        let (anchor, orientation) = parts.next().and_then(|l_move| l_move.split_once(':')).ok_or(USAGE)?;
+        let (row, col) = parse_cell(anchor, 4, 4).ok_or(USAGE)?;
+        let orientation: usize = orientation.parse().map_err(|_| USAGE)?;
+        let mut game_move = vec![row, col, orientation];
+        if let Some(neutral) = parts.next() {
+            let (from, to) = neutral.split_once('-').ok_or(USAGE)?;
+            let (from_row, from_col) = parse_cell(from, 4, 4).ok_or(USAGE)?;
+            let (to_row, to_col) = parse_cell(to, 4, 4).ok_or(USAGE)?;
+            game_move.extend([from_row, from_col, to_row, to_col]);
+        }
+        Ok(game_move)
+    }
+    
+    fn format_move(&self, game_move: &[usize]) -> String {
+        let l_move = format!("{}:{}", format_cell(game_move[0], game_move[1]), game_move[2]);
+        match game_move {
+            [_, _, _, from_row, from_col, to_row, to_col] => {
+                format!("{} {}-{}", l_move, format_cell(*from_row, *from_col), format_cell(*to_row, *to_col))
+            },
+            _ => l_move,
+        }
+    }
+    
+    fn apply(&mut self, game_move: &[usize]) -> Result<(), String> {
+        self.apply_move(game_move).map(|_| ()).map_err(String::from)
+    }
+    
+    fn ai_move(&self) -> Option<Vec<usize>> {
+        self.get_ai_move().map(|(row, col, orientation)| vec![row, col, orientation as usize])
+    }
+    
+    fn side_to_move(&self) -> usize {
+        side_of(self)
+    }
+    
+    fn outcome(&self) -> Option<String> {
+        self.final_result().map(|result| result.summary())
+    }
+}
+
+/// Trio as a two-player race: sides take turns claiming trios that haven't been claimed yet
+#[derive(Clone)]
+struct TrioMatch {
+    puzzle: TrioGame,
+    claimed: Vec<Vec<(usize, usize)>>, // Sorted cells of every claim
+    scores: [usize; 2],
+    side: usize,
+}
+
+impl TrioMatch {
+    fn new(puzzle: TrioGame) -> Self {
+        Self { puzzle, claimed: Vec::new(), scores: [0; 2], side: 0 }
+    }
+    
+    fn is_claimed(&self, game_move: &[usize]) -> bool {
+        let mut cells: Vec<(usize, usize)> = game_move.chunks(2).map(|cell| (cell[0], cell[1])).collect();
+        cells.sort_unstable();
+        self.claimed.contains(&cells)
+    }
+    
+    fn open_solutions(&self) -> Vec<Vec<usize>> {
+        self.puzzle
+            .find_all_solutions()
+            .chunks(7)
+            .map(|solution| solution[..6].iter().map(|&value| value as usize).collect::<Vec<_>>())
+            .filter(|game_move| !self.is_claimed(game_move))
+            .collect()
+    }
+}
+
+impl TerminalGame for TrioMatch {
+    const NAME: &'static str = "trio";
+    
+    fn render(&self) -> String {
+        format!(
+            "{}Target {}   Score {}-{}\n",
+            label_grid(&self.puzzle.board_string(), 3),
+            self.puzzle.get_target_number(),
+            self.scores[0],
+            self.scores[1]
+        )
+    }
+    
+    fn parse_move(&self, text: &str) -> Result<Vec<usize>, String> {
+        let (rows, cols) = (self.puzzle.get_rows(), self.puzzle.get_cols());
+        let cells: Vec<(usize, usize)> = text
+            .split_whitespace()
+            .map(|cell| parse_cell(cell, rows, cols))
+            .collect::<Option<_>>()
+            .ok_or("Expected three cells like a1 b1 c1")?;
+        if cells.len() != 3 {
+            return Err("Expected three cells like a1 b1 c1".to_string());
+        }
+        Ok(cells.into_iter().flat_map(|(row, col)| [row, col]).collect())
+    }
+    
+    fn format_move(&self, game_move: &[usize]) -> String {
+        game_move.chunks(2).map(|cell| format_cell(cell[0], cell[1])).collect::<Vec<_>>().join(" ")
+    }
+    
+    fn apply(&mut self, game_move: &[usize]) -> Result<(), String> {
+        if self.outcome().is_some() {
+            return Err("Game is already over".to_string());
+        }
+        if self.is_claimed(game_move) {
+            return Err("Trio already claimed".to_string());
+        }
+        let &[row1, col1, row2, col2, row3, col3] = game_move else {
+            return Err("Invalid move".to_string());
+        };
+        self.puzzle.make_move(row1, col1, row2, col2, row3, col3)?;
+    
+        let mut cells = vec![(row1, col1), (row2, col2), (row3, col3)];
+        cells.sort_unstable();
+        self.claimed.push(cells);
+        self.scores[self.side] += 1;
+        self.side = 1 - self.side;
+        Ok(())
+    }
+    
+    fn ai_move(&self) -> Option<Vec<usize>> {
+        self.open_solutions().into_iter().next()
+    }
+    
+    fn side_to_move(&self) -> usize {
+        self.side
+    }
+    
+    fn outcome(&self) -> Option<String> {
+        if !self.open_solutions().is_empty() {
+            return None;
+        }
+        Some(match self.scores[0].cmp(&self.scores[1]) {
+            std::cmp::Ordering::Greater => format!("First player wins {}-{}", self.scores[0], self.scores[1]),
+            std::cmp::Ordering::Less => format!("Second player wins {}-{}", self.scores[1], self.scores[0]),
+            std::cmp::Ordering::Equal => format!("Draw {}-{}", self.scores[0], self.scores[1]),
+        })
+    }
+    
+    fn setup(&self) -> Option<String> {
+        Some(format!("puzzle {}", self.puzzle.to_code()))
+    }
+}
+
+/// A game in progress with its move list, so undo and saving work the same for every game
+struct Session<G: TerminalGame> {
+    initial: G,
+    game: G,
+    moves: Vec<Vec<usize>>,
+    ai_sides: [bool; 2],
+}
+
+impl<G: TerminalGame> Session<G> {
+    fn new(initial: G, ai_sides: [bool; 2]) -> Self {
+        Self { game: initial.clone(), initial, moves: Vec::new(), ai_sides }
+    }
+    
+    fn play(&mut self, game_move: Vec<usize>) -> Result<(), String> {
+        self.game.apply(&game_move)?;
+        self.moves.push(game_move);
+        Ok(())
+    }
+    
+    /// Take back the last move, and against the AI also the AI's reply, so the human is to move again
+    fn undo(&mut self) -> bool {
+        if self.moves.is_empty() {
+            return false;
+        }
+        self.moves.pop();
+        while !self.moves.is_empty() && self.ai_sides[self.side_after(self.moves.len())] && !self.ai_sides.iter().all(|&ai| ai) {
+            self.moves.pop();
+        }
+        self.replay();
+        true
+    }
+    
+    /// Side to move after the first `count` moves
+    fn side_after(&self, count: usize) -> usize {
+        let mut game = self.initial.clone();
+        for game_move in &self.moves[..count] {
+            let _ = game.apply(game_move);
+        }
+        game.side_to_move()
+    }
+    
+    fn replay(&mut self) {
+        self.game = self.initial.clone();
+        for game_move in &self.moves {
+            self.game.apply(game_move).expect("recorded moves replay");
+        }
+    }
+    
+    /// Saved game: `game <name>`, an optional setup line, then one move per line in the game's notation
+    fn record(&self) -> String {
+        let mut lines = vec![format!("game {}", G::NAME)];
+        lines.extend(self.initial.setup());
+        let mut game = self.initial.clone();
+        for game_move in &self.moves {
+            lines.push(game.format_move(game_move));
+            let _ = game.apply(game_move);
+        }
+        lines.join("\n") + "\n"
+    }
+    
+    /// Play the moves of a saved game (after the header lines)
+    fn load_moves<'a>(&mut self, lines: impl Iterator<Item = &'a str>) -> Result<(), String> {
+        for (number, line) in lines.enumerate() {
+            let game_move = self.game.parse_move(line.trim())?;
+            self.play(game_move).map_err(|e| format!("Move {} ({}): {}", number + 1, line.trim(), e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Command line options
+struct Options {
+    game: String,
+    ai_sides: [bool; 2],
+    difficulty: Option<u8>, // 1-4; each game picks its own default
+    max_moves: usize,
+    load: Option<String>,
+}
+
+const USAGE: &str = "Usage: logiccastle-cli <connect4|gomoku|lgame|trio> [--ai none|first|second|both] [--difficulty 1-4] [--max-moves N] [--load FILE]";
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options { game: String::new(), ai_sides: [false, true], difficulty: None, max_moves: 300, load: None };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--ai" => {
+                options.ai_sides = match value()?.as_str() {
+                    "none" => [false, false],
+                    "first" => [true, false],
+                    "second" => [false, true],
+                    "both" => [true, true],
+                    other => return Err(format!("Unknown AI setting {}", other)),
+                }
+            },
+            "--difficulty" => {
+                let difficulty = value()?.parse().ok().filter(|difficulty| (1..=4).contains(difficulty));
+                options.difficulty = Some(difficulty.ok_or("Difficulty must be 1-4")?);
+            },
+            "--max-moves" => options.max_moves = value()?.parse().map_err(|_| "Move limit must be a number".to_string())?,
+            "--load" => options.load = Some(value()?),
+            game if options.game.is_empty() && !game.starts_with('-') => options.game = game.to_string(),
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+    Ok(options)
+}
+
+/// Interactive loop: humans type moves or commands, AI sides move on their own
+fn run<G: TerminalGame>(mut session: Session<G>, max_moves: usize, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    const SIDES: [&str; 2] = ["First player", "Second player"];
+    writeln!(output, "{}", session.game.render())?;
+    loop {
+        if let Some(outcome) = session.game.outcome() {
+            writeln!(output, "Game over: {}", outcome)?;
+            return Ok(());
+        }
+        if session.moves.len() >= max_moves {
+            writeln!(output, "Move limit of {} reached", max_moves)?;
+            return Ok(());
+        }
+    
+        let side = session.game.side_to_move();
+        if session.ai_sides[side] {
+            let Some(game_move) = session.game.ai_move() else {
+                writeln!(output, "{} (AI) has no move", SIDES[side])?;
+                return Ok(());
+            };
+            let notation = session.game.format_move(&game_move);
+            if let Err(error) = session.play(game_move) {
+                writeln!(output, "AI move {} rejected: {}", notation, error)?;
+                return Ok(());
+            }
+            writeln!(output, "{} (AI) plays {}\n{}", SIDES[side], notation, session.game.render())?;
+            continue;
+        }
+    
+        write!(output, "{} to move> ", SIDES[side])?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let line = line.trim();
+        match line.split_once(' ').map_or((line, ""), |(command, rest)| (command, rest.trim())) {
+            ("", _) => {},
+            ("quit" | "exit", _) => return Ok(()),
+            ("help", _) => writeln!(output, "Commands: <move>, undo, hint, board, moves, save <file>, quit\n{}", USAGE)?,
+            ("board", _) => writeln!(output, "{}", session.game.render())?,
+            ("moves", _) => write!(output, "{}", session.record())?,
+            ("hint", _) => match session.game.ai_move() {
+                Some(game_move) => writeln!(output, "AI suggests {}", session.game.format_move(&game_move))?,
+                None => writeln!(output, "No suggestion")?,
+            },
+            ("undo", _) => {
+                if session.undo() {
+                    writeln!(output, "{}", session.game.render())?;
+                } else {
+                    writeln!(output, "Nothing to undo")?;
+                }
+            },
+            ("save", path) if !path.is_empty() => match fs::write(path, session.record()) {
+                Ok(()) => writeln!(output, "Saved to {}", path)?,
+                Err(error) => writeln!(output, "Cannot save to {}: {}", path, error)?,
+            },
+            _ => match session.game.parse_move(line).and_then(|game_move| session.play(game_move)) {
+                Ok(()) => writeln!(output, "{}", session.game.render())?,
+                Err(error) => writeln!(output, "{}", error)?,
+            },
+        }
+    }
+}
+
+/// Connect4 has three AI levels, so 3 and 4 both play hard
+fn connect4_difficulty(difficulty: u8) -> AIDifficulty {
+    match difficulty {
+        1 => AIDifficulty::Easy,
+        2 => AIDifficulty::Medium,
+        _ => AIDifficulty::Hard,
+    }
+}
+
+/// Gomoku search depth in plies (difficulty 2 keeps the AI's default of 4)
+fn gomoku_depth(difficulty: u8) -> usize {
+    difficulty as usize + 2
+}
+
+fn start<G: TerminalGame>(initial: G, options: &Options, saved_moves: Vec<&str>) -> Result<(), String> {
+    let mut session = Session::new(initial, options.ai_sides);
+    session.load_moves(saved_moves.into_iter())?;
+    let stdin = io::stdin();
+    run(session, options.max_moves, &mut stdin.lock(), &mut io::stdout()).map_err(|e| e.to_string())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = parse_options(&args).and_then(|mut options| {
+        let saved = match &options.load {
+            Some(path) => fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path, e))?,
+            None => String::new(),
+        };
+        let mut lines = saved.lines().filter(|line| !line.trim().is_empty());
+        if options.load.is_some() {
+            let header = lines.next().unwrap_or_default();
+            options.game = header.strip_prefix("game ").ok_or("Saved game must start with `game <name>`")?.trim().to_string();
+        }
+    
+        match options.game.as_str() {
+            "connect4" => {
+                let mut game = Connect4Game::new();
+                game.set_ai_difficulty(connect4_difficulty(options.difficulty.unwrap_or(2)));
+                start(game, &options, lines.collect())
+            },
+            "gomoku" => {
+                let mut game = GomokuGame::new();
+                game.set_ai(&GomokuAI::new_with_depth(gomoku_depth(options.difficulty.unwrap_or(2))));
+                start(game, &options, lines.collect())
+            },
+            "lgame" if options.difficulty.is_some() => Err("The L-Game AI has no difficulty levels".to_string()),
+            "lgame" => start(LGame::new(), &options, lines.collect()),
+            "trio" => {
+                let puzzle = match options.load {
+                    Some(_) => {
+                        let setup = lines.next().and_then(|line| line.strip_prefix("puzzle ")).ok_or("Saved Trio game needs a `puzzle <code>` line")?;
+                        TrioGame::from_code(setup.trim()).map_err(String::from)?
+                    },
+                    None => TrioGame::new(options.difficulty.unwrap_or(2)),
+                };
+                start(TrioMatch::new(puzzle), &options, lines.collect())
+            },
+            _ => Err(USAGE.to_string()),
+        }
+    });
+    
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_notation_round_trip() {
+        let connect4 = Connect4Game::new();
+        assert_eq!(connect4.parse_move("4"), Ok(vec![3]));
+        assert_eq!(connect4.parse_move("p2"), Ok(vec![8]));
+        assert_eq!(connect4.format_move(&[8]), "p2");
+    
+        let gomoku = GomokuGame::new();
+        assert_eq!(gomoku.parse_move("h8"), Ok(vec![7, 7]));
+        assert!(gomoku.parse_move("p1").is_err());
+    
+        let l_game = LGame::new();
+        let game_move = l_game.parse_move("b1:3 d1-b3").unwrap();
+        assert_eq!(game_move, vec![0, 1, 3, 0, 3, 2, 1]);
+        assert_eq!(l_game.format_move(&game_move), "b1:3 d1-b3");
+    }
+    
+    #[test]
+    fn test_undo_against_ai_and_saved_record() {
+        let mut session = Session::new(Connect4Game::new(), [false, true]);
+        session.play(vec![3]).unwrap();
+        session.play(vec![3]).unwrap(); // AI reply
+        session.play(vec![2]).unwrap();
+        assert!(session.undo());
+        assert_eq!(session.moves, vec![vec![3], vec![3]]);
+        assert!(session.undo()); // Takes back the AI reply too
+        assert!(session.moves.is_empty());
+        assert!(!session.undo());
+    
+        let mut session = Session::new(LGame::new(), [false, false]);
+        let (row, col, orientation) = session.game.get_ai_move().unwrap();
+        session.play(vec![row, col, orientation as usize]).unwrap();
+        let record = session.record();
+        let mut loaded = Session::new(LGame::new(), [false, false]);
+        loaded.load_moves(record.lines().skip(1)).unwrap();
+        assert_eq!(loaded.game.state_hash(), session.game.state_hash());
+    }
+    
+    #[test]
+    fn test_trio_match_claims() {
+        let mut trio = TrioMatch::new(TrioGame::new(1));
+        while let Some(game_move) = trio.ai_move() {
+            trio.apply(&game_move).unwrap();
+            assert!(trio.apply(&game_move).is_err()); // Claimed trios can't be claimed again
+        }
+        assert!(trio.outcome().is_some());
+        assert_eq!(trio.scores[0] + trio.scores[1], trio.claimed.len());
+    }
+    
+    #[test]
+    fn test_difficulty_option() {
+        let args = |text: &str| text.split_whitespace().map(String::from).collect::<Vec<String>>();
+        assert_eq!(parse_options(&args("gomoku --difficulty 4")).unwrap().difficulty, Some(4));
+        assert_eq!(parse_options(&args("gomoku")).unwrap().difficulty, None);
+        assert!(parse_options(&args("gomoku --difficulty 5")).is_err());
+        assert!(parse_options(&args("connect4 --difficulty 0")).is_err());
+        
+        assert_eq!(connect4_difficulty(1), AIDifficulty::Easy);
+        assert_eq!(connect4_difficulty(4), AIDifficulty::Hard);
+        assert_eq!(gomoku_depth(2), 4); // The Gomoku AI's default depth
+    }
+}
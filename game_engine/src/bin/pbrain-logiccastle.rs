@@ -0,0 +1,325 @@
+//! Gomocup (Piskvork) brain: plays `GomokuAI` over the brain protocol on stdin/stdout
+//!
+//! Supported commands: START, RECTSTART, RESTART, BEGIN, TURN, BOARD ... DONE, TAKEBACK, INFO, ABOUT, END.
+//! Coordinates are `x,y` with x the column and y the row, both 0-based.
+//! Only 15x15 boards are supported; other sizes are refused with ERROR as the protocol allows.
+//! INFO rule: 1 = exact five, 4 = Renju (continuous games and Caro are not supported).
+//! INFO timeout_turn, timeout_match and time_left set the deadline of every move's search.
+
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+use game_engine::ai::connect4_ai::SearchLimits;
+use game_engine::ai::GomokuAI;
+use game_engine::{GomokuGame, Player};
+
+/// The only board size `GomokuGame` plays on
+const BOARD_SIZE: usize = 15;
+
+/// Rule bits sent with `INFO rule`
+const RULE_EXACT_FIVE: u32 = 1;
+const RULE_CONTINUOUS: u32 = 2;
+const RULE_RENJU: u32 = 4;
+const RULE_CARO: u32 = 8;
+
+/// Moves the remaining match time is spread over
+const MOVES_TO_GO: u64 = 25;
+
+/// Share of the move budget the search may use; the rest covers the tactical checks and replying
+const SEARCH_SHARE_PERCENT: u64 = 80;
+
+/// Protocol state of one brain process
+struct Brain {
+    game: GomokuGame,
+    started: bool,
+    rule: u32,
+    timeout_turn: u64,  // ms per move, 0 = play as fast as possible
+    timeout_match: u64, // ms per match, 0 = no limit
+    time_left: Option<u64>,
+    board_input: Option<Vec<(usize, usize, u8)>>, // Stones collected between BOARD and DONE
+}
+
+impl Brain {
+    fn new() -> Self {
+        Self {
+            game: GomokuGame::new(),
+            started: false,
+            rule: 0,
+            timeout_turn: 30_000,
+            timeout_match: 0,
+            time_left: None,
+            board_input: None,
+        }
+    }
+    
+    /// Answer one input line; None once the manager sent END
+    fn handle(&mut self, line: &str) -> Option<Vec<String>> {
+        let line = line.trim();
+        if let Some(stones) = self.board_input.as_mut() {
+            if line.eq_ignore_ascii_case("DONE") {
+                let stones = self.board_input.take().unwrap_or_default();
+                return Some(vec![self.load_board(&stones).and_then(|()| self.reply()).unwrap_or_else(error)]);
+            }
+            return Some(match parse_stone(line) {
+                Some(stone) => {
+                    stones.push(stone);
+                    Vec::new()
+                },
+                None => vec![error(format!("bad BOARD line `{}`", line))],
+            });
+        }
+    
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+        let args = args.trim();
+        let reply = match command.to_ascii_uppercase().as_str() {
+            "" => return Some(Vec::new()),
+            "END" => return None,
+            "START" => self.start(args.parse().ok(), args.parse().ok()),
+            "RECTSTART" => {
+                let (width, height) = args.split_once(',').unwrap_or((args, ""));
+                self.start(width.trim().parse().ok(), height.trim().parse().ok())
+            },
+            "RESTART" if self.started => {
+                self.new_game();
+                Ok("OK".to_string())
+            },
+            "BEGIN" if self.started => self.new_game_reply(),
+            "TURN" if self.started => self.turn(args),
+            "BOARD" if self.started => {
+                self.board_input = Some(Vec::new());
+                return Some(Vec::new());
+            },
+            "TAKEBACK" if self.started => self.takeback(args),
+            "RESTART" | "BEGIN" | "TURN" | "BOARD" | "TAKEBACK" => Err("no START received".to_string()),
+            "INFO" => return Some(self.info(args)),
+            "ABOUT" => Ok(format!("name=\"LogicCastle\", version=\"{}\"", env!("CARGO_PKG_VERSION"))),
+            _ => return Some(vec![format!("UNKNOWN command `{}`", command)]),
+        };
+        Some(vec![reply.unwrap_or_else(error)])
+    }
+    
+    fn start(&mut self, width: Option<usize>, height: Option<usize>) -> Result<String, String> {
+        if width != Some(BOARD_SIZE) || height != Some(BOARD_SIZE) {
+            return Err(format!("unsupported size, only {0}x{0} boards are supported", BOARD_SIZE));
+        }
+        self.started = true;
+        self.new_game();
+        Ok("OK".to_string())
+    }
+    
+    fn new_game(&mut self) {
+        self.game = GomokuGame::new();
+        self.apply_rules();
+    }
+    
+    /// BEGIN: the brain plays the first stone of a fresh game
+    fn new_game_reply(&mut self) -> Result<String, String> {
+        if self.game.move_count() > 0 {
+            self.new_game();
+        }
+        self.reply()
+    }
+    
+    /// TURN x,y: play the opponent's stone and answer with our own
+    fn turn(&mut self, args: &str) -> Result<String, String> {
+        let (row, col) = parse_coords(args).ok_or_else(|| format!("bad coordinates `{}`", args))?;
+        self.game.make_move(row, col).map_err(|e| e.to_string())?;
+        self.reply()
+    }
+    
+    /// TAKEBACK x,y: undo the last stone if it is the one named
+    fn takeback(&mut self, args: &str) -> Result<String, String> {
+        let (row, col) = parse_coords(args).ok_or_else(|| format!("bad coordinates `{}`", args))?;
+        if self.game.last_move() != Some((row, col)) || !self.game.undo_move() {
+            return Err(format!("{},{} is not the last stone", col, row));
+        }
+        Ok("OK".to_string())
+    }
+    
+    /// INFO key value: rules and time limits (never answered except for unsupported rules)
+    fn info(&mut self, args: &str) -> Vec<String> {
+        let (key, value) = args.split_once(' ').unwrap_or((args, ""));
+        let Ok(value) = value.trim().parse::<u64>() else {
+            return Vec::new();
+        };
+        match key.to_ascii_lowercase().as_str() {
+            "timeout_turn" => self.timeout_turn = value,
+            "timeout_match" => self.timeout_match = value,
+            "time_left" => self.time_left = Some(value),
+            "rule" => {
+                self.rule = value as u32;
+                self.apply_rules();
+                if self.rule & (RULE_CONTINUOUS | RULE_CARO) != 0 {
+                    return vec!["MESSAGE continuous and Caro rules are not supported".to_string()];
+                }
+            },
+            _ => {}
+        }
+        Vec::new()
+    }
+    
+    fn apply_rules(&mut self) {
+        self.game.set_exact_five_enabled(self.rule & RULE_EXACT_FIVE != 0);
+        self.game.set_renju_enabled(self.rule & RULE_RENJU != 0);
+    }
+    
+    /// Rebuild the game from a BOARD listing (1 = own stone, 2 = opponent's, 3 = winning line)
+    /// The brain moves next, so it plays Black when both sides have the same number of stones
+    fn load_board(&mut self, stones: &[(usize, usize, u8)]) -> Result<(), String> {
+        let own: Vec<(usize, usize)> = stones.iter().filter(|stone| stone.2 == 1).map(|&(row, col, _)| (row, col)).collect();
+        let opponent: Vec<(usize, usize)> = stones.iter().filter(|stone| stone.2 != 1).map(|&(row, col, _)| (row, col)).collect();
+        let (black, white) = match opponent.len().checked_sub(own.len()) {
+            Some(0) => (own, opponent),
+            Some(1) => (opponent, own),
+            _ => return Err(format!("cannot move with {} own and {} opponent stones", own.len(), opponent.len())),
+        };
+    
+        self.new_game();
+        // Replay alternately, keeping each side's listed order
+        for (index, &(row, col)) in black.iter().enumerate() {
+            self.game.make_move(row, col).map_err(|e| e.to_string())?;
+            if let Some(&(row, col)) = white.get(index) {
+                self.game.make_move(row, col).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Choose, play and format the brain's move
+    fn reply(&mut self) -> Result<String, String> {
+        if self.game.is_game_over() {
+            return Err("the game is already over".to_string());
+        }
+        let (row, col) = GomokuAI::new().search(&self.game, &self.search_limits())
+            .or_else(|| self.first_legal_move())
+            .ok_or("no legal move left")?;
+        self.game.make_move(row, col).map_err(|e| e.to_string())?;
+        Ok(format!("{},{}", col, row))
+    }
+    
+    /// Search deadline for this move's share of the time
+    fn search_limits(&self) -> SearchLimits {
+        let budget = self.move_budget_ms() * SEARCH_SHARE_PERCENT / 100;
+        SearchLimits { deadline: Some(Instant::now() + Duration::from_millis(budget)), ..SearchLimits::default() }
+    }
+    
+    /// Turn limit, further capped by an even share of the remaining match time
+    fn move_budget_ms(&self) -> u64 {
+        if self.timeout_match == 0 {
+            return self.timeout_turn;
+        }
+        let left = self.time_left.unwrap_or(self.timeout_match);
+        self.timeout_turn.min(left / MOVES_TO_GO)
+    }
+    
+    /// Fallback when the AI finds nothing: first cell the side to move may play
+    fn first_legal_move(&self) -> Option<(usize, usize)> {
+        let renju_black = self.game.is_renju_enabled() && self.game.current_player() == Player::Black;
+        (0..BOARD_SIZE)
+            .flat_map(|row| (0..BOARD_SIZE).map(move |col| (row, col)))
+            .find(|&(row, col)| self.game.is_valid_move(row, col) && !(renju_black && self.game.is_forbidden_point(row, col)))
+    }
+}
+
+fn error(message: String) -> String {
+    format!("ERROR {}", message)
+}
+
+/// `x,y` as (row, col)
+fn parse_coords(text: &str) -> Option<(usize, usize)> {
+    let (x, y) = text.split_once(',')?;
+    let (col, row) = (x.trim().parse::<usize>().ok()?, y.trim().parse::<usize>().ok()?);
+    (row < BOARD_SIZE && col < BOARD_SIZE).then_some((row, col))
+}
+
+/// `x,y,field` as (row, col, field)
+fn parse_stone(text: &str) -> Option<(usize, usize, u8)> {
+    let (coords, field) = text.rsplit_once(',')?;
+    let (row, col) = parse_coords(coords)?;
+    let field = field.trim().parse::<u8>().ok().filter(|field| (1..=3).contains(field))?;
+    Some((row, col, field))
+}
+
+fn main() {
+    let mut brain = Brain::new();
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let Some(replies) = brain.handle(&line) else {
+            break;
+        };
+        for reply in replies {
+            if writeln!(stdout, "{}", reply).and_then(|()| stdout.flush()).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn send(brain: &mut Brain, line: &str) -> Vec<String> {
+        brain.handle(line).expect("brain ended")
+    }
+    
+    #[test]
+    fn test_start_turn_and_takeback() {
+        let mut brain = Brain::new();
+        assert!(send(&mut brain, "START 20")[0].starts_with("ERROR"));
+        assert!(send(&mut brain, "TURN 7,7")[0].starts_with("ERROR"));
+        assert_eq!(send(&mut brain, "START 15"), vec!["OK"]);
+    
+        let reply = send(&mut brain, "TURN 7,7");
+        let (row, col) = parse_coords(&reply[0]).expect("brain answers with a move");
+        assert_eq!(brain.game.get_cell(7, 7), 1); // Opponent plays Black
+        assert_eq!(brain.game.get_cell(row, col), 2);
+        assert!(send(&mut brain, "TURN 7,7")[0].starts_with("ERROR")); // Occupied
+    
+        assert_eq!(send(&mut brain, &format!("TAKEBACK {}", reply[0])), vec!["OK"]);
+        assert_eq!(brain.game.move_count(), 1);
+        assert!(send(&mut brain, "TAKEBACK 0,0")[0].starts_with("ERROR"));
+        assert!(brain.handle("END").is_none());
+    }
+    
+    #[test]
+    fn test_board_finds_winning_move() {
+        let mut brain = Brain::new();
+        send(&mut brain, "START 15");
+        assert!(send(&mut brain, "INFO rule 1").is_empty());
+        assert!(brain.game.is_exact_five_enabled());
+    
+        // Own stones on row 3, columns 3-6; the opponent has one stone more on row 10
+        assert!(send(&mut brain, "BOARD").is_empty());
+        for stone in ["3,3,1", "0,10,2", "4,3,1", "2,10,2", "5,3,1", "8,10,2", "6,3,1", "12,10,2", "14,14,2"] {
+            assert!(send(&mut brain, stone).is_empty());
+        }
+        let reply = send(&mut brain, "DONE");
+        assert!(reply == ["2,3"] || reply == ["7,3"], "{:?}", reply);
+        assert_eq!(brain.game.winner(), Some(Player::White));
+    }
+    
+    #[test]
+    fn test_time_limits_cap_search() {
+        let mut brain = Brain::new();
+        assert_eq!(brain.move_budget_ms(), 30_000);
+        brain.handle("INFO timeout_turn 5000");
+        brain.handle("INFO timeout_match 100000");
+        brain.handle("INFO time_left 5000");
+        assert_eq!(brain.move_budget_ms(), 200);
+        
+        // This quiet position takes over a minute at full depth; the deadline cuts the search short
+        let moves = ["7,7", "8,8", "3,11", "11,3"];
+        for (index, game_move) in moves.iter().enumerate() {
+            let (col, row) = game_move.split_once(',').unwrap();
+            brain.game.make_move(row.parse().unwrap(), col.parse().unwrap()).unwrap();
+            assert_eq!(brain.game.move_count(), index + 1);
+        }
+        let started = Instant::now();
+        assert!(GomokuAI::new().search(&brain.game, &brain.search_limits()).is_some());
+        assert!(started.elapsed() < Duration::from_millis(2_000));
+    }
+}
@@ -0,0 +1,145 @@
+//! Evaluator tuning: plays seeded self-play games, then fits the evaluation weights to the results
+//!
+//! Usage: tune <connect4|gomoku> [--games N] [--seed S] [--depth D] [--max-moves N] [--passes N]
+//!             [--params FILE] [--out FILE]
+//!
+//! `--params` gives the starting weights (JSON, see `EvaluatorProfile::from_json`); the tuned set is
+//! written as JSON to `--out`, or printed if no file is given.
+
+use std::process::ExitCode;
+use game_engine::ai::arena::{run_arena, ArenaConfig, ArenaGameRecord, ArenaPlayer};
+use game_engine::ai::tuning::{labeled_positions, tune, TunableGame, TuningReport};
+use game_engine::{Connect4AI, Connect4Game, EvaluatorParams, GomokuAI, GomokuGame, PatternEvaluator};
+
+const USAGE: &str = "Usage: tune <connect4|gomoku> [--games N] [--seed S] [--depth D] [--max-moves N] [--passes N] [--params FILE] [--out FILE]";
+
+struct Options {
+    game: String,
+    depth: usize,
+    passes: usize,
+    params: Option<String>,
+    out: Option<String>,
+    config: ArenaConfig,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        game: String::new(),
+        depth: 2,
+        passes: 50,
+        params: None,
+        out: None,
+        config: ArenaConfig { games: 50, ..ArenaConfig::default() },
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--games" => options.config.games = parse_number(&value()?)?,
+            "--seed" => options.config.seed = parse_number(&value()?)?,
+            "--depth" => options.depth = parse_number(&value()?)?,
+            "--max-moves" => options.config.max_moves = parse_number(&value()?)?,
+            "--passes" => options.passes = parse_number(&value()?)?,
+            "--params" => options.params = Some(value()?),
+            "--out" => options.out = Some(value()?),
+            game if options.game.is_empty() && !game.starts_with('-') => options.game = game.to_string(),
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_number<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.trim().parse().map_err(|_| format!("`{}` is not a valid number", text))
+}
+
+fn connect4_player(params: &EvaluatorParams, depth: usize) -> Connect4AI {
+    let mut ai = Connect4AI::new();
+    ai.set_difficulty(depth);
+    ai.set_evaluator(PatternEvaluator::from_params(params));
+    ai
+}
+
+/// Self-play with the starting weights, then fit them to the recorded results
+/// Both sides are the same player, so an opening gives the same game with either color; repeats are dropped
+fn self_play_and_tune<G, P>(start: G, player: P, params: &EvaluatorParams, options: &Options) -> TuningReport
+where
+    G: TunableGame,
+    P: ArenaPlayer<G>,
+{
+    if options.config.games > start.distinct_games() {
+        eprintln!("Openings repeat after {} games, so later games may duplicate earlier ones", start.distinct_games());
+    }
+    let mut records: Vec<ArenaGameRecord> = Vec::new();
+    run_arena(&start, &player, &player, &options.config, |record, stats| {
+        eprintln!("Game {}: {} moves", stats.games(), record.moves.len());
+        if !records.iter().any(|earlier| earlier.moves == record.moves) {
+            records.push(record.clone());
+        }
+    });
+    let positions = labeled_positions(&start, &records);
+    eprintln!("Tuning on {} positions", positions.len());
+    tune(&positions, params, options.passes)
+}
+
+fn run(options: &Options) -> Result<TuningReport, String> {
+    let params = match &options.params {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+            EvaluatorParams::from_json(&text).map_err(|error| format!("{}: {}", path, error))?
+        },
+        None => EvaluatorParams::default(),
+    };
+    
+    match options.game.as_str() {
+        "connect4" => Ok(self_play_and_tune(Connect4Game::new(), connect4_player(&params, options.depth), &params, options)),
+        "gomoku" => Ok(self_play_and_tune(GomokuGame::new(), GomokuAI::new_with_depth(options.depth).with_params(&params), &params, options)),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = parse_options(&args).and_then(|options| {
+        let report = run(&options)?;
+        eprintln!(
+            "Error {:.5} -> {:.5} after {} passes (scale {:.3e})",
+            report.initial_error, report.final_error, report.passes, report.scale
+        );
+        match &options.out {
+            Some(path) => std::fs::write(path, report.params.to_json()).map_err(|error| format!("{}: {}", path, error)),
+            None => {
+                print!("{}", report.params.to_json());
+                Ok(())
+            },
+        }
+    });
+    
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+    
+    #[test]
+    fn test_options() {
+        let options = parse_options(&args("gomoku --games 8 --depth 3 --out tuned.json")).unwrap();
+        assert_eq!(options.game, "gomoku");
+        assert_eq!(options.config.games, 8);
+        assert_eq!(options.depth, 3);
+        assert_eq!(options.out.as_deref(), Some("tuned.json"));
+        assert!(parse_options(&args("connect4 --passes")).is_err());
+        assert!(run(&parse_options(&args("chess")).unwrap()).is_err());
+    }
+}
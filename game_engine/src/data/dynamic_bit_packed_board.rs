@@ -0,0 +1,308 @@
+// DynamicBitPackedBoard is the runtime-sized sibling of BitPackedBoard
+// Used where board dimensions come from configuration instead of const generics
+
+use crate::data::BitPackedBoard;
+
+/// Memory-efficient board with dimensions chosen at runtime
+/// Same bit layout as `BitPackedBoard`: cells are packed row-major into u64 chunks
+///
+/// Parameters:
+/// - rows / cols: Board dimensions
+/// - bits_per_cell: Bits needed per cell (1-8)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynamicBitPackedBoard {
+    pub data: Vec<u64>,
+    rows: usize,
+    cols: usize,
+    bits_per_cell: usize,
+    cells_per_u64: usize,
+    total_cells: usize,
+    mask: u64,
+}
+
+impl DynamicBitPackedBoard {
+    /// Create new DynamicBitPackedBoard (bits_per_cell is clamped to 1-8)
+    pub fn new(rows: usize, cols: usize, bits_per_cell: usize) -> Self {
+        const BITS_PER_U64: usize = 64;
+        let bits_per_cell = bits_per_cell.clamp(1, 8);
+        let cells_per_u64 = BITS_PER_U64 / bits_per_cell;
+        let total_cells = rows * cols;
+        let data_size = total_cells.div_ceil(cells_per_u64);
+        let mask = (1u64 << bits_per_cell) - 1;
+        
+        Self {
+            data: vec![0; data_size],
+            rows,
+            cols,
+            bits_per_cell,
+            cells_per_u64,
+            total_cells,
+            mask,
+        }
+    }
+    
+    /// Smallest number of bits that can store values up to `max_value`
+    pub fn bits_for_value(max_value: u8) -> usize {
+        (8 - max_value.leading_zeros() as usize).max(1)
+    }
+    
+    /// Get cell value at (row, col)
+    pub fn get_cell(&self, row: usize, col: usize) -> u8 {
+        if row >= self.rows || col >= self.cols {
+            return 0;
+        }
+        
+        let cell_index = row * self.cols + col;
+        let u64_index = cell_index / self.cells_per_u64;
+        let bit_offset = (cell_index % self.cells_per_u64) * self.bits_per_cell;
+        
+        ((self.data[u64_index] >> bit_offset) & self.mask) as u8
+    }
+    
+    /// Set cell value at (row, col)
+    pub fn set_cell(&mut self, row: usize, col: usize, value: u8) -> Result<(), String> {
+        if row >= self.rows || col >= self.cols {
+            return Err("Position out of bounds".to_string());
+        }
+        
+        if (value as u64) > self.mask {
+            return Err("Value exceeds maximum for cell".to_string());
+        }
+        
+        let cell_index = row * self.cols + col;
+        let u64_index = cell_index / self.cells_per_u64;
+        let bit_offset = (cell_index % self.cells_per_u64) * self.bits_per_cell;
+        
+        // Clear existing bits and set new value
+        self.data[u64_index] &= !(self.mask << bit_offset);
+        self.data[u64_index] |= (value as u64) << bit_offset;
+        
+        Ok(())
+    }
+    
+    /// Clear all cells
+    pub fn clear(&mut self) {
+        self.data.fill(0);
+    }
+    
+    /// Clear a specific cell by linear index
+    pub fn clear_index(&mut self, index: usize) {
+        if index >= self.total_cells {
+            return;
+        }
+        
+        let u64_index = index / self.cells_per_u64;
+        let bit_offset = (index % self.cells_per_u64) * self.bits_per_cell;
+        self.data[u64_index] &= !(self.mask << bit_offset);
+    }
+    
+    /// Clear a specific cell by row/col coordinates
+    pub fn clear_cell(&mut self, row: usize, col: usize) {
+        if let Some(index) = self.coord_to_index(row, col) {
+            self.clear_index(index);
+        }
+    }
+    
+    /// Get memory usage in bytes
+    pub fn memory_usage(&self) -> usize {
+        self.data.len() * std::mem::size_of::<u64>()
+    }
+    
+    /// Get board dimensions
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+    
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+    
+    pub fn bits_per_cell(&self) -> usize {
+        self.bits_per_cell
+    }
+    
+    /// Largest value a cell can hold
+    pub fn max_value(&self) -> u8 {
+        self.mask as u8
+    }
+    
+    /// Get total number of cells
+    pub fn total_cells(&self) -> usize {
+        self.total_cells
+    }
+    
+    /// Check if position is valid
+    pub fn is_valid_position(&self, row: usize, col: usize) -> bool {
+        row < self.rows && col < self.cols
+    }
+    
+    /// Get all non-empty cells as vector of (row, col, value)
+    pub fn get_occupied_cells(&self) -> Vec<(usize, usize, u8)> {
+        let mut occupied = Vec::new();
+        
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let value = self.get_cell(row, col);
+                if value != 0 {
+                    occupied.push((row, col, value));
+                }
+            }
+        }
+        
+        occupied
+    }
+    
+    /// Count cells with specific value
+    pub fn count_cells_with_value(&self, target_value: u8) -> usize {
+        (0..self.total_cells)
+            .filter(|&index| self.get_cell(index / self.cols, index % self.cols) == target_value)
+            .count()
+    }
+    
+    /// Count how many set bits this board shares with a mask of the same shape
+    pub fn count_set_bits_in_mask(&self, mask: &DynamicBitPackedBoard) -> u32 {
+        self.data
+            .iter()
+            .zip(&mask.data)
+            .map(|(self_chunk, mask_chunk)| (self_chunk & mask_chunk).count_ones())
+            .sum()
+    }
+    
+    /// Set a bit at the given linear index (for mask creation)
+    pub fn set_bit(&mut self, index: usize, value: bool) {
+        if index >= self.total_cells {
+            return;
+        }
+        
+        let u64_index = index / self.cells_per_u64;
+        let bit_offset = (index % self.cells_per_u64) * self.bits_per_cell;
+        
+        if value {
+            self.data[u64_index] |= 1u64 << bit_offset;
+        } else {
+            self.data[u64_index] &= !(1u64 << bit_offset);
+        }
+    }
+    
+    /// Get a bit at the given linear index
+    pub fn get_bit(&self, index: usize) -> bool {
+        if index >= self.total_cells {
+            return false;
+        }
+        
+        let u64_index = index / self.cells_per_u64;
+        let bit_offset = (index % self.cells_per_u64) * self.bits_per_cell;
+        
+        (self.data[u64_index] >> bit_offset) & 1 == 1
+    }
+    
+    /// Convert 2D coordinates to linear index
+    pub fn coord_to_index(&self, row: usize, col: usize) -> Option<usize> {
+        if row < self.rows && col < self.cols {
+            Some(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+    
+    /// Convert linear index to 2D coordinates
+    pub fn index_to_coord(&self, index: usize) -> Option<(usize, usize)> {
+        if index < self.total_cells {
+            Some((index / self.cols, index % self.cols))
+        } else {
+            None
+        }
+    }
+    
+    /// XOR operation for finding differences between boards of the same shape
+    pub fn xor(&self, other: &DynamicBitPackedBoard) -> DynamicBitPackedBoard {
+        let mut result = self.clone();
+        
+        for (result_chunk, other_chunk) in result.data.iter_mut().zip(&other.data) {
+            *result_chunk ^= other_chunk;
+        }
+        
+        result
+    }
+    
+    /// Count total set bits in this board
+    pub fn count_set_bits(&self) -> u32 {
+        self.data.iter().map(|chunk| chunk.count_ones()).sum()
+    }
+    
+    /// Find the index of the first set bit
+    pub fn first_set_bit_index(&self) -> Option<usize> {
+        for (chunk_idx, chunk) in self.data.iter().enumerate() {
+            if *chunk != 0 {
+                let bit_pos = chunk.trailing_zeros();
+                let absolute_index = chunk_idx * self.cells_per_u64 + (bit_pos as usize / self.bits_per_cell);
+                if absolute_index < self.total_cells {
+                    return Some(absolute_index);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const BITS_PER_CELL: usize> From<&BitPackedBoard<ROWS, COLS, BITS_PER_CELL>> for DynamicBitPackedBoard {
+    /// Convert a fixed-size board (identical bit layout, so the data is copied as-is)
+    fn from(board: &BitPackedBoard<ROWS, COLS, BITS_PER_CELL>) -> Self {
+        let mut dynamic = Self::new(ROWS, COLS, BITS_PER_CELL);
+        dynamic.data.copy_from_slice(&board.data);
+        dynamic
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_basic_operations() {
+        let mut board = DynamicBitPackedBoard::new(9, 11, 5);
+        
+        board.set_cell(8, 10, 31).unwrap();
+        board.set_cell(0, 0, 17).unwrap();
+        assert_eq!(board.get_cell(8, 10), 31);
+        assert_eq!(board.get_cell(0, 0), 17);
+        assert_eq!(board.dimensions(), (9, 11));
+        
+        // Bounds and value checks
+        assert!(board.set_cell(9, 0, 1).is_err());
+        assert!(board.set_cell(0, 0, 32).is_err());
+        assert_eq!(board.get_cell(20, 20), 0);
+        
+        board.clear_cell(0, 0);
+        assert_eq!(board.get_cell(0, 0), 0);
+        assert_eq!(board.get_occupied_cells(), vec![(8, 10, 31)]);
+    }
+    
+    #[test]
+    fn test_matches_const_generic_layout() {
+        let mut fixed: BitPackedBoard<6, 7, 2> = BitPackedBoard::new();
+        let mut dynamic = DynamicBitPackedBoard::new(6, 7, 2);
+        
+        for (row, col, value) in [(0, 0, 1), (5, 6, 2), (3, 4, 3)] {
+            fixed.set_cell(row, col, value).unwrap();
+            dynamic.set_cell(row, col, value).unwrap();
+        }
+        
+        assert_eq!(fixed.data, dynamic.data);
+        assert_eq!(fixed.memory_usage(), dynamic.memory_usage());
+        assert_eq!(DynamicBitPackedBoard::from(&fixed), dynamic);
+    }
+    
+    #[test]
+    fn test_bits_for_value() {
+        assert_eq!(DynamicBitPackedBoard::bits_for_value(0), 1);
+        assert_eq!(DynamicBitPackedBoard::bits_for_value(1), 1);
+        assert_eq!(DynamicBitPackedBoard::bits_for_value(9), 4);
+        assert_eq!(DynamicBitPackedBoard::bits_for_value(16), 5);
+        assert_eq!(DynamicBitPackedBoard::bits_for_value(255), 8);
+    }
+}
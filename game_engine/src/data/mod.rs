@@ -1,3 +1,5 @@
 pub mod bit_packed_board;
+pub mod dynamic_bit_packed_board;
 
-pub use bit_packed_board::BitPackedBoard;
\ No newline at end of file
+pub use bit_packed_board::BitPackedBoard;
+pub use dynamic_bit_packed_board::DynamicBitPackedBoard;
@@ -0,0 +1,401 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Every error reported by the engine
+/// Each variant has a stable numeric code (see `code`), so frontends can react without matching messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    // General move errors (1xx)
+    OutOfBounds,
+    PositionOccupied,
+    GameAlreadyOver,
+    InvalidPlayer,
+    BoardError,
+    InvalidMove,
+    TimeForfeit,
+    NotYourTurn,
+    
+    // Column games: Connect4 and gravity m,n,k (2xx)
+    InvalidColumn,
+    ColumnFull,
+    PopOutDisabled,
+    NotOwnDisc,          // PopOut of an opponent's bottom disc
+    GravityDisabled,
+    FloatingPiece,       // Gravity variant: piece above a free cell
+    
+    // Gomoku (3xx)
+    ForbiddenRenjuPoint,
+    
+    // L-Game (4xx)
+    InvalidOrientation,  // L-piece orientation outside 0-7
+    LPieceNotMoved,      // The L-piece must end on a different position
+    NeutralNotFound,     // No neutral piece on the source cell
+    
+    // Trio (5xx)
+    NotATrio,            // Cells are not adjacent in a line or don't reach the target
+    InvalidPuzzleCode,   // Unknown characters, wrong length or values outside the rules
+    PuzzleCodeChecksum,
+    UnsupportedPuzzleCodeVersion(u8),
+    InvalidNumberRange { min: u8, max: u8, limit: u8 },
+    NoOperationsEnabled,
+    InvalidPlayerCount { count: usize, min: usize, max: usize },
+    NoRounds,
+    
+    // Game configuration (6xx)
+    InvalidBoardSize { rows: usize, cols: usize, min: usize, max: usize },
+    InvalidWinLength { win_length: usize, rows: usize, cols: usize },
+    InvalidDifficulty(u8),
+    InvalidDate { year: i32, month: u32, day: u32 },
+    MalformedParameters,     // Evaluator parameter set that is not a flat JSON object
+    UnknownParameter(String),
+    UnknownProfile(String),
+    
+    // Matches, resignations and draws (7xx)
+    MatchAlreadyOver,
+    GameNotOver,
+    DrawOfferUnavailable, // Game over or a draw offer is already pending
+    NoDrawOffer,
+    
+    // Network play (8xx)
+    UnsupportedProtocolVersion(u32),
+    MalformedMessage,
+    OutOfSync,
+    ImpersonatedPlayer,   // The opponent acted for the local player
+    ResyncMoveRejected(usize), // 1-based index of the rejected move
+    ResyncMismatch,
+}
+
+/// Languages for error messages
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    English = 0,
+    German = 1,
+}
+
+impl GameError {
+    /// Stable numeric code; codes are never reused or renumbered
+    pub fn code(&self) -> u16 {
+        match self {
+            GameError::OutOfBounds => 100,
+            GameError::PositionOccupied => 101,
+            GameError::GameAlreadyOver => 102,
+            GameError::InvalidPlayer => 103,
+            GameError::BoardError => 104,
+            GameError::InvalidMove => 105,
+            GameError::TimeForfeit => 106,
+            GameError::NotYourTurn => 107,
+            GameError::InvalidColumn => 200,
+            GameError::ColumnFull => 201,
+            GameError::PopOutDisabled => 202,
+            GameError::NotOwnDisc => 203,
+            GameError::GravityDisabled => 204,
+            GameError::FloatingPiece => 205,
+            GameError::ForbiddenRenjuPoint => 300,
+            GameError::InvalidOrientation => 400,
+            GameError::LPieceNotMoved => 401,
+            GameError::NeutralNotFound => 402,
+            GameError::NotATrio => 500,
+            GameError::InvalidPuzzleCode => 501,
+            GameError::PuzzleCodeChecksum => 502,
+            GameError::UnsupportedPuzzleCodeVersion(_) => 503,
+            GameError::InvalidNumberRange { .. } => 504,
+            GameError::NoOperationsEnabled => 505,
+            GameError::InvalidPlayerCount { .. } => 506,
+            GameError::NoRounds => 507,
+            GameError::InvalidBoardSize { .. } => 600,
+            GameError::InvalidWinLength { .. } => 601,
+            GameError::InvalidDifficulty(_) => 602,
+            GameError::InvalidDate { .. } => 603,
+            GameError::MalformedParameters => 604,
+            GameError::UnknownParameter(_) => 605,
+            GameError::UnknownProfile(_) => 606,
+            GameError::MatchAlreadyOver => 700,
+            GameError::GameNotOver => 701,
+            GameError::DrawOfferUnavailable => 702,
+            GameError::NoDrawOffer => 703,
+            GameError::UnsupportedProtocolVersion(_) => 800,
+            GameError::MalformedMessage => 801,
+            GameError::OutOfSync => 802,
+            GameError::ImpersonatedPlayer => 803,
+            GameError::ResyncMoveRejected(_) => 804,
+            GameError::ResyncMismatch => 805,
+        }
+    }
+    
+    /// Human-readable message in the given language
+    pub fn message(&self, locale: Locale) -> String {
+        match locale {
+            Locale::English => self.english(),
+            Locale::German => self.german(),
+        }
+    }
+    
+    /// Message without the details of data-carrying variants (e.g. when only the code is known)
+    pub fn generic_message(&self, locale: Locale) -> String {
+        let (english, german) = match self {
+            GameError::UnsupportedPuzzleCodeVersion(_) => ("Unsupported puzzle code version", "Rätselcode-Version wird nicht unterstützt"),
+            GameError::InvalidNumberRange { .. } => ("Invalid number range", "Ungültiger Zahlenbereich"),
+            GameError::InvalidPlayerCount { .. } => ("Invalid number of players", "Ungültige Spieleranzahl"),
+            GameError::InvalidBoardSize { .. } => ("Invalid board size", "Ungültige Spielfeldgröße"),
+            GameError::InvalidWinLength { .. } => ("Win length doesn't fit the board", "Gewinnlänge passt nicht auf das Spielfeld"),
+            GameError::InvalidDifficulty(_) => ("Invalid difficulty", "Ungültiger Schwierigkeitsgrad"),
+            GameError::InvalidDate { .. } => ("Invalid date", "Ungültiges Datum"),
+            GameError::UnknownParameter(_) => ("Unknown evaluator parameter", "Unbekannter Bewertungsparameter"),
+            GameError::UnknownProfile(_) => ("Unknown AI profile", "Unbekanntes KI-Profil"),
+            GameError::UnsupportedProtocolVersion(_) => ("Unsupported protocol version", "Protokollversion wird nicht unterstützt"),
+            GameError::ResyncMoveRejected(_) => ("Resync move rejected", "Zug der Resynchronisierung abgelehnt"),
+            _ => return self.message(locale),
+        };
+        match locale {
+            Locale::English => english.to_string(),
+            Locale::German => german.to_string(),
+        }
+    }
+    
+    fn english(&self) -> String {
+        match self {
+            GameError::OutOfBounds => "Position out of bounds".to_string(),
+            GameError::PositionOccupied => "Position already occupied".to_string(),
+            GameError::GameAlreadyOver => "Game is already over".to_string(),
+            GameError::InvalidPlayer => "Invalid player".to_string(),
+            GameError::BoardError => "Board operation failed".to_string(),
+            GameError::InvalidMove => "Invalid move".to_string(),
+            GameError::TimeForfeit => "Time forfeit".to_string(),
+            GameError::NotYourTurn => "Not your turn".to_string(),
+            GameError::InvalidColumn => "Invalid column".to_string(),
+            GameError::ColumnFull => "Column is full".to_string(),
+            GameError::PopOutDisabled => "PopOut is not enabled".to_string(),
+            GameError::NotOwnDisc => "Bottom disc does not belong to the current player".to_string(),
+            GameError::GravityDisabled => "Gravity is not enabled".to_string(),
+            GameError::FloatingPiece => "Piece must be placed on the lowest free cell of the column".to_string(),
+            GameError::ForbiddenRenjuPoint => "Forbidden point under Renju rules".to_string(),
+            GameError::InvalidOrientation => "Invalid L-piece orientation".to_string(),
+            GameError::LPieceNotMoved => "The L-piece must be moved to a new position".to_string(),
+            GameError::NeutralNotFound => "No neutral piece on that cell".to_string(),
+            GameError::NotATrio => "Not a trio for the target".to_string(),
+            GameError::InvalidPuzzleCode => "Invalid puzzle code".to_string(),
+            GameError::PuzzleCodeChecksum => "Puzzle code checksum mismatch".to_string(),
+            GameError::UnsupportedPuzzleCodeVersion(version) => format!("Unsupported puzzle code version {}", version),
+            GameError::InvalidNumberRange { min, max, limit } => format!("Number range {}-{} must lie within 1-{}", min, max, limit),
+            GameError::NoOperationsEnabled => "At least one operation must be enabled".to_string(),
+            GameError::InvalidPlayerCount { count, min, max } => format!("Needs {}-{} players, got {}", min, max, count),
+            GameError::NoRounds => "At least one round is needed".to_string(),
+            GameError::InvalidBoardSize { rows, cols, min, max } => format!("Board size {}×{} outside {}-{}", cols, rows, min, max),
+            GameError::InvalidWinLength { win_length, rows, cols } => format!("Win length {} doesn't fit a {}×{} board", win_length, cols, rows),
+            GameError::InvalidDifficulty(difficulty) => format!("Invalid difficulty {}", difficulty),
+            GameError::InvalidDate { year, month, day } => format!("Invalid date {:04}-{:02}-{:02}", year, month, day),
+            GameError::MalformedParameters => "Malformed evaluator parameters".to_string(),
+            GameError::UnknownParameter(name) => format!("Unknown evaluator parameter {}", name),
+            GameError::UnknownProfile(name) => format!("Unknown AI profile {}", name),
+            GameError::MatchAlreadyOver => "Match is already over".to_string(),
+            GameError::GameNotOver => "Game is not over".to_string(),
+            GameError::DrawOfferUnavailable => "A draw cannot be offered now".to_string(),
+            GameError::NoDrawOffer => "No draw offer to accept".to_string(),
+            GameError::UnsupportedProtocolVersion(version) => format!("Unsupported protocol version {}", version),
+            GameError::MalformedMessage => "Malformed message".to_string(),
+            GameError::OutOfSync => "Session is out of sync".to_string(),
+            GameError::ImpersonatedPlayer => "Opponent acted for the local player".to_string(),
+            GameError::ResyncMoveRejected(index) => format!("Resync move {} rejected", index),
+            GameError::ResyncMismatch => "Resync state does not match the authority".to_string(),
+        }
+    }
+    
+    fn german(&self) -> String {
+        match self {
+            GameError::OutOfBounds => "Position außerhalb des Spielfelds".to_string(),
+            GameError::PositionOccupied => "Feld ist bereits belegt".to_string(),
+            GameError::GameAlreadyOver => "Das Spiel ist bereits beendet".to_string(),
+            GameError::InvalidPlayer => "Ungültiger Spieler".to_string(),
+            GameError::BoardError => "Spielfeldoperation fehlgeschlagen".to_string(),
+            GameError::InvalidMove => "Ungültiger Zug".to_string(),
+            GameError::TimeForfeit => "Zeit überschritten".to_string(),
+            GameError::NotYourTurn => "Du bist nicht am Zug".to_string(),
+            GameError::InvalidColumn => "Ungültige Spalte".to_string(),
+            GameError::ColumnFull => "Die Spalte ist voll".to_string(),
+            GameError::PopOutDisabled => "PopOut ist nicht aktiviert".to_string(),
+            GameError::NotOwnDisc => "Der unterste Stein gehört nicht dir".to_string(),
+            GameError::GravityDisabled => "Schwerkraft ist nicht aktiviert".to_string(),
+            GameError::FloatingPiece => "Der Stein muss auf das unterste freie Feld der Spalte".to_string(),
+            GameError::ForbiddenRenjuPoint => "Verbotener Punkt nach Renju-Regeln".to_string(),
+            GameError::InvalidOrientation => "Ungültige Ausrichtung des L-Steins".to_string(),
+            GameError::LPieceNotMoved => "Der L-Stein muss auf eine neue Position gezogen werden".to_string(),
+            GameError::NeutralNotFound => "Auf diesem Feld steht kein neutraler Stein".to_string(),
+            GameError::NotATrio => "Kein Trio für die Zielzahl".to_string(),
+            GameError::InvalidPuzzleCode => "Ungültiger Rätselcode".to_string(),
+            GameError::PuzzleCodeChecksum => "Prüfsumme des Rätselcodes stimmt nicht".to_string(),
+            GameError::UnsupportedPuzzleCodeVersion(version) => format!("Rätselcode-Version {} wird nicht unterstützt", version),
+            GameError::InvalidNumberRange { min, max, limit } => format!("Zahlenbereich {}-{} muss innerhalb von 1-{} liegen", min, max, limit),
+            GameError::NoOperationsEnabled => "Mindestens eine Rechenart muss aktiviert sein".to_string(),
+            GameError::InvalidPlayerCount { count, min, max } => format!("{}-{} Spieler nötig, nicht {}", min, max, count),
+            GameError::NoRounds => "Mindestens eine Runde ist nötig".to_string(),
+            GameError::InvalidBoardSize { rows, cols, min, max } => format!("Spielfeldgröße {}×{} außerhalb von {}-{}", cols, rows, min, max),
+            GameError::InvalidWinLength { win_length, rows, cols } => format!("Gewinnlänge {} passt nicht auf ein {}×{}-Spielfeld", win_length, cols, rows),
+            GameError::InvalidDifficulty(difficulty) => format!("Ungültiger Schwierigkeitsgrad {}", difficulty),
+            GameError::InvalidDate { year, month, day } => format!("Ungültiges Datum {:02}.{:02}.{:04}", day, month, year),
+            GameError::MalformedParameters => "Fehlerhafte Bewertungsparameter".to_string(),
+            GameError::UnknownParameter(name) => format!("Unbekannter Bewertungsparameter {}", name),
+            GameError::UnknownProfile(name) => format!("Unbekanntes KI-Profil {}", name),
+            GameError::MatchAlreadyOver => "Das Match ist bereits beendet".to_string(),
+            GameError::GameNotOver => "Das Spiel ist noch nicht beendet".to_string(),
+            GameError::DrawOfferUnavailable => "Ein Remis kann jetzt nicht angeboten werden".to_string(),
+            GameError::NoDrawOffer => "Kein Remisangebot vorhanden".to_string(),
+            GameError::UnsupportedProtocolVersion(version) => format!("Protokollversion {} wird nicht unterstützt", version),
+            GameError::MalformedMessage => "Fehlerhafte Nachricht".to_string(),
+            GameError::OutOfSync => "Die Sitzung ist nicht synchron".to_string(),
+            GameError::ImpersonatedPlayer => "Der Gegner hat für den lokalen Spieler gehandelt".to_string(),
+            GameError::ResyncMoveRejected(index) => format!("Zug {} der Resynchronisierung abgelehnt", index),
+            GameError::ResyncMismatch => "Resynchronisierter Zustand weicht von der Gegenseite ab".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.english())
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<GameError> for String {
+    fn from(error: GameError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Error as seen from JS: the stable code plus messages in every locale
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameErrorInfo {
+    error: GameError,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameErrorInfo {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn code(&self) -> u16 {
+        self.error.code()
+    }
+    
+    /// English message
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn message(&self) -> String {
+        self.error.to_string()
+    }
+    
+    pub fn localized(&self, locale: Locale) -> String {
+        self.error.message(locale)
+    }
+    
+    /// Keeps `String(error)` and template literals showing the message
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = toString))]
+    pub fn to_display_string(&self) -> String {
+        self.message()
+    }
+}
+
+impl From<GameError> for GameErrorInfo {
+    fn from(error: GameError) -> Self {
+        Self { error }
+    }
+}
+
+impl GameErrorInfo {
+    pub fn error(&self) -> &GameError {
+        &self.error
+    }
+}
+
+/// JS callers receive a `GameErrorInfo` as the thrown value
+#[cfg(feature = "wasm")]
+impl From<GameError> for JsValue {
+    fn from(error: GameError) -> Self {
+        GameErrorInfo::from(error).into()
+    }
+}
+
+/// Message for an error code, e.g. one received over the network (None for unknown codes)
+/// Variants with details fall back to their generic wording
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn error_message_for_code(code: u16, locale: Locale) -> Option<String> {
+    ALL_ERRORS.iter().find(|error| error.code() == code).map(|error| error.generic_message(locale))
+}
+
+/// One representative per error code (the data of detail variants is a placeholder and never shown)
+const ALL_ERRORS: &[GameError] = &[
+    GameError::OutOfBounds,
+    GameError::PositionOccupied,
+    GameError::GameAlreadyOver,
+    GameError::InvalidPlayer,
+    GameError::BoardError,
+    GameError::InvalidMove,
+    GameError::TimeForfeit,
+    GameError::NotYourTurn,
+    GameError::InvalidColumn,
+    GameError::ColumnFull,
+    GameError::PopOutDisabled,
+    GameError::NotOwnDisc,
+    GameError::GravityDisabled,
+    GameError::FloatingPiece,
+    GameError::ForbiddenRenjuPoint,
+    GameError::InvalidOrientation,
+    GameError::LPieceNotMoved,
+    GameError::NeutralNotFound,
+    GameError::NotATrio,
+    GameError::InvalidPuzzleCode,
+    GameError::PuzzleCodeChecksum,
+    GameError::UnsupportedPuzzleCodeVersion(0),
+    GameError::InvalidNumberRange { min: 0, max: 0, limit: 0 },
+    GameError::NoOperationsEnabled,
+    GameError::InvalidPlayerCount { count: 0, min: 0, max: 0 },
+    GameError::NoRounds,
+    GameError::InvalidBoardSize { rows: 0, cols: 0, min: 0, max: 0 },
+    GameError::InvalidWinLength { win_length: 0, rows: 0, cols: 0 },
+    GameError::InvalidDifficulty(0),
+    GameError::InvalidDate { year: 0, month: 0, day: 0 },
+    GameError::MalformedParameters,
+    GameError::UnknownParameter(String::new()),
+    GameError::UnknownProfile(String::new()),
+    GameError::MatchAlreadyOver,
+    GameError::GameNotOver,
+    GameError::DrawOfferUnavailable,
+    GameError::NoDrawOffer,
+    GameError::UnsupportedProtocolVersion(0),
+    GameError::MalformedMessage,
+    GameError::OutOfSync,
+    GameError::ImpersonatedPlayer,
+    GameError::ResyncMoveRejected(0),
+    GameError::ResyncMismatch,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    
+    #[test]
+    fn test_error_codes_are_unique_and_localized() {
+        let codes: HashSet<u16> = ALL_ERRORS.iter().map(GameError::code).collect();
+        assert_eq!(codes.len(), ALL_ERRORS.len());
+        for error in ALL_ERRORS {
+            assert_ne!(error.message(Locale::English), error.message(Locale::German), "{:?}", error);
+        }
+        
+        assert_eq!(GameError::ColumnFull.code(), 201);
+        assert_eq!(GameError::ColumnFull.to_string(), "Column is full");
+        assert_eq!(error_message_for_code(201, Locale::German).as_deref(), Some("Die Spalte ist voll"));
+        assert_eq!(GameError::ForbiddenRenjuPoint.code(), 300);
+        assert_eq!(error_message_for_code(300, Locale::German).as_deref(), Some("Verbotener Punkt nach Renju-Regeln"));
+        assert_eq!(error_message_for_code(999, Locale::English), None);
+        assert_eq!(error_message_for_code(600, Locale::English).as_deref(), Some("Invalid board size"));
+        assert_eq!(error_message_for_code(606, Locale::German).as_deref(), Some("Unbekanntes KI-Profil"));
+        for error in ALL_ERRORS {
+            let generic = error.generic_message(Locale::English);
+            assert!(!generic.contains('0') && !generic.ends_with(' '), "{:?}: {}", error, generic);
+        }
+        let info = GameErrorInfo::from(GameError::InvalidDifficulty(9));
+        assert_eq!((info.code(), info.message()), (602, "Invalid difficulty 9".to_string()));
+    }
+}
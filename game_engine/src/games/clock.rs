@@ -0,0 +1,425 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::Player;
+
+/// Moves a player is assumed to still need when spreading main time over the game
+const MOVES_TO_GO: u64 = 30;
+
+/// How time is credited after each move
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeControlMode {
+    SuddenDeath = 0, // No time is credited
+    Fischer = 1,     // A fixed increment is added after every move
+    Bronstein = 2,   // Time used is given back, up to the delay
+}
+
+/// Time control settings: main time, increment or delay, and optional byo-yomi periods
+/// Byo-yomi starts once main time is used up: each move must be made within one period,
+/// and every period overrun uses up a period; the player flags when none are left
+/// Using exactly all of the main time or a period is still in time
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeControl {
+    mode: TimeControlMode,
+    base_ms: u64,
+    increment_ms: u64, // Fischer increment or Bronstein delay
+    byo_yomi_periods: u32,
+    byo_yomi_ms: u64,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TimeControl {
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn sudden_death(base_ms: u64) -> TimeControl {
+        Self { mode: TimeControlMode::SuddenDeath, base_ms, increment_ms: 0, byo_yomi_periods: 0, byo_yomi_ms: 0 }
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn fischer(base_ms: u64, increment_ms: u64) -> TimeControl {
+        Self { mode: TimeControlMode::Fischer, increment_ms, ..Self::sudden_death(base_ms) }
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn bronstein(base_ms: u64, delay_ms: u64) -> TimeControl {
+        Self { mode: TimeControlMode::Bronstein, increment_ms: delay_ms, ..Self::sudden_death(base_ms) }
+    }
+    
+    /// Add byo-yomi periods played after main time runs out
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn with_byo_yomi(self, periods: u32, period_ms: u64) -> TimeControl {
+        Self { byo_yomi_periods: periods, byo_yomi_ms: period_ms, ..self }
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn mode(&self) -> TimeControlMode { self.mode }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn base_ms(&self) -> u64 { self.base_ms }
+    
+    /// Fischer increment or Bronstein delay (0 for sudden death)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn increment_ms(&self) -> u64 { self.increment_ms }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn byo_yomi_periods(&self) -> u32 { self.byo_yomi_periods }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn byo_yomi_ms(&self) -> u64 { self.byo_yomi_ms }
+}
+
+/// Millisecond time source for game clocks; inject a `ManualTimeSource` for deterministic tests
+/// Sources are shared across threads, so games with a clock can be sent to a search thread
+pub trait TimeSource: Send + Sync {
+    fn now_ms(&self) -> u64;
+}
+
+/// Wall clock time (`Date.now()` in the browser)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemTimeSource;
+
+impl TimeSource for SystemTimeSource {
+    #[cfg(all(target_arch = "wasm32", feature = "js_sys"))]
+    fn now_ms(&self) -> u64 {
+        js_sys::Date::now() as u64
+    }
+    
+    #[cfg(not(all(target_arch = "wasm32", feature = "js_sys")))]
+    fn now_ms(&self) -> u64 {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_millis() as u64)
+    }
+}
+
+/// Time source that only moves when told to; clones share the same time
+#[derive(Clone, Debug, Default)]
+pub struct ManualTimeSource {
+    now: Arc<AtomicU64>,
+}
+
+impl ManualTimeSource {
+    pub fn new(start_ms: u64) -> Self {
+        Self { now: Arc::new(AtomicU64::new(start_ms)) }
+    }
+    
+    pub fn advance(&self, ms: u64) {
+        self.now.fetch_add(ms, Ordering::Relaxed);
+    }
+    
+    pub fn set(&self, ms: u64) {
+        self.now.store(ms, Ordering::Relaxed);
+    }
+}
+
+impl TimeSource for ManualTimeSource {
+    fn now_ms(&self) -> u64 {
+        self.now.load(Ordering::Relaxed)
+    }
+}
+
+/// Two-player game clock
+/// Sides are the first player (Yellow/Black) and the second player (Red/White), so one clock fits every game
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct GameClock {
+    control: TimeControl,
+    remaining_ms: [u64; 2],  // Main time left per side
+    periods_left: [u32; 2],  // Byo-yomi periods left per side
+    running: Option<usize>,  // Side whose clock is running
+    turn_started_ms: u64,
+    flagged: Option<usize>,  // Side that ran out of time
+    source: Arc<dyn TimeSource>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameClock {
+    /// Create a stopped clock using wall clock time
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(control: TimeControl) -> GameClock {
+        Self::with_time_source(control, Arc::new(SystemTimeSource))
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn control(&self) -> TimeControl { self.control }
+    
+    /// Start (or restart) the clock for the player to move
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn start(&mut self, player: Player) {
+        if self.flagged.is_none() {
+            self.running = Some(Self::side(player));
+            self.turn_started_ms = self.source.now_ms();
+        }
+    }
+    
+    /// Stop the clock, charging the running player for the time used so far
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn stop(&mut self) {
+        if let Some(side) = self.running.take() {
+            let elapsed = self.elapsed_ms();
+            match self.charge(side, elapsed) {
+                Some((remaining, periods)) => {
+                    self.remaining_ms[side] = remaining;
+                    self.periods_left[side] = periods;
+                },
+                None => self.flagged = Some(side),
+            }
+        }
+    }
+    
+    /// Hand the turn back to `player` after a move was taken back
+    /// The running side is charged for the time used so far; no increment or delay is credited
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn take_back(&mut self, player: Player) {
+        self.stop();
+        self.start(player);
+    }
+    
+    /// True if the player's time is up right now (unlike `check_flag`, nothing is recorded)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_out_of_time(&self, player: Player) -> bool {
+        self.live_state(Self::side(player)).is_none()
+    }
+    
+    /// Reset both sides to the full time control and start the clock for `first_player`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn restart(&mut self, first_player: Player) {
+        self.remaining_ms = [self.control.base_ms; 2];
+        self.periods_left = [self.control.byo_yomi_periods; 2];
+        self.flagged = None;
+        self.start(first_player);
+    }
+    
+    /// True if the player's clock is running
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_running(&self, player: Player) -> bool {
+        self.running == Some(Self::side(player))
+    }
+    
+    /// True if the player ran out of time (as of the last check or press)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_flagged(&self, player: Player) -> bool {
+        self.flagged == Some(Self::side(player))
+    }
+    
+    /// Main time left for a player, including the running turn
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn remaining_ms(&self, player: Player) -> u64 {
+        self.live_state(Self::side(player)).map_or(0, |(remaining, _)| remaining)
+    }
+    
+    /// Byo-yomi periods left for a player, including the running turn
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn periods_left(&self, player: Player) -> u32 {
+        self.live_state(Self::side(player)).map_or(0, |(_, periods)| periods)
+    }
+    
+    /// Flag the running player if their time is up; returns true if either player has flagged
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn check_flag(&mut self) -> bool {
+        if let Some(side) = self.running.filter(|&side| self.live_state(side).is_none()) {
+            self.running = None;
+            self.flagged = Some(side);
+        }
+        self.flagged.is_some()
+    }
+    
+    /// Hit the clock after `player` moved: charge the turn, credit increment or delay and start the opponent
+    /// Returns false if it is not the player's turn on the clock or the player ran out of time
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn press(&mut self, player: Player) -> bool {
+        let side = Self::side(player);
+        if self.running != Some(side) {
+            return false;
+        }
+    
+        let elapsed = self.elapsed_ms();
+        let Some((remaining, periods)) = self.charge(side, elapsed) else {
+            self.running = None;
+            self.flagged = Some(side);
+            return false;
+        };
+    
+        // Increments and delays only apply while the move was paid from main time
+        let paid_from_main_time = elapsed <= self.remaining_ms[side];
+        self.remaining_ms[side] = match self.control.mode {
+            TimeControlMode::Fischer if paid_from_main_time => remaining + self.control.increment_ms,
+            TimeControlMode::Bronstein if paid_from_main_time => remaining + elapsed.min(self.control.increment_ms),
+            _ => remaining,
+        };
+        self.periods_left[side] = periods;
+        self.start(player.opponent());
+        true
+    }
+    
+    /// Time the player should spend on the next move: a share of main time plus the increment or delay,
+    /// at least one byo-yomi period, and never more than the player has left
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn move_budget_ms(&self, player: Player) -> u64 {
+        let Some((remaining, periods)) = self.live_state(Self::side(player)) else {
+            return 0;
+        };
+        let period_ms = if periods > 0 { self.control.byo_yomi_ms } else { 0 };
+        let share = remaining / MOVES_TO_GO + self.control.increment_ms;
+        share.max(period_ms).min(remaining + period_ms)
+    }
+}
+
+impl GameClock {
+    /// Create a stopped clock reading time from `source`
+    pub fn with_time_source(control: TimeControl, source: Arc<dyn TimeSource>) -> Self {
+        Self {
+            control,
+            remaining_ms: [control.base_ms; 2],
+            periods_left: [control.byo_yomi_periods; 2],
+            running: None,
+            turn_started_ms: 0,
+            flagged: None,
+            source,
+        }
+    }
+    
+    fn side(player: Player) -> usize {
+        match player {
+            Player::Yellow | Player::Black => 0,
+            Player::Red | Player::White => 1,
+        }
+    }
+    
+    fn elapsed_ms(&self) -> u64 {
+        self.source.now_ms().saturating_sub(self.turn_started_ms)
+    }
+    
+    /// Main time and periods a side has left right now (None if flagged)
+    fn live_state(&self, side: usize) -> Option<(u64, u32)> {
+        if self.flagged == Some(side) {
+            return None;
+        }
+        if self.running == Some(side) {
+            self.charge(side, self.elapsed_ms())
+        } else {
+            Some((self.remaining_ms[side], self.periods_left[side]))
+        }
+    }
+    
+    /// Main time and periods left after a side spent `elapsed` on one move (None if that flags)
+    fn charge(&self, side: usize, elapsed: u64) -> Option<(u64, u32)> {
+        let remaining = self.remaining_ms[side];
+        let periods = self.periods_left[side];
+        if elapsed <= remaining {
+            return Some((remaining - elapsed, periods));
+        }
+    
+        // Main time is gone: the move must fit in a period, and each overrun period is used up
+        // (a move made exactly as a period ends is in time, like one made as main time ends)
+        if self.control.byo_yomi_ms == 0 {
+            return None;
+        }
+        let used = (elapsed - remaining - 1) / self.control.byo_yomi_ms;
+        (used < periods as u64).then(|| (0, periods - used as u32))
+    }
+}
+
+/// Clock slot of a game
+/// Copies of a game (AI lookahead, hypothetical states) never carry the clock, so only the real game presses it
+#[derive(Default)]
+pub struct AttachedClock(Option<GameClock>);
+
+impl Clone for AttachedClock {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl AttachedClock {
+    pub fn get(&self) -> Option<&GameClock> {
+        self.0.as_ref()
+    }
+    
+    pub fn get_mut(&mut self) -> Option<&mut GameClock> {
+        self.0.as_mut()
+    }
+    
+    pub fn set(&mut self, clock: GameClock) {
+        self.0 = Some(clock);
+    }
+    
+    pub fn take(&mut self) -> Option<GameClock> {
+        self.0.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn manual_clock(control: TimeControl) -> (GameClock, ManualTimeSource) {
+        let time = ManualTimeSource::new(1_000);
+        let mut clock = GameClock::with_time_source(control, Arc::new(time.clone()));
+        clock.start(Player::Yellow);
+        (clock, time)
+    }
+    
+    #[test]
+    fn test_sudden_death_and_fischer() {
+        let (mut clock, time) = manual_clock(TimeControl::sudden_death(10_000));
+        time.advance(3_000);
+        assert_eq!(clock.remaining_ms(Player::Yellow), 7_000);
+        assert!(!clock.press(Player::Red)); // Not Red's turn
+        assert!(clock.press(Player::Yellow));
+        assert_eq!(clock.remaining_ms(Player::Yellow), 7_000);
+        time.advance(10_001);
+        assert!(clock.check_flag());
+        assert!(clock.is_flagged(Player::Red));
+        assert!(!clock.press(Player::Red));
+    
+        let (mut clock, time) = manual_clock(TimeControl::fischer(10_000, 2_000));
+        time.advance(3_000);
+        assert!(clock.press(Player::Yellow));
+        assert_eq!(clock.remaining_ms(Player::Yellow), 9_000);
+        assert!(!clock.check_flag());
+    }
+    
+    #[test]
+    fn test_bronstein_delay() {
+        let (mut clock, time) = manual_clock(TimeControl::bronstein(10_000, 2_000));
+        time.advance(1_500);
+        assert!(clock.press(Player::Yellow));
+        assert_eq!(clock.remaining_ms(Player::Yellow), 10_000); // Fully given back
+        time.advance(5_000);
+        assert!(clock.press(Player::Red));
+        assert_eq!(clock.remaining_ms(Player::Red), 7_000);
+    }
+    
+    #[test]
+    fn test_byo_yomi_periods() {
+        let (mut clock, time) = manual_clock(TimeControl::sudden_death(1_000).with_byo_yomi(2, 5_000));
+        time.advance(4_000); // 3s into the first period
+        assert!(clock.press(Player::Yellow));
+        assert_eq!((clock.remaining_ms(Player::Yellow), clock.periods_left(Player::Yellow)), (0, 2));
+    
+        clock.press(Player::Red);
+        time.advance(6_000); // Overruns one period
+        assert!(clock.press(Player::Yellow));
+        assert_eq!(clock.periods_left(Player::Yellow), 1);
+    
+        clock.press(Player::Red);
+        time.advance(5_000); // Exactly one period is still in time
+        assert!(!clock.check_flag());
+        time.advance(1); // Last period used up
+        assert!(clock.check_flag());
+        assert!(clock.is_flagged(Player::Black)); // Sides are shared across games
+    }
+    
+    #[test]
+    fn test_move_budget() {
+        let (clock, time) = manual_clock(TimeControl::fischer(60_000, 1_000));
+        assert_eq!(clock.move_budget_ms(Player::Yellow), 3_000);
+        time.advance(59_900);
+        assert_eq!(clock.move_budget_ms(Player::Yellow), 100); // Never more than what is left
+    
+        let (clock, _) = manual_clock(TimeControl::sudden_death(0).with_byo_yomi(3, 10_000));
+        assert_eq!(clock.move_budget_ms(Player::Yellow), 10_000);
+    }
+}
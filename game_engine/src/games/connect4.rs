@@ -1,21 +1,94 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
-use crate::data::BitPackedBoard;
-use crate::geometry::{Connect4Grid, BoardGeometry, PatternProvider};
+use crate::data::{BitPackedBoard, DynamicBitPackedBoard};
+use crate::geometry::{DynamicQuadraticGrid, BoardGeometry};
 use crate::ai::{Connect4AI, PatternEvaluator};
 use crate::ai::connect4_ai::AIStrategy;
-use crate::{GamePhase, PositionAnalysis, Player};
+use crate::games::game_result::{Adjudication, GameResult, TerminationReason};
+use crate::games::clock::{AttachedClock, GameClock};
+use crate::{GameError, GamePhase, PositionAnalysis, Player, WinningLine};
+
+/// Standard tournament board variants (columns × rows)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connect4Variant {
+    Standard = 0,  // 7×6, four in a row
+    Large8x7 = 1,  // 8×7, four in a row
+    Large9x7 = 2,  // 9×7, four in a row
+    Large10x7 = 3, // 10×7, four in a row
+    Connect5 = 4,  // 9×6, five in a row
+}
+
+impl Connect4Variant {
+    /// Board shape of the variant as (rows, cols, win_length)
+    pub fn dimensions(self) -> (usize, usize, usize) {
+        match self {
+            Connect4Variant::Standard => (6, 7, 4),
+            Connect4Variant::Large8x7 => (7, 8, 4),
+            Connect4Variant::Large9x7 => (7, 9, 4),
+            Connect4Variant::Large10x7 => (7, 10, 4),
+            Connect4Variant::Connect5 => (6, 9, 5),
+        }
+    }
+}
+
+/// Kind of a Connect4 move (PopOut adds pops to the usual drops)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connect4MoveKind {
+    Drop = 0, // Disc enters the column from the top
+    Pop = 1,  // Own disc leaves the bottom row, the column drops by one
+}
+
+/// A played move as stored in the move history (used for undo)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Connect4Move {
+    pub column: usize,
+    pub kind: Connect4MoveKind,
+    pub player: Player,
+}
+
+impl Connect4Move {
+    /// Encode as a single number: drops are `column`, pops are `cols + column`
+    pub fn encode(self, cols: usize) -> usize {
+        match self.kind {
+            Connect4MoveKind::Drop => self.column,
+            Connect4MoveKind::Pop => cols + self.column,
+        }
+    }
+    
+    /// Decode a move number produced by `encode` (None if out of range)
+    pub fn decode(code: usize, cols: usize, player: Player) -> Option<Connect4Move> {
+        let (column, kind) = if code < cols {
+            (code, Connect4MoveKind::Drop)
+        } else if code < 2 * cols {
+            (code - cols, Connect4MoveKind::Pop)
+        } else {
+            return None;
+        };
+        Some(Connect4Move { column, kind, player })
+    }
+}
+
+/// Occurrences of the same position (and player to move) that end a PopOut game in a draw
+const REPETITION_DRAW_COUNT: usize = 3;
+
+/// Supported board sides and win lengths for custom Connect4 boards
+pub const MIN_BOARD_SIDE: usize = 4;
+pub const MAX_BOARD_SIDE: usize = 16;
+pub const MIN_WIN_LENGTH: usize = 3;
 
 /// Connect4 game implementation using the Three-Layer Architecture
 /// Composes geometry and data layers for clean separation of concerns
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct Connect4Game {
     // Composition: Geometry layer handles coordinate logic
-    geometry: Connect4Grid,
+    geometry: DynamicQuadraticGrid,
     
     // Composition: Data layer handles efficient storage
-    yellow_board: BitPackedBoard<6, 7, 2>,
-    red_board: BitPackedBoard<6, 7, 2>,
+    yellow_board: DynamicBitPackedBoard,
+    red_board: DynamicBitPackedBoard,
     
     // Composition: AI layer for strategic evaluation
     ai: Connect4AI,
@@ -25,12 +98,25 @@ pub struct Connect4Game {
     current_player: Player,
     winner: Option<Player>,
     move_count: usize,
-    column_heights: [usize; 7], // Track how many pieces in each column
+    column_heights: Vec<usize>, // Track how many pieces in each column
+    move_history: Vec<Connect4Move>, // Played moves for undo functionality
+    position_history: Vec<u64>, // Position hashes for the PopOut repetition draw
+    lines_closed: bool, // No winning line is open for either player; refreshed whenever the discs change
+    
+    // Board configuration
+    rows: usize,
+    cols: usize,
+    win_length: usize,
+    popout: bool,
+    misere: bool,
+    claimable_draw: bool, // A draw may be claimed once no side can complete a line in time
+    adjudication: Adjudication, // Resignations, draw offers and claimed draws
+    clock: AttachedClock, // Optional time control; flags are checked on every move
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Connect4Game {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         Self::new_with_starting_player(Player::Yellow)
     }
@@ -38,81 +124,330 @@ impl Connect4Game {
     /// Create a new Connect4 game with a specific starting player
     /// This is essential for game series where "loser starts next game"
     pub fn new_with_starting_player(starting_player: Player) -> Self {
-        Self {
-            geometry: Connect4Grid::new(),
-            yellow_board: BitPackedBoard::new(),
-            red_board: BitPackedBoard::new(),
-            ai: Connect4AI::new(),
-            evaluator: PatternEvaluator::new(),
-            current_player: starting_player,
-            winner: None,
-            move_count: 0,
-            column_heights: [0; 7],
-        }
+        let (rows, cols, win_length) = Connect4Variant::Standard.dimensions();
+        Self::with_dimensions(rows, cols, win_length, starting_player)
+    }
+    
+    /// Create a game on one of the standard tournament boards
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn new_variant(variant: Connect4Variant) -> Connect4Game {
+        let (rows, cols, win_length) = variant.dimensions();
+        Self::with_dimensions(rows, cols, win_length, Player::Yellow)
+    }
+    
+    /// Create a game with a custom board size and win length
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn new_with_size(rows: usize, cols: usize, win_length: usize) -> Result<Connect4Game, GameError> {
+        Self::new_with_size_internal(rows, cols, win_length)
+    }
+    
+    /// Number of rows on the board
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_rows(&self) -> usize {
+        self.rows
+    }
+    
+    /// Number of columns on the board
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_cols(&self) -> usize {
+        self.cols
+    }
+    
+    /// Pieces in a row needed to win
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_win_length(&self) -> usize {
+        self.win_length
     }
     
     /// Make a move in the specified column
-    #[wasm_bindgen]
-    pub fn make_move(&mut self, column: usize) -> Result<bool, JsValue> {
-        // Validate column
-        if column >= 7 {
-            return Err(JsValue::from_str("Invalid column"));
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn make_move(&mut self, column: usize) -> Result<bool, GameError> {
+        self.make_move_internal(column)
+    }
+    
+    /// Enable or disable the PopOut rule (players may pop their own discs from the bottom row)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_popout_enabled(&mut self, enabled: bool) {
+        self.popout = enabled;
+        self.position_history.clear();
+        if enabled {
+            self.position_history.push(self.position_hash());
         }
-        
-        // Check if column is full
-        if self.column_heights[column] >= 6 {
-            return Err(JsValue::from_str("Column is full"));
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_popout_enabled(&self) -> bool {
+        self.popout
+    }
+    
+    /// Enable or disable misère rules (completing a line loses instead of wins)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_misere_enabled(&mut self, enabled: bool) {
+        self.misere = enabled;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_misere_enabled(&self) -> bool {
+        self.misere
+    }
+    
+    /// Enable or disable claimable draws (see `can_claim_draw`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_claimable_draw_enabled(&mut self, enabled: bool) {
+        self.claimable_draw = enabled;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_claimable_draw_enabled(&self) -> bool {
+        self.claimable_draw
+    }
+    
+    /// True if no winning line is open (free of opponent discs) for either player
+    /// Such a position can only end in a draw; PopOut pops may reopen lines, so it never applies there
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_dead_position(&self) -> bool {
+        !self.popout && self.winner.is_none() && self.lines_closed
+    }
+    
+    /// Recompute `lines_closed` after the discs changed (once per move, not on every draw check)
+    fn refresh_lines_closed(&mut self) {
+        self.lines_closed = !self.geometry.get_winning_lines(self.win_length).iter().any(|line_mask| {
+            self.yellow_board.count_set_bits_in_mask(line_mask) == 0
+                || self.red_board.count_set_bits_in_mask(line_mask) == 0
+        });
+    }
+    
+    /// Check if the current player may claim a draw (claimable draws enabled)
+    /// Claimable as soon as neither player has enough moves left to fill any open line
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn can_claim_draw(&self) -> bool {
+        if !self.claimable_draw || self.popout || self.is_game_over() {
+            return false;
         }
         
-        // Check if game is over
-        if self.winner.is_some() {
-            return Err(JsValue::from_str("Game is already over"));
+        let empty_cells = self.rows * self.cols - self.move_count;
+        [self.current_player, self.current_player.opponent()]
+            .into_iter()
+            .enumerate()
+            .all(|(turn, player)| {
+                // The player to move gets the odd empty cell
+                let moves_left = (empty_cells + 1 - turn) / 2;
+                self.min_discs_missing(player).is_none_or(|missing| missing > moves_left)
+            })
+    }
+    
+    /// Claim a draw; returns false if the position does not allow it
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn claim_draw(&mut self) -> bool {
+        if !self.can_claim_draw() {
+            return false;
         }
-        
-        // Calculate the row where the piece will land
-        let row = 6 - 1 - self.column_heights[column]; // Bottom up
-        
-        // Use geometry layer to get the index
-        let index = self.geometry.to_index((row as i32, column as i32))
-            .ok_or_else(|| JsValue::from_str("Invalid position"))?;
-        
-        // Use data layer to place the piece
-        let player_board = self.get_current_player_board_mut();
-        player_board.set_bit(index, true);
-        
-        // Update column height
-        self.column_heights[column] += 1;
-        self.move_count += 1;
-        
-        // Check for win condition
-        let won = self.check_win_condition();
-        
-        if !won {
-            // Switch players
-            self.current_player = self.current_player.opponent();
+        self.adjudication.end(None, TerminationReason::ClaimedDraw);
+        true
+    }
+    
+    /// Resign the game: the opponent wins; returns false if the game is over or the player is not Yellow/Red
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn resign(&mut self, player: Player) -> bool {
+        if self.is_game_over() || !matches!(player, Player::Yellow | Player::Red) {
+            return false;
         }
-        
-        Ok(won)
+        self.winner = Some(player.opponent());
+        self.adjudication.end(self.winner, TerminationReason::Resignation);
+        true
+    }
+    
+    /// Offer a draw; it stands until the opponent accepts it or declines by moving
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn offer_draw(&mut self, player: Player) -> bool {
+        if self.is_game_over() || !matches!(player, Player::Yellow | Player::Red) {
+            return false;
+        }
+        self.adjudication.offer_draw(player);
+        true
+    }
+    
+    /// Accept the opponent's pending draw offer; returns false if there is none
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn accept_draw(&mut self, player: Player) -> bool {
+        !self.is_game_over() && self.adjudication.accept_draw(player)
+    }
+    
+    /// Player with a pending draw offer (if any)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_draw_offer(&self) -> Option<Player> {
+        self.adjudication.draw_offer()
+    }
+    
+    /// Attach a clock; it starts running for the player to move
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn attach_clock(&mut self, mut clock: GameClock) {
+        clock.start(self.current_player);
+        self.clock.set(clock);
+    }
+    
+    /// Remove the clock (games without a clock are untimed)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn detach_clock(&mut self) -> Option<GameClock> {
+        self.clock.take()
+    }
+    
+    /// Snapshot of the attached clock for display
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_clock(&self) -> Option<GameClock> {
+        self.clock.get().cloned()
+    }
+    
+    /// Check whether the player to move ran out of time; if so they lose on time
+    /// Returns true if the game was lost on time
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn check_clock(&mut self) -> bool {
+        if self.is_game_over() {
+            return false;
+        }
+        let Some(clock) = self.clock.get_mut() else {
+            return false;
+        };
+        if !clock.check_flag() || !clock.is_flagged(self.current_player) {
+            return false;
+        }
+        self.winner = Some(self.current_player.opponent());
+        self.adjudication.end(self.winner, TerminationReason::Timeout);
+        true
     }
     
-    /// Check if the current player has won
+    /// True if the player to move ran out of time (nothing is recorded)
+    fn is_out_of_time(&self) -> bool {
+        self.clock.get().is_some_and(|clock| clock.is_out_of_time(self.current_player))
+    }
+    
+    /// Final result with termination reason (None while the game is running)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_result(&self) -> Option<GameResult> {
+        let final_move = self.move_history.last().map(|last| vec![last.encode(self.cols)]).unwrap_or_default();
+        let (winner, reason) = if let Some(ending) = self.adjudication.ending() {
+            ending
+        } else if self.winner.is_some() {
+            (self.winner, TerminationReason::LineCompleted)
+        } else if !self.is_draw() {
+            return None;
+        } else if self.is_repetition_draw() {
+            (None, TerminationReason::Repetition)
+        } else if self.column_heights.iter().all(|&height| height >= self.rows) {
+            (None, TerminationReason::BoardFull)
+        } else {
+            (None, TerminationReason::DeadPosition)
+        };
+        Some(GameResult::new(winner, reason, final_move))
+    }
+    
+    /// PopOut: remove the current player's disc from the bottom of a column
+    /// Returns true if the move ended the game with a winner (which may be the opponent)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn pop_out(&mut self, column: usize) -> Result<bool, GameError> {
+        self.pop_out_internal(column)
+    }
+    
+    /// Check if the current player may pop the bottom disc of a column
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn can_pop(&self, column: usize) -> bool {
+        self.popout
+            && column < self.cols
+            && !self.is_game_over()
+            && self.get_cell(self.rows - 1, column) == Self::player_cell(self.current_player)
+    }
+    
+    /// All legal moves as move numbers: drops are `column`, pops are `cols + column`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_legal_moves(&self) -> Vec<usize> {
+        (0..2 * self.cols).filter(|&code| self.is_legal_move_code(code)).collect()
+    }
+    
+    /// Play a move number as returned by `get_legal_moves` or `get_ai_move`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn make_move_code(&mut self, code: usize) -> Result<bool, GameError> {
+        self.make_move_code_internal(code)
+    }
+    
+    /// True if the game ended because a position occurred for the third time (PopOut only)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_repetition_draw(&self) -> bool {
+        self.winner.is_none() && self.position_repetitions() >= REPETITION_DRAW_COUNT
+    }
+    
+    /// Check if the current player completed a line (a win, or a loss under misère rules)
     fn check_win_condition(&mut self) -> bool {
-        let player_board = self.get_current_player_board();
-        let winning_lines = self.geometry.get_winning_lines(4);
-        
-        // Check each winning line pattern
-        for line_mask in winning_lines {
-            if player_board.count_set_bits_in_mask(line_mask) >= 4 {
-                self.winner = Some(self.current_player);
-                return true;
-            }
+        if self.has_winning_line(self.current_player) {
+            self.winner = Some(self.line_winner(self.current_player));
+            return true;
         }
         
         false
     }
     
+    /// Winner of the game when `player` completes a line
+    fn line_winner(&self, player: Player) -> Player {
+        if self.misere {
+            player.opponent()
+        } else {
+            player
+        }
+    }
+    
+    /// Check if a player has a complete line on the board
+    fn has_winning_line(&self, player: Player) -> bool {
+        let player_board = self.get_player_board(player);
+        let winning_lines = self.geometry.get_winning_lines(self.win_length);
+        
+        // Check each winning line pattern
+        winning_lines
+            .iter()
+            .any(|line_mask| player_board.count_set_bits_in_mask(line_mask) >= self.win_length as u32)
+    }
+    
+    /// Cells of every completed line, flattened as (row, col) pairs (empty while the game is running)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_winning_cells(&self) -> Vec<usize> {
+        WinningLine::flatten_cells(&self.get_completed_lines())
+    }
+    
+    /// Every completed line on the board (a PopOut pop may complete lines for both players)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_completed_lines(&self) -> Vec<WinningLine> {
+        [Player::Yellow, Player::Red]
+            .into_iter()
+            .flat_map(|player| {
+                let player_board = self.get_player_board(player);
+                let windows = self.geometry
+                    .get_winning_lines(self.win_length)
+                    .iter()
+                    .filter(|line_mask| player_board.count_set_bits_in_mask(line_mask) >= self.win_length as u32)
+                    .map(|line_mask| {
+                        (0..self.rows * self.cols)
+                            .filter(|&index| line_mask.get_bit(index))
+                            .map(|index| (index / self.cols, index % self.cols))
+                            .collect()
+                    })
+                    .collect();
+                WinningLine::from_windows(player, windows)
+            })
+            .collect()
+    }
+    
+    /// Fewest discs a player still needs to complete one of their open lines (None if no line is open)
+    fn min_discs_missing(&self, player: Player) -> Option<usize> {
+        let own = self.get_player_board(player);
+        let opponent = self.get_player_board(player.opponent());
+        self.geometry
+            .get_winning_lines(self.win_length)
+            .iter()
+            .filter(|line_mask| opponent.count_set_bits_in_mask(line_mask) == 0)
+            .map(|line_mask| self.win_length - own.count_set_bits_in_mask(line_mask) as usize)
+            .min()
+    }
+    
     /// Get the board state for a specific player
-    fn get_player_board(&self, player: Player) -> &BitPackedBoard<6, 7, 2> {
+    fn get_player_board(&self, player: Player) -> &DynamicBitPackedBoard {
         match player {
             Player::Yellow => &self.yellow_board,
             Player::Red => &self.red_board,
@@ -124,7 +459,7 @@ impl Connect4Game {
     }
     
     /// Get the mutable board state for current player
-    fn get_current_player_board_mut(&mut self) -> &mut BitPackedBoard<6, 7, 2> {
+    fn get_current_player_board_mut(&mut self) -> &mut DynamicBitPackedBoard {
         match self.current_player {
             Player::Yellow => &mut self.yellow_board,
             Player::Red => &mut self.red_board,
@@ -136,7 +471,7 @@ impl Connect4Game {
     }
     
     /// Get the board state for current player
-    fn get_current_player_board(&self) -> &BitPackedBoard<6, 7, 2> {
+    fn get_current_player_board(&self) -> &DynamicBitPackedBoard {
         match self.current_player {
             Player::Yellow => &self.yellow_board,
             Player::Red => &self.red_board,
@@ -148,9 +483,9 @@ impl Connect4Game {
     }
     
     /// Get cell value at position (0 = empty, 1 = yellow, 2 = red)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_cell(&self, row: usize, col: usize) -> u8 {
-        if row >= 6 || col >= 7 {
+        if row >= self.rows || col >= self.cols {
             return 0;
         }
         
@@ -167,33 +502,33 @@ impl Connect4Game {
     }
     
     /// Get current player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn current_player(&self) -> Player {
         self.current_player
     }
     
     /// Get winner (if any)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn winner(&self) -> Option<Player> {
         self.winner
     }
     
     /// Get move count
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn move_count(&self) -> usize {
         self.move_count
     }
     
     /// Check if column is valid for next move
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn is_valid_move(&self, column: usize) -> bool {
-        column < 7 && self.column_heights[column] < 6 && self.winner.is_none()
+        column < self.cols && self.column_heights[column] < self.rows && !self.is_game_over()
     }
     
     /// Get column height
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_column_height(&self, column: usize) -> usize {
-        if column < 7 {
+        if column < self.cols {
             self.column_heights[column]
         } else {
             0
@@ -201,7 +536,7 @@ impl Connect4Game {
     }
     
     /// Reset game to initial state
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn reset(&mut self) {
         self.reset_with_starting_player(Player::Yellow);
     }
@@ -212,11 +547,21 @@ impl Connect4Game {
         self.red_board.clear();
         self.current_player = starting_player;
         self.winner = None;
+        self.adjudication.clear();
+        if let Some(clock) = self.clock.get_mut() {
+            clock.restart(starting_player);
+        }
         self.move_count = 0;
-        self.column_heights = [0; 7];
+        self.column_heights.fill(0);
+        self.move_history.clear();
+        self.position_history.clear();
+        if self.popout {
+            self.position_history.push(self.position_hash());
+        }
+        self.lines_closed = false;
         
-        // Reset AI state
-        self.ai = Connect4AI::new();
+        // Reset AI state (the AI itself, with its difficulty and profile, stays)
+        self.ai.evaluator().clear_cache();
         self.evaluator = PatternEvaluator::new();
     }
     
@@ -236,7 +581,7 @@ impl Connect4Game {
     /// Start a new game series with fixed player colors
     /// Players keep their colors throughout the series, only start order changes
     /// This is ideal for tournaments where Player A = always Yellow, Player B = always Red
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn start_new_series_with_players(&mut self, player_a: Player, player_b: Player, winner: Player) {
         // Validate that we're using Connect4 players
         let (color_a, color_b) = match (player_a, player_b) {
@@ -261,12 +606,12 @@ impl Connect4Game {
     }
     
     /// Get board state as string for debugging
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn board_string(&self) -> String {
         let mut result = String::new();
         
-        for row in 0..6 {
-            for col in 0..7 {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
                 let cell = self.get_cell(row, col);
                 let char = match cell {
                     0 => '.',
@@ -282,22 +627,39 @@ impl Connect4Game {
         result
     }
     
-    /// Check if game is draw (board full, no winner)
-    #[wasm_bindgen]
+    /// Check if game is draw (no winner and no legal move left, a dead position,
+    /// a claimed or agreed draw, or a PopOut repetition)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn is_draw(&self) -> bool {
-        self.winner.is_none() && self.move_count >= 42
+        if self.winner.is_some() {
+            return false;
+        }
+        if self.adjudication.is_draw() || self.is_dead_position() {
+            return true;
+        }
+        
+        let board_full = self.column_heights.iter().all(|&height| height >= self.rows);
+        let can_pop = self.popout
+            && (0..self.cols).any(|col| self.get_cell(self.rows - 1, col) == Self::player_cell(self.current_player));
+        (board_full && !can_pop) || self.is_repetition_draw()
     }
     
     /// Check if game is over (win or draw)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn is_game_over(&self) -> bool {
         self.winner.is_some() || self.is_draw()
     }
     
     /// Get AI move suggestion using BULLETPROOF 4-stage hierarchical decision logic
     /// ABSOLUTE PRIORITY: Own win > Block opponent > Strategic play
-    #[wasm_bindgen]
+    /// Returns a column, or `cols + column` for a PopOut pop (see `make_move_code`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_ai_move(&self) -> Option<usize> {
+        // Misère flips "win" and "block", so it has its own stages
+        if self.misere {
+            return self.get_misere_ai_move();
+        }
+        
         // STAGE 1: ABSOLUTE PRIORITY - Check for immediate winning moves
         // If we can win RIGHT NOW, do it - no matter what!
         if let Some(winning_move) = self.ai.find_immediate_win(self) {
@@ -307,6 +669,12 @@ impl Connect4Game {
         // STAGE 2: PFLICHTSTEIN-LOGIK - Block opponent's immediate wins
         // Only if we can't win ourselves, check if opponent can win
         let opponent_winning_moves = self.ai.find_opponent_winning_moves(self);
+        // A winning pop isn't blocked by dropping into its column, only by moves that defuse it
+        let opponent_winning_moves = if opponent_winning_moves.iter().any(|&code| code >= self.cols) {
+            self.ai.find_blocking_moves(self)
+        } else {
+            opponent_winning_moves
+        };
         if !opponent_winning_moves.is_empty() {
             // DOUBLE-CHECK: Make sure we're not missing our own win by blocking
             // This should never happen due to Stage 1, but extra safety
@@ -342,7 +710,7 @@ impl Connect4Game {
     }
     
     /// Analyze current position comprehensively
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn analyze_position(&self) -> PositionAnalysis {
         let current_threats = self.count_threats(self.current_player);
         let opponent_threats = self.count_threats(self.current_player.opponent());
@@ -361,134 +729,145 @@ impl Connect4Game {
     }
     
     /// Get current game phase for AI strategy
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_game_phase(&self) -> GamePhase {
-        match self.move_count {
-            0..=10 => GamePhase::Opening,
-            11..=30 => GamePhase::Middle,
-            _ => GamePhase::Endgame,
+        // Phase boundaries scale with the board (10 and 30 moves on the standard 42 cells)
+        let cells = self.rows * self.cols;
+        if self.move_count * 42 <= 10 * cells {
+            GamePhase::Opening
+        } else if self.move_count * 42 <= 30 * cells {
+            GamePhase::Middle
+        } else {
+            GamePhase::Endgame
         }
     }
     
     // === MISSING API METHODS FOR FRONTEND COMPATIBILITY ===
     
     /// Get memory usage of the game state (for performance monitoring)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn memory_usage(&self) -> usize {
         // Calculate approximate memory usage
-        let bitpacked_boards = std::mem::size_of::<BitPackedBoard<6, 7, 2>>() * 2; // yellow + red
-        let game_state = std::mem::size_of::<Connect4Game>() - bitpacked_boards;
+        let bitpacked_boards = self.yellow_board.memory_usage() + self.red_board.memory_usage();
+        let game_state = std::mem::size_of::<Connect4Game>() + self.column_heights.len() * std::mem::size_of::<usize>();
         let ai_state = std::mem::size_of::<Connect4AI>() + std::mem::size_of::<PatternEvaluator>();
         
         bitpacked_boards + game_state + ai_state
     }
     
     /// Get current player (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_current_player(&self) -> Player {
         self.current_player()
     }
     
     /// Set AI difficulty level
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn set_ai_difficulty(&mut self, difficulty: crate::ai::connect4_ai::AIDifficulty) {
         self.ai.set_difficulty_level(difficulty);
     }
     
     /// Get current AI difficulty level
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_ai_difficulty(&self) -> crate::ai::connect4_ai::AIDifficulty {
         self.ai.get_difficulty_level()
     }
     
+    /// Use this AI (e.g. one with a loaded profile) for hints and AI moves; kept across resets
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_ai(&mut self, ai: &Connect4AI) {
+        self.ai = ai.clone();
+    }
+    
+    /// Name of the AI's evaluation profile
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_ai_profile_name(&self) -> String {
+        self.ai.get_profile_name()
+    }
+    
     /// Get move count (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_move_count(&self) -> usize {
         self.move_count()
     }
     
     /// Get winner (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_winner(&self) -> Option<Player> {
         self.winner()
     }
     
-    /// Get board state as flat array for frontend (6 rows × 7 cols = 42 elements)
-    #[wasm_bindgen]
+    /// Get board state as flat array for frontend (rows × cols elements, 42 for the standard board)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_board(&self) -> Vec<u8> {
-        let mut board = vec![0u8; 42];
-        for row in 0..6 {
-            for col in 0..7 {
-                board[row * 7 + col] = self.get_cell(row, col);
+        let mut board = vec![0u8; self.rows * self.cols];
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                board[row * self.cols + col] = self.get_cell(row, col);
             }
         }
         board
     }
     
     /// Check if undo is possible
-    #[wasm_bindgen]
+    /// Only moves played on this game object count; discs loaded with `from_boards` have no history
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn can_undo(&self) -> bool {
-        self.move_count > 0 && !self.is_game_over()
+        !self.move_history.is_empty() && !self.is_game_over()
     }
     
-    /// Undo the last move
-    #[wasm_bindgen]
+    /// Undo the last move (drops and PopOut pops)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn undo_move(&mut self) -> bool {
-        if !self.can_undo() {
+        // A player whose flag fell has lost and can't take a move back
+        if !self.can_undo() || self.check_clock() {
             return false;
         }
         
-        // Find the column with the highest piece (most recent move)
-        let mut highest_col = None;
-        let mut highest_row = 0;
-        
-        for col in 0..7 {
-            if self.column_heights[col] > 0 {
-                let row = self.column_heights[col] - 1;
-                if row >= highest_row {
-                    highest_row = row;
-                    highest_col = Some(col);
+        if let Some(last_move) = self.move_history.pop() {
+            match last_move.kind {
+                Connect4MoveKind::Drop => {
+                    // The dropped disc is the top disc of its column
+                    let row = self.rows - self.column_heights[last_move.column];
+                    self.remove_piece_at(row, last_move.column);
+                }
+                Connect4MoveKind::Pop => {
+                    // Lift the column back up and put the popped disc under it
+                    self.shift_column_up(last_move.column);
+                    let index = (self.rows - 1) * self.cols + last_move.column;
+                    self.get_player_board_mut(last_move.player).set_bit(index, true);
+                    self.column_heights[last_move.column] += 1;
+                    self.move_count -= 1;
                 }
             }
-        }
-        
-        if let Some(col) = highest_col {
-            let row = self.column_heights[col] - 1;
-            let cell_value = self.get_cell(row, col);
             
-            if cell_value != 0 {
-                // Remove the piece from the appropriate board
-                if cell_value == 1 {
-                    self.yellow_board.clear_cell(row, col);
-                } else if cell_value == 2 {
-                    self.red_board.clear_cell(row, col);
-                }
-                
-                // Update game state
-                self.column_heights[col] -= 1;
-                self.move_count -= 1;
-                self.current_player = self.current_player.opponent();
-                self.winner = None; // Reset winner since we undid a move
-                
-                return true;
+            // Update game state
+            self.current_player = last_move.player;
+            self.winner = None; // Reset winner since we undid a move
+            self.position_history.pop();
+            self.refresh_lines_closed();
+            if let Some(clock) = self.clock.get_mut() {
+                clock.take_back(self.current_player);
             }
+            
+            true
+        } else {
+            false
         }
-        
-        false
     }
     
     /// Get AI board representation (for assistance system)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_ai_board(&self) -> Vec<u8> {
         self.get_board()
     }
     
     /// Get threatening moves for a player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_threatening_moves(&self, player: Player) -> Vec<usize> {
         let mut threats = Vec::new();
         
-        for col in 0..7 {
+        for col in 0..self.cols {
             if self.is_valid_move(col) {
                 // Simulate move
                 let mut test_game = self.clone();
@@ -505,11 +884,11 @@ impl Connect4Game {
     }
     
     /// Get winning moves for a player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_winning_moves(&self, player: Player) -> Vec<usize> {
         let mut winning_moves = Vec::new();
         
-        for col in 0..7 {
+        for col in 0..self.cols {
             if self.is_valid_move(col) {
                 // Simulate move
                 let mut test_game = self.clone();
@@ -523,12 +902,12 @@ impl Connect4Game {
     }
     
     /// Get blocking moves (moves that prevent opponent from winning)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_blocking_moves(&self, player: Player) -> Vec<usize> {
         let opponent = player.opponent();
         let mut blocking_moves = Vec::new();
         
-        for col in 0..7 {
+        for col in 0..self.cols {
             if self.is_valid_move(col) {
                 // Simulate opponent move
                 let mut test_game = self.create_hypothetical_state(opponent);
@@ -542,24 +921,24 @@ impl Connect4Game {
     }
     
     /// Evaluate position for a specific player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn evaluate_position_for_player(&self, player: Player) -> i32 {
         let hypothetical_game = self.create_hypothetical_state(player);
         self.ai.evaluate_position(&hypothetical_game)
     }
     
     /// Frontend-friendly method aliases
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn newGame(&mut self) {
         self.reset();
     }
     
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn undoMove(&mut self) -> bool {
         self.undo_move()
     }
     
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn getAIMove(&self) -> Option<usize> {
         self.get_ai_move()
     }
@@ -567,39 +946,77 @@ impl Connect4Game {
 
 // Internal implementation for AI access
 impl Connect4Game {
-    /// Create Connect4Game from existing boards (for testing)
+    /// Create a game with a custom board size (pure Rust, testable without a JS runtime)
+    pub fn new_with_size_internal(rows: usize, cols: usize, win_length: usize) -> Result<Connect4Game, GameError> {
+        if !(MIN_BOARD_SIDE..=MAX_BOARD_SIDE).contains(&rows) || !(MIN_BOARD_SIDE..=MAX_BOARD_SIDE).contains(&cols) {
+            return Err(GameError::InvalidBoardSize { rows, cols, min: MIN_BOARD_SIDE, max: MAX_BOARD_SIDE });
+        }
+        if win_length < MIN_WIN_LENGTH || win_length > rows.max(cols) {
+            return Err(GameError::InvalidWinLength { win_length, rows, cols });
+        }
+        
+        Ok(Self::with_dimensions(rows, cols, win_length, Player::Yellow))
+    }
+    
+    /// Empty game with the given (already validated) configuration
+    fn with_dimensions(rows: usize, cols: usize, win_length: usize, starting_player: Player) -> Self {
+        let geometry = DynamicQuadraticGrid::with_line_length(rows, cols, 2, win_length);
+        Self {
+            yellow_board: geometry.empty_board(),
+            red_board: geometry.empty_board(),
+            geometry,
+            ai: Connect4AI::new(),
+            evaluator: PatternEvaluator::new(),
+            current_player: starting_player,
+            winner: None,
+            move_count: 0,
+            column_heights: vec![0; cols],
+            move_history: Vec::new(),
+            position_history: Vec::new(),
+            lines_closed: false,
+            rows,
+            cols,
+            win_length,
+            popout: false,
+            misere: false,
+            claimable_draw: false,
+            adjudication: Adjudication::default(),
+            clock: AttachedClock::default(),
+        }
+    }
+    
+    /// Create Connect4Game from existing standard 7×6 boards (for testing)
     /// This allows loading arbitrary board states without move validation
+    /// The loaded discs are not part of the move history: later moves can be undone, the loaded ones can't
     pub fn from_boards(
         yellow_board: BitPackedBoard<6, 7, 2>,
         red_board: BitPackedBoard<6, 7, 2>, 
         current_player: Player
     ) -> Self {
+        let yellow_board = DynamicBitPackedBoard::from(&yellow_board);
+        let red_board = DynamicBitPackedBoard::from(&red_board);
+        
         // Calculate move count and column heights from boards
         let yellow_pieces = yellow_board.count_set_bits() as usize;
         let red_pieces = red_board.count_set_bits() as usize;
         let move_count = yellow_pieces + red_pieces;
         
         // Calculate column heights by counting pieces in each column
-        let mut column_heights = [0usize; 7];
-        for col in 0..7 {
-            for row in 0..6 {
+        let mut game = Self::new_with_starting_player(current_player);
+        for col in 0..game.cols {
+            for row in 0..game.rows {
                 if yellow_board.get_cell(row, col) != 0 || red_board.get_cell(row, col) != 0 {
-                    column_heights[col] += 1;
+                    game.column_heights[col] += 1;
                 }
             }
         }
         
-        Self {
-            geometry: Connect4Grid::new(),
-            yellow_board,
-            red_board,
-            ai: Connect4AI::new(),
-            evaluator: PatternEvaluator::new(),
-            current_player,
-            winner: None, // Will be determined by check_win_condition if needed
-            move_count,
-            column_heights,
-        }
+        // Winner will be determined by check_win_condition if needed
+        game.yellow_board = yellow_board;
+        game.red_board = red_board;
+        game.move_count = move_count;
+        game.refresh_lines_closed();
+        game
     }
     
     /// Validate if a Connect4 game state is reachable through legal moves
@@ -624,8 +1041,8 @@ impl Connect4Game {
         }
         
         // Physical validation: Check all pieces respect gravity
-        for row in 0..6 {
-            for col in 0..7 {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
                 if (self.yellow_board.get_cell(row, col) != 0 || 
                     self.red_board.get_cell(row, col) != 0) && 
                    !self.respects_gravity(row, col) {
@@ -665,9 +1082,9 @@ impl Connect4Game {
     fn find_valid_last_moves(&self, last_player: Player) -> Vec<(usize, usize)> {
         let mut valid_moves = Vec::new();
         
-        for col in 0..7 {
+        for col in 0..self.cols {
             // Find the topmost piece in this column
-            for row in 0..6 {
+            for row in 0..self.rows {
                 let is_yellow = self.yellow_board.get_cell(row, col) != 0;
                 let is_red = self.red_board.get_cell(row, col) != 0;
                 
@@ -690,7 +1107,7 @@ impl Connect4Game {
     /// Check if a piece placement respects gravity (no floating pieces)
     fn respects_gravity(&self, row: usize, col: usize) -> bool {
         // Bottom row always respects gravity
-        if row == 5 {
+        if row == self.rows - 1 {
             return true;
         }
         
@@ -707,7 +1124,7 @@ impl Connect4Game {
         self.red_board.clear_cell(row, col);
         
         // Update column heights
-        if col < 7 && self.column_heights[col] > 0 {
+        if col < self.cols && self.column_heights[col] > 0 {
             self.column_heights[col] -= 1;
         }
         
@@ -717,55 +1134,227 @@ impl Connect4Game {
         }
     }
     
-    /// Make a move for internal/test use (returns Result<bool, String>)
-    pub fn make_move_internal(&mut self, column: usize) -> Result<bool, String> {
+    /// Make a move for internal/test use
+    pub fn make_move_internal(&mut self, column: usize) -> Result<bool, GameError> {
         // Validate column
-        if column >= 7 {
-            return Err("Invalid column".to_string());
+        if column >= self.cols {
+            return Err(GameError::InvalidColumn);
         }
         
         // Check if column is full
-        if self.column_heights[column] >= 6 {
-            return Err("Column is full".to_string());
+        if self.column_heights[column] >= self.rows {
+            return Err(GameError::ColumnFull);
         }
         
         // Check if game is over
-        if self.winner.is_some() {
-            return Err("Game is already over".to_string());
+        if self.is_game_over() {
+            return Err(GameError::GameAlreadyOver);
+        }
+        
+        // No move once the flag fell; the game is left as it was and `check_clock` records the loss
+        if self.is_out_of_time() {
+            return Err(GameError::TimeForfeit);
         }
         
         // Calculate the row where the piece will land
-        let row = 6 - 1 - self.column_heights[column]; // Bottom up
+        let row = self.rows - 1 - self.column_heights[column]; // Bottom up
         
         // Use geometry layer to get the index
         let index = self.geometry.to_index((row as i32, column as i32))
-            .ok_or_else(|| "Invalid position".to_string())?;
+            .ok_or(GameError::OutOfBounds)?;
         
         // Use data layer to place the piece
         let player_board = self.get_current_player_board_mut();
         player_board.set_bit(index, true);
+        self.refresh_lines_closed();
         
         // Update column height
         self.column_heights[column] += 1;
         self.move_count += 1;
+        self.move_history.push(Connect4Move { column, kind: Connect4MoveKind::Drop, player: self.current_player });
+        self.adjudication.on_move(self.current_player);
         
         // Check for win condition
         let won = self.check_win_condition();
+        self.finish_move(won);
         
-        if !won {
-            // Switch players
-            self.current_player = self.current_player.opponent();
+        Ok(won)
+    }
+    
+    /// PopOut move for internal/test use
+    /// A pop can complete lines for both players: the popping player's own line decides first,
+    /// otherwise the opponent's completed line decides (misère rules swap the winner in both cases)
+    pub fn pop_out_internal(&mut self, column: usize) -> Result<bool, GameError> {
+        if !self.popout {
+            return Err(GameError::PopOutDisabled);
+        }
+        if column >= self.cols {
+            return Err(GameError::InvalidColumn);
+        }
+        if self.is_game_over() {
+            return Err(GameError::GameAlreadyOver);
+        }
+        if self.is_out_of_time() {
+            return Err(GameError::TimeForfeit);
+        }
+        if self.get_cell(self.rows - 1, column) != Self::player_cell(self.current_player) {
+            return Err(GameError::NotOwnDisc);
         }
         
+        self.shift_column_down(column);
+        self.refresh_lines_closed();
+        self.column_heights[column] -= 1;
+        self.move_count += 1;
+        self.move_history.push(Connect4Move { column, kind: Connect4MoveKind::Pop, player: self.current_player });
+        self.adjudication.on_move(self.current_player);
+        
+        // Simultaneous-four resolution: own line first, then the opponent's
+        let opponent = self.current_player.opponent();
+        let won = if self.check_win_condition() {
+            true
+        } else if self.has_winning_line(opponent) {
+            self.winner = Some(self.line_winner(opponent));
+            true
+        } else {
+            false
+        };
+        self.finish_move(won);
+        
         Ok(won)
     }
+    
+    /// End of every drop and pop: the turn passes unless a line ended the game,
+    /// then the position is recorded and the mover's clock pressed
+    fn finish_move(&mut self, won: bool) {
+        let mover = self.current_player;
+        if !won {
+            self.current_player = mover.opponent();
+        }
+        self.record_position();
+        self.press_clock(mover);
+    }
+    
+    /// Hit the clock after a move: stop it if the game ended, otherwise start the opponent's time
+    /// The mover still loses on time if the move itself took too long
+    fn press_clock(&mut self, mover: Player) {
+        let game_over = self.is_game_over();
+        let Some(clock) = self.clock.get_mut() else {
+            return;
+        };
+        if game_over {
+            clock.stop();
+        } else if !clock.press(mover) {
+            self.winner = Some(mover.opponent());
+            self.adjudication.end(self.winner, TerminationReason::Timeout);
+        }
+    }
+    
+    /// Play a move number (drops are `column`, pops are `cols + column`)
+    pub fn make_move_code_internal(&mut self, code: usize) -> Result<bool, GameError> {
+        let game_move = Connect4Move::decode(code, self.cols, self.current_player)
+            .ok_or(GameError::InvalidMove)?;
+        match game_move.kind {
+            Connect4MoveKind::Drop => self.make_move_internal(game_move.column),
+            Connect4MoveKind::Pop => self.pop_out_internal(game_move.column),
+        }
+    }
+    
+    /// Check if a move number is legal for the current player
+    pub fn is_legal_move_code(&self, code: usize) -> bool {
+        match Connect4Move::decode(code, self.cols, self.current_player) {
+            Some(Connect4Move { column, kind: Connect4MoveKind::Drop, .. }) => self.is_valid_move(column),
+            Some(Connect4Move { column, kind: Connect4MoveKind::Pop, .. }) => self.can_pop(column),
+            None => false,
+        }
+    }
+    
+    /// Create a copy of the game with a move number applied (for AI lookahead)
+    pub fn make_move_code_copy(&self, code: usize) -> Option<Connect4Game> {
+        if !self.is_legal_move_code(code) {
+            return None;
+        }
+        
+        let mut game_copy = self.clone();
+        game_copy.make_move_code_internal(code).ok().map(|_| game_copy)
+    }
+    
+    /// Moves played so far (oldest first)
+    pub fn move_history(&self) -> &[Connect4Move] {
+        &self.move_history
+    }
+    
+    /// Cell value used for a player's discs (1 = yellow, 2 = red)
+    fn player_cell(player: Player) -> u8 {
+        match player {
+            Player::Yellow => 1,
+            Player::Red => 2,
+            Player::Black | Player::White => 0,
+        }
+    }
+    
+    /// Mutable board of a specific player
+    fn get_player_board_mut(&mut self, player: Player) -> &mut DynamicBitPackedBoard {
+        match player {
+            Player::Red => &mut self.red_board,
+            _ => &mut self.yellow_board,
+        }
+    }
+    
+    /// Remove the bottom disc of a column and let the discs above drop one row
+    fn shift_column_down(&mut self, column: usize) {
+        for board in [&mut self.yellow_board, &mut self.red_board] {
+            for row in (1..self.rows).rev() {
+                let above = board.get_bit((row - 1) * self.cols + column);
+                board.set_bit(row * self.cols + column, above);
+            }
+            board.set_bit(column, false);
+        }
+    }
+    
+    /// Inverse of `shift_column_down`: lift the column by one row, leaving the bottom empty
+    fn shift_column_up(&mut self, column: usize) {
+        for board in [&mut self.yellow_board, &mut self.red_board] {
+            for row in 0..self.rows - 1 {
+                let below = board.get_bit((row + 1) * self.cols + column);
+                board.set_bit(row * self.cols + column, below);
+            }
+            board.set_bit((self.rows - 1) * self.cols + column, false);
+        }
+    }
+    
+    /// Hash of the discs and the player to move (PopOut repetition detection)
+    fn position_hash(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.yellow_board.data.hash(&mut hasher);
+        self.red_board.data.hash(&mut hasher);
+        (self.current_player as u8).hash(&mut hasher);
+        hasher.finish()
+    }
+    
+    /// Remember the position after a move (only PopOut positions can repeat)
+    fn record_position(&mut self) {
+        if self.popout {
+            let hash = self.position_hash();
+            self.position_history.push(hash);
+        }
+    }
+    
+    /// How often the current position has occurred (PopOut only, 0 otherwise)
+    fn position_repetitions(&self) -> usize {
+        match self.position_history.last() {
+            Some(current) if self.popout => self.position_history.iter().filter(|&hash| hash == current).count(),
+            _ => 0,
+        }
+    }
+    
     /// Get board for AI evaluation (internal use)
-    pub fn get_board_for_player(&self, player: Player) -> &BitPackedBoard<6, 7, 2> {
+    pub fn get_board_for_player(&self, player: Player) -> &DynamicBitPackedBoard {
         self.get_player_board(player)
     }
     
     /// Get geometry for AI evaluation (internal use)
-    pub fn geometry(&self) -> &Connect4Grid {
+    pub fn geometry(&self) -> &DynamicQuadraticGrid {
         &self.geometry
     }
     
@@ -784,15 +1373,15 @@ impl Connect4Game {
     pub fn count_threats(&self, player: Player) -> usize {
         let player_board = self.get_player_board(player);
         let opponent_board = self.get_player_board(player.opponent());
-        let winning_lines = self.geometry.get_winning_lines(4);
+        let winning_lines = self.geometry.get_winning_lines(self.win_length);
         
         let mut threats = 0;
         for line_mask in winning_lines {
             let player_pieces = player_board.count_set_bits_in_mask(line_mask);
             let opponent_pieces = opponent_board.count_set_bits_in_mask(line_mask);
             
-            // A threat is 3 pieces in a line with no opponent pieces
-            if player_pieces == 3 && opponent_pieces == 0 {
+            // A threat is one piece short of a win in a line with no opponent pieces
+            if player_pieces == self.win_length as u32 - 1 && opponent_pieces == 0 {
                 threats += 1;
             }
         }
@@ -804,8 +1393,8 @@ impl Connect4Game {
     pub fn evaluate_connectivity(&self) -> i32 {
         let mut connectivity = 0;
         
-        // Check 2-piece and 3-piece combinations for both players
-        let lines_of_3 = self.geometry.get_winning_lines(3);
+        // Check 2-piece and 3-piece combinations for both players (lines one shorter than a win)
+        let lines_of_3 = self.geometry.get_winning_lines(self.win_length - 1);
         
         for line_mask in lines_of_3 {
             let current_pieces = self.get_current_player_board().count_set_bits_in_mask(line_mask);
@@ -827,17 +1416,7 @@ impl Connect4Game {
             return None;
         }
         
-        let mut game_copy = Self {
-            geometry: self.geometry.clone(),
-            yellow_board: self.yellow_board.clone(),
-            red_board: self.red_board.clone(),
-            ai: self.ai.clone(),
-            evaluator: self.evaluator.clone(),
-            current_player: self.current_player,
-            winner: self.winner,
-            move_count: self.move_count,
-            column_heights: self.column_heights,
-        };
+        let mut game_copy = self.clone();
         
         if game_copy.make_move(column).is_ok() {
             Some(game_copy)
@@ -852,17 +1431,8 @@ impl Connect4Game {
             return None;
         }
         
-        let mut game_copy = Self {
-            geometry: self.geometry.clone(),
-            yellow_board: self.yellow_board.clone(),
-            red_board: self.red_board.clone(),
-            ai: self.ai.clone(),
-            evaluator: self.evaluator.clone(),
-            current_player: player,  // Set the specific player
-            winner: self.winner,
-            move_count: self.move_count,
-            column_heights: self.column_heights,
-        };
+        let mut game_copy = self.clone();
+        game_copy.current_player = player;  // Set the specific player
         
         if game_copy.make_move(column).is_ok() {
             Some(game_copy)
@@ -872,20 +1442,25 @@ impl Connect4Game {
     }
     
     /// Get safe moves that don't create immediate wins for opponent (Stage 3)
+    /// Includes PopOut pops (as move numbers `cols + column`) when the rule is enabled
     fn get_safe_moves(&self) -> Vec<usize> {
         let mut safe_moves = Vec::new();
-        
-        for col in 0..7 {
-            if self.is_valid_move(col) {
-                // Create a copy with this move applied
-                if let Some(game_after_move) = self.make_move_copy(col) {
-                    // FIXED: Check if opponent has winning moves after our move
-                    let opponent_winning_moves = game_after_move.ai.find_opponent_winning_moves(&game_after_move);
-                    
-                    // Move is safe if opponent has NO winning moves after it
-                    if opponent_winning_moves.is_empty() {
-                        safe_moves.push(col);
-                    }
+        let opponent = self.current_player.opponent();
+        
+        for col in self.get_legal_moves() {
+            // Create a copy with this move applied
+            if let Some(game_after_move) = self.make_move_code_copy(col) {
+                // A pop can hand the opponent a finished line
+                if game_after_move.winner() == Some(opponent) {
+                    continue;
+                }
+                
+                // FIXED: Check if opponent has winning moves after our move
+                let opponent_winning_moves = game_after_move.ai.find_opponent_winning_moves(&game_after_move);
+                
+                // Move is safe if opponent has NO winning moves after it
+                if opponent_winning_moves.is_empty() {
+                    safe_moves.push(col);
                 }
             }
         }
@@ -893,6 +1468,65 @@ impl Connect4Game {
         safe_moves
     }
     
+    /// Misère move selection (completing an own line loses)
+    /// STAGE 1: take a win handed over by a pop that completes the opponent's line
+    /// STAGE 2: leave the opponent only moves that complete one of their lines
+    /// STAGE 3: never complete an own line, and avoid giving the opponent an immediate win
+    /// STAGE 4: difficulty-specific strategy on the inverted evaluation
+    fn get_misere_ai_move(&self) -> Option<usize> {
+        let player = self.current_player;
+        let mut non_losing = Vec::new();
+        
+        for code in self.get_legal_moves() {
+            if let Some(game_after_move) = self.make_move_code_copy(code) {
+                match game_after_move.winner() {
+                    Some(winner) if winner == player => return Some(code),
+                    Some(_) => {}
+                    None => non_losing.push((code, game_after_move)),
+                }
+            }
+        }
+        
+        if non_losing.is_empty() {
+            // Every move completes an own line - the game is lost anyway
+            return self.get_legal_moves().first().copied();
+        }
+        
+        if let Some((code, _)) = non_losing.iter().find(|(_, after)| after.every_reply_loses()) {
+            return Some(*code);
+        }
+        
+        let safe_moves: Vec<usize> = non_losing
+            .iter()
+            .filter(|(_, after)| !after.has_immediate_win())
+            .map(|(code, _)| *code)
+            .collect();
+        if safe_moves.is_empty() {
+            let fallback: Vec<usize> = non_losing.iter().map(|(code, _)| *code).collect();
+            return self.get_stage4_move(&fallback);
+        }
+        
+        self.get_stage4_move(&safe_moves)
+    }
+    
+    /// True if every legal move of the current player ends the game in the opponent's favour
+    fn every_reply_loses(&self) -> bool {
+        let opponent = self.current_player.opponent();
+        let moves = self.get_legal_moves();
+        !moves.is_empty()
+            && moves
+                .into_iter()
+                .all(|code| self.make_move_code_copy(code).is_some_and(|after| after.winner() == Some(opponent)))
+    }
+    
+    /// True if the current player has a move that wins on the spot
+    fn has_immediate_win(&self) -> bool {
+        let player = self.current_player;
+        self.get_legal_moves()
+            .into_iter()
+            .any(|code| self.make_move_code_copy(code).is_some_and(|after| after.winner() == Some(player)))
+    }
+    
     /// Get best move from a list of candidate moves using minimax (Stage 4)
     fn get_best_move_from_candidates(&self, candidates: &[usize]) -> Option<usize> {
         if candidates.is_empty() {
@@ -907,7 +1541,7 @@ impl Connect4Game {
         let mut best_score = i32::MIN;
         
         for &col in candidates {
-            if let Some(game_copy) = self.make_move_copy(col) {
+            if let Some(game_copy) = self.make_move_code_copy(col) {
                 // Evaluate this position
                 let score = self.ai.evaluate_position(&game_copy);
                 
@@ -940,11 +1574,18 @@ impl Connect4Game {
             AIStrategy::StrongMCTS |
             AIStrategy::AdaptiveMCTS => {
                 let depth = self.ai.get_mcts_depth(strategy, self.move_count);
+                let depth = self.clock_depth_limit().map_or(depth, |limit| depth.min(limit));
                 self.get_mcts_move_with_depth(safe_moves, depth)
             },
         }
     }
     
+    /// Search depth the clock allows the player to move (None if untimed or plenty of time is left)
+    fn clock_depth_limit(&self) -> Option<usize> {
+        let clock = self.clock.get()?;
+        self.ai.depth_limit_for_budget(clock.move_budget_ms(self.current_player))
+    }
+    
     /// Get random move from safe moves (Stage 4: Random strategy)
     fn get_random_safe_move(&self, safe_moves: &[usize]) -> Option<usize> {
         if safe_moves.is_empty() {
@@ -971,7 +1612,7 @@ impl Connect4Game {
         let mut best_score = i32::MIN;
         
         for &col in safe_moves {
-            if let Some(game_copy) = self.make_move_copy(col) {
+            if let Some(game_copy) = self.make_move_code_copy(col) {
                 // Use temporary AI with specific depth
                 let mut temp_ai = self.ai.clone();
                 temp_ai.set_difficulty(depth);
@@ -1044,6 +1685,23 @@ mod tests {
         assert_eq!(game.winner(), Some(Player::Yellow));
     }
     
+    #[test]
+    fn test_winning_cells() {
+        let mut game = Connect4Game::new();
+        for column in [0, 0, 1, 1, 2, 2, 4, 4, 5, 5, 6, 6] {
+            game.make_move_internal(column).unwrap();
+        }
+        assert!(game.get_winning_cells().is_empty());
+        
+        // Filling the gap completes the whole bottom row: one line of seven
+        assert_eq!(game.make_move_internal(3), Ok(true));
+        let lines = game.get_completed_lines();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].player(), Player::Yellow);
+        assert_eq!(lines[0].length(), 7);
+        assert_eq!(game.get_winning_cells(), (0..7).flat_map(|col| [5, col]).collect::<Vec<_>>());
+    }
+    
     #[test]
     fn test_horizontal_blocking_scenario() {
         let mut game = Connect4Game::new();
@@ -1688,4 +2346,436 @@ mod tests {
         game2.start_new_series_with_players(Player::Yellow, Player::Red, Player::Red);
         assert_eq!(game2.current_player(), Player::Yellow);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_tournament_variants() {
+        let mut game = Connect4Game::new_variant(Connect4Variant::Large10x7);
+        assert_eq!((game.get_rows(), game.get_cols(), game.get_win_length()), (7, 10, 4));
+        assert_eq!(game.get_board().len(), 70);
+        
+        // Horizontal win along the bottom row using the extra columns
+        for col in 6..9 {
+            game.make_move_internal(col).unwrap(); // Yellow
+            game.make_move_internal(col).unwrap(); // Red
+        }
+        assert_eq!(game.make_move_internal(9), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Yellow));
+        assert_eq!(game.get_cell(6, 9), 1);
+        
+        // Columns fill up after 7 pieces
+        let mut tall = Connect4Game::new_variant(Connect4Variant::Large8x7);
+        for _ in 0..7 {
+            tall.make_move_internal(7).unwrap();
+        }
+        assert!(!tall.is_valid_move(7));
+        assert!(tall.make_move_internal(7).is_err());
+        assert!(tall.make_move_internal(8).is_err());
+    }
+    
+    #[test]
+    fn test_connect5_needs_five() {
+        let mut game = Connect4Game::new_variant(Connect4Variant::Connect5);
+        
+        for col in 0..4 {
+            assert_eq!(game.make_move_internal(col), Ok(false)); // Yellow
+            game.make_move_internal(col).unwrap(); // Red
+        }
+        assert_eq!(game.winner(), None);
+        assert_eq!(game.count_threats(Player::Yellow), 1);
+        
+        assert_eq!(game.make_move_internal(4), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Yellow));
+    }
+    
+    #[test]
+    fn test_custom_size_validation_and_draw() {
+        assert!(Connect4Game::new_with_size_internal(3, 7, 4).is_err());
+        assert!(Connect4Game::new_with_size_internal(6, 7, 8).is_err());
+        assert!(Connect4Game::new_with_size_internal(6, 7, 2).is_err());
+        
+        // 4×4 board with four in a row: play it out without a line to reach a draw
+        // The position goes dead (every line holds both colours) before the board is full
+        let mut game = Connect4Game::new_with_size_internal(4, 4, 4).unwrap();
+        for col in [0, 1, 0, 1, 2, 3, 2, 3, 1, 0, 1, 0, 3, 2, 3, 2] {
+            if game.is_game_over() {
+                break;
+            }
+            assert_eq!(game.make_move_internal(col), Ok(false));
+        }
+        assert!(game.is_dead_position());
+        assert!(game.move_count() < 16);
+        assert!(game.is_draw());
+        assert!(game.is_game_over());
+    }
+    
+    #[test]
+    fn test_claimable_draw() {
+        let mut game = Connect4Game::new_with_size_internal(4, 4, 4).unwrap();
+        assert!(!game.claim_draw()); // Not enabled
+        game.set_claimable_draw_enabled(true);
+        
+        for col in [0, 1, 0, 1, 2, 3, 2, 3, 1, 0, 1, 0, 3] {
+            assert!(!game.can_claim_draw());
+            game.make_move_internal(col).unwrap();
+        }
+        
+        // One open line is left, but its owner can't fill it with the moves remaining
+        assert!(!game.is_dead_position());
+        assert!(game.can_claim_draw());
+        assert!(game.claim_draw());
+        assert!(game.is_draw());
+        assert!(game.is_game_over());
+        assert!(!game.is_valid_move(2));
+        
+        game.reset();
+        assert!(!game.is_draw());
+    }
+    
+    #[test]
+    fn test_game_results() {
+        let mut game = Connect4Game::new();
+        assert_eq!(game.get_result(), None);
+        for column in [0, 1, 0, 1, 0, 1] {
+            game.make_move_internal(column).unwrap();
+        }
+        game.make_move_internal(0).unwrap();
+        let result = game.get_result().unwrap();
+        assert_eq!(result.winner(), Some(Player::Yellow));
+        assert_eq!(result.reason(), TerminationReason::LineCompleted);
+        assert_eq!(result.final_move(), vec![0]);
+        
+        // Resignation hands the win to the opponent
+        game.reset();
+        game.make_move_internal(3).unwrap();
+        assert!(!game.resign(Player::Black));
+        assert!(game.resign(Player::Red));
+        assert!(!game.resign(Player::Yellow)); // Already over
+        assert_eq!(game.winner(), Some(Player::Yellow));
+        assert_eq!(game.get_result().unwrap().to_record(), "Yellow|resignation|3");
+        
+        // A draw offer is declined by moving on, accepted by the opponent
+        game.reset();
+        assert!(game.offer_draw(Player::Yellow));
+        assert!(!game.accept_draw(Player::Yellow));
+        game.make_move_internal(3).unwrap(); // Yellow
+        game.make_move_internal(3).unwrap(); // Red declines by moving
+        assert_eq!(game.get_draw_offer(), None);
+        assert!(game.offer_draw(Player::Yellow));
+        assert!(game.accept_draw(Player::Red));
+        assert!(game.is_draw());
+        assert!(!game.is_valid_move(0));
+        let result = game.get_result().unwrap();
+        assert!(result.is_draw());
+        assert_eq!(result.reason(), TerminationReason::AgreedDraw);
+    }
+    
+    #[test]
+    fn test_clock_flags_on_move() {
+        use crate::games::clock::{ManualTimeSource, TimeControl};
+        use std::sync::Arc;
+        
+        let time = ManualTimeSource::new(0);
+        let mut game = Connect4Game::new();
+        game.attach_clock(GameClock::with_time_source(TimeControl::fischer(5_000, 1_000), Arc::new(time.clone())));
+        
+        time.advance(2_000);
+        game.make_move_internal(3).unwrap();
+        let clock = game.get_clock().unwrap();
+        assert_eq!(clock.remaining_ms(Player::Yellow), 4_000);
+        assert!(clock.is_running(Player::Red));
+        
+        // Clones for AI lookahead don't carry the clock
+        assert!(game.clone().get_clock().is_none());
+        
+        // A short budget caps the search depth
+        time.advance(4_950);
+        assert_eq!(game.clock_depth_limit(), Some(2));
+        
+        time.advance(100);
+        assert_eq!(game.make_move_internal(3), Err(GameError::TimeForfeit));
+        assert_eq!(game.winner(), None); // Left untouched until the loss is recorded
+        assert_eq!(game.move_count(), 1);
+        assert!(game.check_clock());
+        assert_eq!(game.winner(), Some(Player::Yellow));
+        assert_eq!(game.get_result().unwrap().reason(), TerminationReason::Timeout);
+        
+        // Reset restarts the clock with full time
+        game.reset();
+        assert_eq!(game.get_clock().unwrap().remaining_ms(Player::Red), 5_000);
+        assert!(game.make_move_internal(3).is_ok());
+    }
+    
+    #[test]
+    fn test_undo_hands_the_clock_back() {
+        use crate::games::clock::{ManualTimeSource, TimeControl};
+        use std::sync::Arc;
+        
+        let time = ManualTimeSource::new(0);
+        let mut game = Connect4Game::new();
+        game.attach_clock(GameClock::with_time_source(TimeControl::fischer(60_000, 0), Arc::new(time.clone())));
+        
+        time.advance(1_000);
+        game.make_move_internal(3).unwrap();
+        time.advance(2_000);
+        assert!(game.undo_move());
+        let clock = game.get_clock().unwrap();
+        assert!(clock.is_running(Player::Yellow));
+        assert_eq!(clock.remaining_ms(Player::Red), 58_000); // Red is charged for the time before the undo
+        
+        assert_eq!(game.make_move_internal(2), Ok(false));
+        assert_eq!(game.winner(), None);
+        assert!(game.get_result().is_none());
+        
+        // A player whose flag fell can't undo
+        time.advance(60_000);
+        assert!(!game.undo_move());
+        assert_eq!(game.winner(), Some(Player::Yellow));
+    }
+    
+    /// Stack discs bottom-up into a column without move validation
+    fn stack(game: &mut Connect4Game, col: usize, discs: &[Player]) {
+        for &player in discs {
+            let index = (game.rows - 1 - game.column_heights[col]) * game.cols + col;
+            game.get_player_board_mut(player).set_bit(index, true);
+            game.column_heights[col] += 1;
+            game.move_count += 1;
+        }
+    }
+    
+    #[test]
+    fn test_popout_moves_and_undo() {
+        let mut game = Connect4Game::new();
+        assert!(game.pop_out_internal(0).is_err()); // Rule not enabled
+        game.set_popout_enabled(true);
+        
+        game.make_move_internal(0).unwrap(); // Yellow
+        game.make_move_internal(1).unwrap(); // Red
+        game.make_move_internal(0).unwrap(); // Yellow
+        
+        // Red may only pop its own bottom disc
+        assert!(!game.can_pop(0));
+        assert!(game.can_pop(1));
+        assert_eq!(game.get_legal_moves(), vec![0, 1, 2, 3, 4, 5, 6, 8]);
+        assert!(game.pop_out_internal(0).is_err());
+        
+        assert_eq!(game.make_move_code_internal(8), Ok(false)); // Red pops column 1
+        assert_eq!(game.get_column_height(1), 0);
+        assert_eq!(game.pop_out_internal(0), Ok(false)); // Yellow pops column 0
+        assert_eq!(game.get_column_height(0), 1);
+        assert_eq!(game.get_cell(5, 0), 1);
+        assert_eq!(game.get_cell(4, 0), 0);
+        
+        // Undo restores the popped discs and the player to move
+        assert!(game.undo_move());
+        assert_eq!(game.current_player(), Player::Yellow);
+        assert_eq!((game.get_cell(5, 0), game.get_cell(4, 0)), (1, 1));
+        assert!(game.undo_move());
+        assert_eq!(game.get_cell(5, 1), 2);
+        assert_eq!(game.get_column_height(1), 1);
+        assert_eq!(game.move_count(), 3);
+    }
+    
+    #[test]
+    fn test_popout_simultaneous_four_goes_to_popper() {
+        let mut game = Connect4Game::new();
+        game.set_popout_enabled(true);
+        
+        // Popping column 0 completes Red's bottom row and Yellow's second row at once
+        stack(&mut game, 0, &[Player::Yellow, Player::Red, Player::Yellow]);
+        for col in 1..4 {
+            stack(&mut game, col, &[Player::Red, Player::Yellow]);
+        }
+        
+        assert_eq!(game.pop_out_internal(0), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Yellow));
+        assert_eq!(game.current_player(), Player::Yellow);
+    }
+    
+    #[test]
+    fn test_misere_popout_double_line_ends_like_a_drop() {
+        let mut game = Connect4Game::new();
+        game.set_popout_enabled(true);
+        game.set_misere_enabled(true);
+        
+        // Same position as above: Yellow's own row decides first and now loses
+        stack(&mut game, 0, &[Player::Yellow, Player::Red, Player::Yellow]);
+        for col in 1..4 {
+            stack(&mut game, col, &[Player::Red, Player::Yellow]);
+        }
+        
+        assert_eq!(game.pop_out_internal(0), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Red));
+        assert_eq!(game.current_player(), Player::Yellow); // The turn doesn't pass, as after a final drop
+    }
+    
+    #[test]
+    fn test_popout_repetition_draw() {
+        let mut game = Connect4Game::new();
+        game.set_popout_enabled(true);
+        
+        for _ in 0..2 {
+            game.make_move_internal(0).unwrap(); // Yellow
+            game.make_move_internal(1).unwrap(); // Red
+            assert!(!game.is_repetition_draw());
+            game.pop_out_internal(0).unwrap(); // Yellow
+            game.pop_out_internal(1).unwrap(); // Red: empty board, Yellow to move again
+        }
+        
+        // Third occurrence of the empty board with Yellow to move
+        assert!(game.is_repetition_draw());
+        assert!(game.is_draw());
+        assert!(game.make_move_internal(3).is_err());
+    }
+    
+    #[test]
+    fn test_popout_full_board_is_not_a_draw() {
+        let mut game = Connect4Game::new_with_size_internal(4, 4, 4).unwrap();
+        game.set_popout_enabled(true);
+        for col in [0, 1, 0, 1, 2, 3, 2, 3, 1, 0, 1, 0, 3, 2, 3, 2] {
+            game.make_move_internal(col).unwrap();
+        }
+        
+        // Board is full, but Yellow still owns bottom discs to pop
+        assert!(!game.is_draw());
+        assert_eq!(game.get_legal_moves(), vec![4, 6]);
+    }
+    
+    #[test]
+    fn test_ai_finds_winning_pop() {
+        let mut game = Connect4Game::new();
+        game.set_popout_enabled(true);
+        
+        // Red pops column 0: its upper disc drops into the gap of the Red row
+        stack(&mut game, 0, &[Player::Red, Player::Yellow, Player::Red]);
+        for col in 1..4 {
+            stack(&mut game, col, &[Player::Yellow, Player::Red]);
+        }
+        game.current_player = Player::Red;
+        
+        assert_eq!(game.ai.find_immediate_win(&game), Some(7));
+        assert_eq!(game.get_ai_move(), Some(7));
+        assert_eq!(game.make_move_code_internal(7), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Red));
+    }
+    
+    #[test]
+    fn test_ai_profile_survives_reset() {
+        let mut ai = Connect4AI::with_difficulty(crate::ai::connect4_ai::AIDifficulty::Hard);
+        ai.set_profile("positional").unwrap();
+        
+        let mut game = Connect4Game::new();
+        game.set_ai(&ai);
+        game.make_move(3).unwrap();
+        assert_eq!(game.get_ai_profile_name(), "positional");
+        
+        game.reset();
+        game.reset_with_starting_player(Player::Red);
+        assert_eq!(game.get_ai_profile_name(), "positional");
+        assert_eq!(game.get_ai_difficulty(), crate::ai::connect4_ai::AIDifficulty::Hard);
+        assert_eq!(game.ai.evaluator().center_bonus, 20);
+    }
+    
+    #[test]
+    fn test_ai_blocks_winning_pop() {
+        let mut game = Connect4Game::new();
+        game.set_popout_enabled(true);
+        
+        // Yellow's only threat is popping column 3, which drops a Yellow disc into the gap of the second row
+        for col in 0..2 {
+            stack(&mut game, col, &[Player::Red, Player::Yellow]);
+        }
+        stack(&mut game, 2, &[Player::Yellow, Player::Yellow]);
+        stack(&mut game, 3, &[Player::Yellow, Player::Red, Player::Yellow]);
+        stack(&mut game, 5, &[Player::Red, Player::Red]);
+        stack(&mut game, 6, &[Player::Red]);
+        game.current_player = Player::Red;
+        
+        assert_eq!(game.ai.find_opponent_winning_moves(&game), vec![10]);
+        
+        // Dropping into column 3 keeps the threat; popping a Red disc out from under Yellow's row breaks it
+        let blocking_moves = game.ai.find_blocking_moves(&game);
+        assert_eq!(blocking_moves, vec![7, 8]);
+        let ai_move = game.get_ai_move().unwrap();
+        assert!(blocking_moves.contains(&ai_move));
+        game.make_move_code_internal(ai_move).unwrap();
+        assert!(game.get_legal_moves().iter().all(|&code| game.make_move_code_copy(code).unwrap().winner() != Some(Player::Yellow)));
+    }
+    
+    #[test]
+    fn test_misere_completing_four_loses() {
+        let mut game = Connect4Game::new();
+        game.set_misere_enabled(true);
+        assert!(game.is_misere_enabled());
+        
+        for col in [0, 6, 1, 6, 2, 5] {
+            assert_eq!(game.make_move_internal(col), Ok(false));
+        }
+        assert_eq!(game.make_move_internal(3), Ok(true)); // Yellow completes the bottom row
+        assert_eq!(game.winner(), Some(Player::Red));
+    }
+    
+    #[test]
+    fn test_misere_ai_avoids_own_four() {
+        let mut game = Connect4Game::new();
+        game.set_misere_enabled(true);
+        for col in 0..3 {
+            stack(&mut game, col, &[Player::Yellow]);
+        }
+        stack(&mut game, 6, &[Player::Red, Player::Red, Player::Red]);
+        
+        // Normal rules take the win, misère rules must stay away from it
+        game.set_misere_enabled(false);
+        assert_eq!(game.get_ai_move(), Some(3));
+        game.set_misere_enabled(true);
+        let ai_move = game.get_ai_move().unwrap();
+        assert_ne!(ai_move, 3);
+        assert_eq!(game.make_move_code_internal(ai_move), Ok(false));
+    }
+    
+    #[test]
+    fn test_misere_popout_own_line_loses() {
+        let mut game = Connect4Game::new();
+        game.set_popout_enabled(true);
+        game.set_misere_enabled(true);
+        
+        // Same position as the winning pop above: under misère Red's own row now loses
+        stack(&mut game, 0, &[Player::Red, Player::Yellow, Player::Red]);
+        for col in 1..4 {
+            stack(&mut game, col, &[Player::Yellow, Player::Red]);
+        }
+        game.current_player = Player::Red;
+        
+        assert_ne!(game.get_ai_move(), Some(7));
+        assert_eq!(game.make_move_code_internal(7), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Yellow));
+    }
+    
+    #[test]
+    fn test_undo_after_loading_from_boards() {
+        let mut source = Connect4Game::new();
+        for col in [3, 3, 4] {
+            source.make_move(col).unwrap();
+        }
+        let mut yellow = crate::data::BitPackedBoard::<6, 7, 2>::new();
+        let mut red = crate::data::BitPackedBoard::<6, 7, 2>::new();
+        for row in 0..6 {
+            for col in 0..7 {
+                match source.get_cell(row, col) {
+                    1 => yellow.set_cell(row, col, 1).unwrap(),
+                    2 => red.set_cell(row, col, 1).unwrap(),
+                    _ => {}
+                }
+            }
+        }
+        
+        let mut game = Connect4Game::from_boards(yellow, red, Player::Red);
+        assert!(!game.can_undo()); // Loaded discs have no history
+        
+        game.make_move(0).unwrap();
+        game.make_move(6).unwrap();
+        assert!(game.undo_move() && game.undo_move());
+        assert_eq!(game.get_board(), source.get_board());
+        assert_eq!(game.current_player(), Player::Red);
+        assert!(!game.undo_move());
+    }
+}
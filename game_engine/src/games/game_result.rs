@@ -0,0 +1,226 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::Player;
+
+/// Why a game ended
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TerminationReason {
+    LineCompleted = 0, // A player completed a line (the loser under misère rules)
+    Blockade = 1,      // L-Game: the player to move has no legal L-piece move
+    BoardFull = 2,     // No empty cell left without a winner
+    DeadPosition = 3,  // No winning line is open for either player
+    Repetition = 4,    // Position repeated (PopOut)
+    Resignation = 5,
+    Timeout = 6,
+    AgreedDraw = 7,
+    ClaimedDraw = 8,
+    IllegalMove = 9,   // Forfeit after an illegal move
+}
+
+impl TerminationReason {
+    /// All reasons in code order
+    pub const ALL: [TerminationReason; 10] = [
+        TerminationReason::LineCompleted,
+        TerminationReason::Blockade,
+        TerminationReason::BoardFull,
+        TerminationReason::DeadPosition,
+        TerminationReason::Repetition,
+        TerminationReason::Resignation,
+        TerminationReason::Timeout,
+        TerminationReason::AgreedDraw,
+        TerminationReason::ClaimedDraw,
+        TerminationReason::IllegalMove,
+    ];
+    
+    /// Stable name used in game records
+    pub fn record_name(self) -> &'static str {
+        match self {
+            TerminationReason::LineCompleted => "line",
+            TerminationReason::Blockade => "blockade",
+            TerminationReason::BoardFull => "board_full",
+            TerminationReason::DeadPosition => "dead_position",
+            TerminationReason::Repetition => "repetition",
+            TerminationReason::Resignation => "resignation",
+            TerminationReason::Timeout => "timeout",
+            TerminationReason::AgreedDraw => "agreed_draw",
+            TerminationReason::ClaimedDraw => "claimed_draw",
+            TerminationReason::IllegalMove => "illegal_move",
+        }
+    }
+    
+    pub fn from_record_name(name: &str) -> Option<TerminationReason> {
+        Self::ALL.into_iter().find(|reason| reason.record_name() == name)
+    }
+}
+
+/// Final outcome of a game: winner (None for a draw), why it ended and the last move played
+/// The last move uses each game's move encoding (Connect4 move code, (row, col), L-Game (row, col, orientation))
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameResult {
+    winner: Option<Player>,
+    reason: TerminationReason,
+    final_move: Vec<usize>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameResult {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn winner(&self) -> Option<Player> { self.winner }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn reason(&self) -> TerminationReason { self.reason }
+    
+    /// Last move played before the game ended (empty if no move was played)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn final_move(&self) -> Vec<usize> { self.final_move.clone() }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_draw(&self) -> bool {
+        self.winner.is_none()
+    }
+    
+    /// Serialize for game records as `winner|reason|move`, e.g. `Yellow|line|3` or `-|agreed_draw|7,7`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn to_record(&self) -> String {
+        let winner = self.winner.map_or("-".to_string(), |player| format!("{:?}", player));
+        let final_move: Vec<String> = self.final_move.iter().map(|value| value.to_string()).collect();
+        format!("{}|{}|{}", winner, self.reason.record_name(), final_move.join(","))
+    }
+    
+    /// Parse a result written by `to_record` (None if malformed)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn from_record(record: &str) -> Option<GameResult> {
+        let mut fields = record.trim().split('|');
+        let (winner, reason, final_move) = (fields.next()?, fields.next()?, fields.next()?);
+        if fields.next().is_some() {
+            return None;
+        }
+        
+        let winner = match winner {
+            "-" => None,
+            "Yellow" => Some(Player::Yellow),
+            "Red" => Some(Player::Red),
+            "Black" => Some(Player::Black),
+            "White" => Some(Player::White),
+            _ => return None,
+        };
+        let final_move = if final_move.is_empty() {
+            Vec::new()
+        } else {
+            final_move.split(',').map(|value| value.parse().ok()).collect::<Option<Vec<usize>>>()?
+        };
+        
+        Some(GameResult { winner, reason: TerminationReason::from_record_name(reason)?, final_move })
+    }
+    
+    pub fn summary(&self) -> String {
+        match self.winner {
+            Some(player) => format!("{:?} wins ({:?})", player, self.reason),
+            None => format!("Draw ({:?})", self.reason),
+        }
+    }
+}
+
+impl GameResult {
+    pub fn new(winner: Option<Player>, reason: TerminationReason, final_move: Vec<usize>) -> Self {
+        Self { winner, reason, final_move }
+    }
+}
+
+/// Endings decided off the board (resignation, draw agreement, claims, forfeits) and pending draw offers
+/// Shared by the games so `resign`, `offer_draw` and `accept_draw` behave the same everywhere
+#[derive(Clone, Debug, Default)]
+pub struct Adjudication {
+    ending: Option<(Option<Player>, TerminationReason)>,
+    draw_offer: Option<Player>,
+}
+
+impl Adjudication {
+    /// Winner and reason if the game was adjudicated
+    pub fn ending(&self) -> Option<(Option<Player>, TerminationReason)> {
+        self.ending
+    }
+    
+    /// True if the game was ended as a draw off the board
+    pub fn is_draw(&self) -> bool {
+        matches!(self.ending, Some((None, _)))
+    }
+    
+    /// End the game; the caller must check that it is still running
+    pub fn end(&mut self, winner: Option<Player>, reason: TerminationReason) {
+        self.ending = Some((winner, reason));
+        self.draw_offer = None;
+    }
+    
+    /// Player with a pending draw offer
+    pub fn draw_offer(&self) -> Option<Player> {
+        self.draw_offer
+    }
+    
+    pub fn offer_draw(&mut self, player: Player) {
+        self.draw_offer = Some(player);
+    }
+    
+    /// Accept the opponent's pending offer; returns false if there is none
+    pub fn accept_draw(&mut self, player: Player) -> bool {
+        if self.draw_offer != Some(player.opponent()) {
+            return false;
+        }
+        self.end(None, TerminationReason::AgreedDraw);
+        true
+    }
+    
+    /// A move by the offered-to player declines a pending offer
+    pub fn on_move(&mut self, mover: Player) {
+        if self.draw_offer == Some(mover.opponent()) {
+            self.draw_offer = None;
+        }
+    }
+    
+    pub fn clear(&mut self) {
+        self.ending = None;
+        self.draw_offer = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_record_round_trip() {
+        let result = GameResult::new(Some(Player::Yellow), TerminationReason::LineCompleted, vec![3]);
+        assert_eq!(result.to_record(), "Yellow|line|3");
+        assert_eq!(GameResult::from_record("Yellow|line|3"), Some(result));
+        
+        let draw = GameResult::new(None, TerminationReason::AgreedDraw, vec![7, 7]);
+        assert_eq!(GameResult::from_record(&draw.to_record()), Some(draw));
+        
+        let resigned = GameResult::new(Some(Player::White), TerminationReason::Resignation, Vec::new());
+        assert_eq!(GameResult::from_record(&resigned.to_record()), Some(resigned));
+        
+        assert_eq!(GameResult::from_record("Green|line|3"), None);
+        assert_eq!(GameResult::from_record("Red|stalemate|"), None);
+        assert_eq!(GameResult::from_record("Red|line|x"), None);
+    }
+    
+    #[test]
+    fn test_draw_offers() {
+        let mut adjudication = Adjudication::default();
+        assert!(!adjudication.accept_draw(Player::Red));
+        
+        adjudication.offer_draw(Player::Yellow);
+        assert!(!adjudication.accept_draw(Player::Yellow)); // Can't accept your own offer
+        adjudication.on_move(Player::Yellow);
+        assert_eq!(adjudication.draw_offer(), Some(Player::Yellow));
+        adjudication.on_move(Player::Red); // Declined by playing on
+        assert_eq!(adjudication.draw_offer(), None);
+        
+        adjudication.offer_draw(Player::Red);
+        assert!(adjudication.accept_draw(Player::Yellow));
+        assert!(adjudication.is_draw());
+        assert_eq!(adjudication.ending(), Some((None, TerminationReason::AgreedDraw)));
+    }
+}
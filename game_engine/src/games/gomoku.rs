@@ -1,20 +1,128 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use crate::data::BitPackedBoard;
 use crate::geometry::{GomokuGrid, BoardGeometry, PatternProvider};
 use crate::ai::GomokuAI;
-use crate::{GamePhase, Player};
+use crate::games::game_result::{Adjudication, GameResult, TerminationReason};
+use crate::games::clock::{AttachedClock, GameClock};
+use crate::games::gomoku_threats::{is_renju_forbidden, line_lengths_through, threats_after_move, GomokuThreat, ThreatDetector, ThreatKind};
+use crate::{GameError, GamePhase, Player, WinningLine};
 
 /// A struct to represent an AI move for wasm-bindgen.
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug)]
 pub struct AiMove {
     pub row: usize,
     pub col: usize,
 }
 
+/// Threat counts of one player, as shown in the Gomoku hint panel
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GomokuThreatCounts {
+    pub fives: usize,
+    pub open_fours: usize,
+    pub fours: usize,
+    pub open_threes: usize,
+    pub split_threes: usize,
+}
+
+impl GomokuThreatCounts {
+    fn from_threats(threats: &[GomokuThreat]) -> Self {
+        let mut counts = Self::default();
+        for threat in threats {
+            match threat.kind() {
+                ThreatKind::Five => counts.fives += 1,
+                ThreatKind::OpenFour => counts.open_fours += 1,
+                ThreatKind::Four => counts.fours += 1,
+                ThreatKind::OpenThree => counts.open_threes += 1,
+                ThreatKind::SplitThree => counts.split_threes += 1,
+                ThreatKind::DoubleThree => {} // Only reported for hypothetical moves
+            }
+        }
+        counts
+    }
+}
+
+/// Structured Gomoku position analysis (counterpart of Connect4's PositionAnalysis)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug)]
+pub struct GomokuAnalysis {
+    current_player: Player,
+    black: GomokuThreatCounts,
+    white: GomokuThreatCounts,
+    critical_cells: Vec<(usize, usize)>,
+    forbidden_points: Vec<(usize, usize)>,
+    evaluation_score: i32,
+    game_phase: GamePhase,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GomokuAnalysis {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn current_player(&self) -> Player { self.current_player }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn black(&self) -> GomokuThreatCounts { self.black }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn white(&self) -> GomokuThreatCounts { self.white }
+    
+    /// Empty cells completing a five or an open four for either player, flattened as (row, col) pairs
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn critical_cells(&self) -> Vec<usize> {
+        self.critical_cells.iter().flat_map(|&(row, col)| [row, col]).collect()
+    }
+    
+    /// Cells Black may not play under Renju rules, flattened as (row, col) pairs (empty without Renju)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn forbidden_points(&self) -> Vec<usize> {
+        self.forbidden_points.iter().flat_map(|&(row, col)| [row, col]).collect()
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn evaluation_score(&self) -> i32 { self.evaluation_score }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn game_phase(&self) -> GamePhase { self.game_phase }
+    
+    /// Threat counts for a specific player
+    pub fn counts_for(&self, player: Player) -> GomokuThreatCounts {
+        match player {
+            Player::White => self.white,
+            _ => self.black,
+        }
+    }
+    
+    /// Get analysis summary as string for debugging
+    pub fn summary(&self) -> String {
+        format!(
+            "Phase: {:?}, Score: {}, Black: {:?}, White: {:?}, Critical: {}, Forbidden: {}",
+            self.game_phase,
+            self.evaluation_score,
+            self.black,
+            self.white,
+            self.critical_cells.len(),
+            self.forbidden_points.len()
+        )
+    }
+}
+
+impl GomokuAnalysis {
+    /// Critical cells as (row, col)
+    pub fn critical_positions(&self) -> &[(usize, usize)] {
+        &self.critical_cells
+    }
+    
+    /// Renju forbidden points as (row, col)
+    pub fn forbidden_positions(&self) -> &[(usize, usize)] {
+        &self.forbidden_points
+    }
+}
+
 /// Gomoku/Gobang game implementation using the Three-Layer Architecture
 /// Composes geometry and data layers for clean separation of concerns
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct GomokuGame {
     // Composition: Geometry layer handles coordinate logic
@@ -32,11 +140,18 @@ pub struct GomokuGame {
     winner: Option<Player>,
     move_count: usize,
     move_history: Vec<(usize, usize)>, // Store move positions for undo functionality
+    lines_closed: bool, // No five-cell line is open for either player; refreshed whenever the stones change
+    misere: bool, // Completing five loses instead of wins
+    renju: bool, // Black may not play overlines, double fours or double threes
+    exact_five: bool, // Only exactly five in a row wins; overlines do not count
+    claimable_draw: bool, // A draw may be claimed once no side can complete five in time
+    adjudication: Adjudication, // Resignations, draw offers and claimed draws
+    clock: AttachedClock, // Optional time control; flags are checked on every move
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl GomokuGame {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         Self::new_with_starting_player(Player::Black)
     }
@@ -53,60 +168,125 @@ impl GomokuGame {
             winner: None,
             move_count: 0,
             move_history: Vec::new(),
+            lines_closed: false,
+            misere: false,
+            renju: false,
+            exact_five: false,
+            claimable_draw: false,
+            adjudication: Adjudication::default(),
+            clock: AttachedClock::default(),
         }
     }
     
     /// Make a move at the specified position (row, col)
     /// Gomoku allows free placement anywhere on the board
-    #[wasm_bindgen]
-    pub fn make_move(&mut self, row: usize, col: usize) -> Result<bool, JsValue> {
-        // Validate coordinates
-        if row >= 15 || col >= 15 {
-            return Err(JsValue::from_str("Invalid coordinates"));
-        }
-        
-        // Check if position is already occupied
-        if self.get_cell(row, col) != 0 {
-            return Err(JsValue::from_str("Position already occupied"));
-        }
-        
-        // Check if game is over
-        if self.winner.is_some() {
-            return Err(JsValue::from_str("Game is already over"));
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn make_move(&mut self, row: usize, col: usize) -> Result<bool, GameError> {
+        self.make_move_internal(row, col)
+    }
+    
+    /// Enable or disable misère rules (completing five in a row loses instead of wins)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_misere_enabled(&mut self, enabled: bool) {
+        self.misere = enabled;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_misere_enabled(&self) -> bool {
+        self.misere
+    }
+    
+    /// Enable or disable Renju restrictions (Black may not play overlines, double fours or double threes)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_renju_enabled(&mut self, enabled: bool) {
+        self.renju = enabled;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_renju_enabled(&self) -> bool {
+        self.renju
+    }
+    
+    /// Enable or disable the exact-five rule (six or more in a row does not win)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_exact_five_enabled(&mut self, enabled: bool) {
+        self.exact_five = enabled;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_exact_five_enabled(&self) -> bool {
+        self.exact_five
+    }
+    
+    /// Use this AI (e.g. one with a loaded personality) for hints and AI moves; kept across resets
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_ai(&mut self, ai: &GomokuAI) {
+        self.ai = ai.clone();
+    }
+    
+    /// Name of the AI's personality
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_ai_profile_name(&self) -> String {
+        self.ai.get_profile_name()
+    }
+    
+    /// Check if a stone of `player` at the empty cell (row, col) would win under the current line rules
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn completes_five(&self, row: usize, col: usize, player: Player) -> bool {
+        if row >= 15 || col >= 15 || self.get_cell(row, col) != 0 {
+            return false;
         }
-        
-        // Use geometry layer to get the index
-        let index = self.geometry.to_index((row as i32, col as i32))
-            .ok_or_else(|| JsValue::from_str("Invalid position"))?;
-        
-        // Use data layer to place the stone
-        let player_board = self.get_current_player_board_mut();
-        player_board.set_bit(index, true);
-        
-        // Update move history
-        self.move_history.push((row, col));
-        self.move_count += 1;
-        
-        // Check for win condition (5 in a row)
-        let won = self.check_win_condition();
-        
-        if !won {
-            // Switch players
-            self.current_player = self.current_player.opponent();
+        let lengths = line_lengths_through(self.get_player_board(player), row, col);
+        if self.exact_five {
+            lengths.contains(&5)
+        } else {
+            lengths.iter().any(|&length| length >= 5)
         }
-        
-        Ok(won)
     }
     
-    /// Check if the current player has won (5 stones in a row)
+    /// Check if (row, col) is a forbidden point for Black (always false without Renju)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_forbidden_point(&self, row: usize, col: usize) -> bool {
+        self.renju && row < 15 && col < 15
+            && is_renju_forbidden(&self.geometry, &self.black_board, &self.white_board, row, col)
+    }
+    
+    /// All forbidden points for Black under Renju rules
+    /// Each pair of consecutive elements represents (row, col)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_forbidden_points(&self) -> Vec<usize> {
+        self.forbidden_positions().into_iter().flat_map(|(row, col)| [row, col]).collect()
+    }
+    
+    /// Check if the current player completed 5 stones in a row (a win, or a loss under misère rules)
     fn check_win_condition(&mut self) -> bool {
+        // Exact five: only a line through the last stone of exactly five counts
+        if self.exact_five {
+            let Some(&(row, col)) = self.move_history.last() else {
+                return false;
+            };
+            if !line_lengths_through(self.get_current_player_board(), row, col).contains(&5) {
+                return false;
+            }
+            self.winner = Some(if self.misere {
+                self.current_player.opponent()
+            } else {
+                self.current_player
+            });
+            return true;
+        }
+        
         let player_board = self.get_current_player_board();
         let winning_lines = self.geometry.get_winning_lines(5);
         
         // Check each winning line pattern
         for line_mask in winning_lines {
             if player_board.count_set_bits_in_mask(line_mask) >= 5 {
-                self.winner = Some(self.current_player);
+                self.winner = Some(if self.misere {
+                    self.current_player.opponent()
+                } else {
+                    self.current_player
+                });
                 return true;
             }
         }
@@ -114,6 +294,37 @@ impl GomokuGame {
         false
     }
     
+    /// Cells of every completed five, flattened as (row, col) pairs (empty while the game is running)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_winning_cells(&self) -> Vec<usize> {
+        WinningLine::flatten_cells(&self.get_completed_lines())
+    }
+    
+    /// Every completed line on the board (one move may complete several fives)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_completed_lines(&self) -> Vec<WinningLine> {
+        [Player::Black, Player::White]
+            .into_iter()
+            .flat_map(|player| {
+                let player_board = self.get_player_board(player);
+                let windows = self.geometry
+                    .get_winning_lines(5)
+                    .iter()
+                    .filter(|line_mask| player_board.count_set_bits_in_mask(line_mask) >= 5)
+                    .map(|line_mask| {
+                        (0..225)
+                            .filter(|&index| line_mask.get_bit(index))
+                            .map(|index| (index / 15, index % 15))
+                            .collect()
+                    })
+                    .collect();
+                WinningLine::from_windows(player, windows)
+            })
+            // Overlines merge into one longer line, which does not count under exact five
+            .filter(|line| !self.exact_five || line.length() == 5)
+            .collect()
+    }
+    
     /// Get the board state for a specific player
     fn get_player_board(&self, player: Player) -> &BitPackedBoard<15, 15, 2> {
         match player {
@@ -143,7 +354,7 @@ impl GomokuGame {
     }
     
     /// Get cell value at position (0 = empty, 1 = black, 2 = white)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_cell(&self, row: usize, col: usize) -> u8 {
         if row >= 15 || col >= 15 {
             return 0;
@@ -162,31 +373,32 @@ impl GomokuGame {
     }
     
     /// Get current player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn current_player(&self) -> Player {
         self.current_player
     }
     
     /// Get winner (if any)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn winner(&self) -> Option<Player> {
         self.winner
     }
     
     /// Get move count
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn move_count(&self) -> usize {
         self.move_count
     }
     
     /// Check if position is valid for next move
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn is_valid_move(&self, row: usize, col: usize) -> bool {
-        row < 15 && col < 15 && self.get_cell(row, col) == 0 && self.winner.is_none()
+        row < 15 && col < 15 && self.get_cell(row, col) == 0 && !self.is_game_over()
+            && !self.is_forbidden_for_current_player(row, col)
     }
     
     /// Reset game to initial state
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn reset(&mut self) {
         self.reset_with_starting_player(Player::Black);
     }
@@ -195,11 +407,15 @@ impl GomokuGame {
     pub fn reset_with_starting_player(&mut self, starting_player: Player) {
         self.black_board.clear();
         self.white_board.clear();
-        self.ai = GomokuAI::new();
         self.current_player = starting_player;
         self.winner = None;
+        self.adjudication.clear();
+        if let Some(clock) = self.clock.get_mut() {
+            clock.restart(starting_player);
+        }
         self.move_count = 0;
         self.move_history.clear();
+        self.lines_closed = false;
     }
     
     /// Start a new game series with "loser starts" rule (legacy method)
@@ -218,7 +434,7 @@ impl GomokuGame {
     /// Start a new game series with fixed player colors
     /// Players keep their colors throughout the series, only start order changes
     /// This is ideal for tournaments where Player A = always Black, Player B = always White
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn start_new_series_with_players(&mut self, player_a: Player, player_b: Player, winner: Player) {
         // Validate that we're using Gomoku players
         let (color_a, color_b) = match (player_a, player_b) {
@@ -235,7 +451,7 @@ impl GomokuGame {
     }
     
     /// Get board state as string for debugging
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn board_string(&self) -> String {
         let mut result = String::new();
         
@@ -256,20 +472,159 @@ impl GomokuGame {
         result
     }
     
-    /// Check if game is draw (board full, no winner)
-    #[wasm_bindgen]
+    /// Check if game is draw (board full, dead position, claimed or agreed draw, no winner)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn is_draw(&self) -> bool {
-        self.winner.is_none() && self.move_count >= 225 // 15x15 = 225
+        self.winner.is_none() && (self.move_count >= 225 || self.adjudication.is_draw() || self.is_dead_position()) // 15x15 = 225
+    }
+    
+    /// True if no five-cell line is open (free of opponent stones) for either player
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_dead_position(&self) -> bool {
+        self.winner.is_none() && self.lines_closed
+    }
+    
+    /// Resign the game: the opponent wins; returns false if the game is over or the player is not Black/White
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn resign(&mut self, player: Player) -> bool {
+        if self.is_game_over() || !matches!(player, Player::Black | Player::White) {
+            return false;
+        }
+        self.winner = Some(player.opponent());
+        self.adjudication.end(self.winner, TerminationReason::Resignation);
+        true
+    }
+    
+    /// Offer a draw; it stands until the opponent accepts it or declines by moving
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn offer_draw(&mut self, player: Player) -> bool {
+        if self.is_game_over() || !matches!(player, Player::Black | Player::White) {
+            return false;
+        }
+        self.adjudication.offer_draw(player);
+        true
+    }
+    
+    /// Accept the opponent's pending draw offer; returns false if there is none
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn accept_draw(&mut self, player: Player) -> bool {
+        !self.is_game_over() && self.adjudication.accept_draw(player)
+    }
+    
+    /// Player with a pending draw offer (if any)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_draw_offer(&self) -> Option<Player> {
+        self.adjudication.draw_offer()
+    }
+    
+    /// Attach a clock; it starts running for the player to move
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn attach_clock(&mut self, mut clock: GameClock) {
+        clock.start(self.current_player);
+        self.clock.set(clock);
+    }
+    
+    /// Remove the clock (games without a clock are untimed)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn detach_clock(&mut self) -> Option<GameClock> {
+        self.clock.take()
+    }
+    
+    /// Snapshot of the attached clock for display
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_clock(&self) -> Option<GameClock> {
+        self.clock.get().cloned()
+    }
+    
+    /// Check whether the player to move ran out of time; if so they lose on time
+    /// Returns true if the game was lost on time
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn check_clock(&mut self) -> bool {
+        if self.is_game_over() {
+            return false;
+        }
+        let Some(clock) = self.clock.get_mut() else {
+            return false;
+        };
+        if !clock.check_flag() || !clock.is_flagged(self.current_player) {
+            return false;
+        }
+        self.winner = Some(self.current_player.opponent());
+        self.adjudication.end(self.winner, TerminationReason::Timeout);
+        true
+    }
+    
+    /// True if the player to move ran out of time (nothing is recorded)
+    fn is_out_of_time(&self) -> bool {
+        self.clock.get().is_some_and(|clock| clock.is_out_of_time(self.current_player))
+    }
+    
+    /// Final result with termination reason (None while the game is running)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_result(&self) -> Option<GameResult> {
+        let final_move = self.move_history.last().map(|&(row, col)| vec![row, col]).unwrap_or_default();
+        let (winner, reason) = if let Some(ending) = self.adjudication.ending() {
+            ending
+        } else if self.winner.is_some() {
+            (self.winner, TerminationReason::LineCompleted)
+        } else if !self.is_draw() {
+            return None;
+        } else if self.move_count >= 225 {
+            (None, TerminationReason::BoardFull)
+        } else {
+            (None, TerminationReason::DeadPosition)
+        };
+        Some(GameResult::new(winner, reason, final_move))
+    }
+    
+    /// Enable or disable claimable draws (see `can_claim_draw`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_claimable_draw_enabled(&mut self, enabled: bool) {
+        self.claimable_draw = enabled;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_claimable_draw_enabled(&self) -> bool {
+        self.claimable_draw
+    }
+    
+    /// Check if the current player may claim a draw (claimable draws enabled)
+    /// Claimable as soon as neither player has enough moves left to fill any open line
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn can_claim_draw(&self) -> bool {
+        if !self.claimable_draw || self.is_game_over() {
+            return false;
+        }
+        
+        let empty_cells = 225 - self.move_count;
+        [self.current_player, self.current_player.opponent()]
+            .into_iter()
+            .enumerate()
+            .all(|(turn, player)| {
+                // The player to move gets the odd empty cell
+                let moves_left = (empty_cells + 1 - turn) / 2;
+                self.min_stones_missing(player).is_none_or(|missing| missing > moves_left)
+            })
+    }
+    
+    /// Claim a draw; returns false if the position does not allow it
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn claim_draw(&mut self) -> bool {
+        if !self.can_claim_draw() {
+            return false;
+        }
+        self.adjudication.end(None, TerminationReason::ClaimedDraw);
+        true
     }
     
     /// Check if game is over (win or draw)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn is_game_over(&self) -> bool {
         self.winner.is_some() || self.is_draw()
     }
     
     /// Get current game phase for AI strategy
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_game_phase(&self) -> GamePhase {
         match self.move_count {
             0..=20 => GamePhase::Opening,
@@ -281,7 +636,7 @@ impl GomokuGame {
     // === FRONTEND COMPATIBILITY API ===
     
     /// Get memory usage of the game state (for performance monitoring)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn memory_usage(&self) -> usize {
         // Calculate approximate memory usage
         let bitpacked_boards = std::mem::size_of::<BitPackedBoard<15, 15, 2>>() * 2; // black + white
@@ -292,25 +647,25 @@ impl GomokuGame {
     }
     
     /// Get current player (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_current_player(&self) -> Player {
         self.current_player()
     }
     
     /// Get move count (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_move_count(&self) -> usize {
         self.move_count()
     }
     
     /// Get winner (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_winner(&self) -> Option<Player> {
         self.winner()
     }
     
     /// Get board state as flat array for frontend (15 rows × 15 cols = 225 elements)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_board(&self) -> Vec<u8> {
         let mut board = vec![0u8; 225];
         for row in 0..15 {
@@ -322,15 +677,16 @@ impl GomokuGame {
     }
     
     /// Check if undo is possible
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn can_undo(&self) -> bool {
         self.move_count > 0 && !self.is_game_over()
     }
     
     /// Undo the last move
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn undo_move(&mut self) -> bool {
-        if !self.can_undo() {
+        // A player whose flag fell has lost and can't take a move back
+        if !self.can_undo() || self.check_clock() {
             return false;
         }
         
@@ -350,6 +706,10 @@ impl GomokuGame {
                 self.move_count -= 1;
                 self.current_player = self.current_player.opponent();
                 self.winner = None; // Reset winner since we undid a move
+                self.refresh_lines_closed();
+                if let Some(clock) = self.clock.get_mut() {
+                    clock.take_back(self.current_player);
+                }
                 
                 return true;
             }
@@ -359,20 +719,20 @@ impl GomokuGame {
     }
     
     /// Frontend-friendly method aliases
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn newGame(&mut self) {
         self.reset();
     }
     
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn undoMove(&mut self) -> bool {
         self.undo_move()
     }
     
     /// Get AI move suggestion (modern API with Option return type)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_ai_move(&self) -> Vec<usize> {
-        if let Some((row, col)) = self.ai.get_best_move(self) {
+        if let Some((row, col)) = self.clocked_ai(self.current_player).get_best_move(self) {
             vec![row, col]
         } else {
             vec![] // Return empty vector if no move found
@@ -381,12 +741,12 @@ impl GomokuGame {
     
     /// Get AI move suggestion (internal API with proper Option type)
     pub fn get_ai_move_option(&self) -> Option<AiMove> {
-        self.ai.get_best_move(self).map(|(row, col)| AiMove { row, col })
+        self.clocked_ai(self.current_player).get_best_move(self).map(|(row, col)| AiMove { row, col })
     }
     
     /// Get AI move suggestion for specific player
     pub fn get_ai_move_for_player(&self, player: Player) -> Vec<usize> {
-        if let Some((row, col)) = self.ai.get_best_move_for_player(self, player) {
+        if let Some((row, col)) = self.clocked_ai(player).get_best_move_for_player(self, player) {
             vec![row, col]
         } else {
             vec![]
@@ -394,7 +754,7 @@ impl GomokuGame {
     }
     
     /// Evaluate position for current player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn evaluate_position(&self) -> i32 {
         self.ai.evaluate_position(self, self.current_player)
     }
@@ -405,13 +765,67 @@ impl GomokuGame {
     }
     
     /// Get threat level for a position and player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_threat_level(&self, row: usize, col: usize, player: Player) -> u8 {
         self.ai.get_threat_level(self, player, row, col)
     }
     
+    /// All fives, fours and threes a player currently has on the board
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_threats(&self, player: Player) -> Vec<GomokuThreat> {
+        ThreatDetector::new(&self.geometry, self.get_player_board(player), self.get_player_board(player.opponent()), player)
+            .detect_all()
+    }
+    
+    /// Threats a player would create by placing a stone at (row, col)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_threats_after_move(&self, row: usize, col: usize, player: Player) -> Vec<GomokuThreat> {
+        if row >= 15 || col >= 15 {
+            return Vec::new();
+        }
+        threats_after_move(&self.geometry, self.get_player_board(player), self.get_player_board(player.opponent()), player, row, col)
+    }
+    
+    /// Positions where placing a stone creates an open or split three
+    /// Each pair of consecutive elements represents (row, col)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn detect_open_three(&self, player: Player) -> Vec<usize> {
+        self.moves_creating(player, |threat| threat.is_three())
+    }
+    
+    /// Positions where placing a stone creates a four with a single completion point
+    /// Each pair of consecutive elements represents (row, col)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn detect_closed_four(&self, player: Player) -> Vec<usize> {
+        self.moves_creating(player, |threat| threat.kind() == ThreatKind::Four)
+    }
+    
+    /// Positions where placing a stone creates two open threes at once
+    /// Each pair of consecutive elements represents (row, col)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn detect_double_three_forks(&self, player: Player) -> Vec<usize> {
+        self.moves_creating(player, |threat| threat.kind() == ThreatKind::DoubleThree)
+    }
+    
+    /// Positions the opponent would like to play (threat level 3 or higher for them)
+    /// Each pair of consecutive elements represents (row, col)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_dangerous_moves(&self) -> Vec<usize> {
+        let opponent = self.current_player.opponent();
+        let mut moves = Vec::new();
+        for row in 0..15 {
+            for col in 0..15 {
+                if self.is_valid_move(row, col) && self.ai.get_threat_level(self, opponent, row, col) >= 3 {
+                    moves.push(row);
+                    moves.push(col);
+                }
+            }
+        }
+        moves
+    }
+    
     /// Get winning moves for current player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_winning_moves(&self) -> Vec<usize> {
         let mut moves = Vec::new();
         for row in 0..15 {
@@ -430,7 +844,7 @@ impl GomokuGame {
     }
     
     /// Get blocking moves (prevent opponent from winning)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_blocking_moves(&self) -> Vec<usize> {
         let opponent = self.current_player.opponent();
         let mut moves = Vec::new();
@@ -451,28 +865,33 @@ impl GomokuGame {
     }
     
     /// Analyze position (Connect4-compatible API)
-    #[wasm_bindgen]
-    pub fn analyze_position(&self) -> String {
-        let evaluation = self.ai.evaluate_position(self, self.current_player);
-        let threatening_moves = self.get_threatening_moves();
-        let winning_moves = self.get_winning_moves();
-        let blocking_moves = self.get_blocking_moves();
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn analyze_position(&self) -> GomokuAnalysis {
+        let black_threats = self.get_threats(Player::Black);
+        let white_threats = self.get_threats(Player::White);
         
-        format!(
-            "Position Analysis:\n\
-            Evaluation: {}\n\
-            Threatening moves: {}\n\
-            Winning moves: {}\n\
-            Blocking moves: {}",
-            evaluation,
-            threatening_moves.len() / 2,
-            winning_moves.len() / 2,
-            blocking_moves.len() / 2
-        )
+        // Completion points of fours make five, those of threes make an open four
+        let mut critical_cells: Vec<(usize, usize)> = black_threats
+            .iter()
+            .chain(&white_threats)
+            .flat_map(|threat| threat.completion_positions().iter().copied())
+            .collect();
+        critical_cells.sort_unstable();
+        critical_cells.dedup();
+        
+        GomokuAnalysis {
+            current_player: self.current_player,
+            black: GomokuThreatCounts::from_threats(&black_threats),
+            white: GomokuThreatCounts::from_threats(&white_threats),
+            critical_cells,
+            forbidden_points: self.forbidden_positions(),
+            evaluation_score: self.ai.evaluate_position(self, self.current_player),
+            game_phase: self.get_game_phase(),
+        }
     }
     
     /// Get threatening moves for current player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_threatening_moves(&self) -> Vec<usize> {
         let mut moves = Vec::new();
         for row in 0..15 {
@@ -522,7 +941,7 @@ impl GomokuGame {
             }
         }
         
-        Self {
+        let mut game = Self {
             geometry: GomokuGrid::new(),
             black_board,
             white_board,
@@ -531,37 +950,67 @@ impl GomokuGame {
             winner: None, // Will be determined by check_win_condition if needed
             move_count,
             move_history,
-        }
+            lines_closed: false,
+            misere: false,
+            renju: false,
+            exact_five: false,
+            claimable_draw: false,
+            adjudication: Adjudication::default(),
+            clock: AttachedClock::default(),
+        };
+        game.refresh_lines_closed();
+        game
+    }
+    
+    /// Recompute `lines_closed` after the stones changed (once per move, not on every draw check)
+    fn refresh_lines_closed(&mut self) {
+        self.lines_closed = !self.geometry.get_winning_lines(5).iter().any(|line_mask| {
+            self.black_board.count_set_bits_in_mask(line_mask) == 0
+                || self.white_board.count_set_bits_in_mask(line_mask) == 0
+        });
     }
     
-    /// Make a move for internal/test use (returns Result<bool, String>)
-    pub fn make_move_internal(&mut self, row: usize, col: usize) -> Result<bool, String> {
+    /// Make a move for internal/test use
+    pub fn make_move_internal(&mut self, row: usize, col: usize) -> Result<bool, GameError> {
         // Validate coordinates
         if row >= 15 || col >= 15 {
-            return Err("Invalid coordinates".to_string());
+            return Err(GameError::OutOfBounds);
         }
         
         // Check if position is already occupied
         if self.get_cell(row, col) != 0 {
-            return Err("Position already occupied".to_string());
+            return Err(GameError::PositionOccupied);
         }
         
         // Check if game is over
-        if self.winner.is_some() {
-            return Err("Game is already over".to_string());
+        if self.is_game_over() {
+            return Err(GameError::GameAlreadyOver);
+        }
+        
+        // No move once the flag fell; the game is left as it was and `check_clock` records the loss
+        if self.is_out_of_time() {
+            return Err(GameError::TimeForfeit);
+        }
+        
+        // Check Renju restrictions for Black
+        if self.is_forbidden_for_current_player(row, col) {
+            return Err(GameError::ForbiddenRenjuPoint);
         }
         
         // Use geometry layer to get the index
         let index = self.geometry.to_index((row as i32, col as i32))
-            .ok_or_else(|| "Invalid position".to_string())?;
+            .ok_or(GameError::OutOfBounds)?;
         
         // Use data layer to place the stone
         let player_board = self.get_current_player_board_mut();
         player_board.set_bit(index, true);
+        self.refresh_lines_closed();
         
         // Update move history and count
         self.move_history.push((row, col));
         self.move_count += 1;
+        self.adjudication.on_move(self.current_player);
+        let mover = self.current_player;
         
         // Check for win condition
         let won = self.check_win_condition();
@@ -570,15 +1019,87 @@ impl GomokuGame {
             // Switch players
             self.current_player = self.current_player.opponent();
         }
+        self.press_clock(mover);
         
         Ok(won)
     }
     
+    /// AI searching no deeper than the player's clock budget allows
+    fn clocked_ai(&self, player: Player) -> GomokuAI {
+        let limit = self.clock.get().and_then(|clock| self.ai.depth_limit_for_budget(clock.move_budget_ms(player)));
+        match limit {
+            Some(limit) => self.ai.with_depth_limit(limit),
+            None => self.ai.clone(),
+        }
+    }
+    
+    /// Hit the clock after a move: stop it if the game ended, otherwise start the opponent's time
+    /// The mover still loses on time if the move itself took too long
+    fn press_clock(&mut self, mover: Player) {
+        let game_over = self.is_game_over();
+        let Some(clock) = self.clock.get_mut() else {
+            return;
+        };
+        if game_over {
+            clock.stop();
+        } else if !clock.press(mover) {
+            self.winner = Some(mover.opponent());
+            self.adjudication.end(self.winner, TerminationReason::Timeout);
+        }
+    }
+    
+    /// Fewest stones a player still needs to complete one of their open lines (None if no line is open)
+    fn min_stones_missing(&self, player: Player) -> Option<usize> {
+        let own = self.get_player_board(player);
+        let opponent = self.get_player_board(player.opponent());
+        self.geometry
+            .get_winning_lines(5)
+            .iter()
+            .filter(|line_mask| opponent.count_set_bits_in_mask(line_mask) == 0)
+            .map(|line_mask| 5 - own.count_set_bits_in_mask(line_mask) as usize)
+            .min()
+    }
+    
+    /// Forbidden points for Black as (row, col), empty without Renju
+    fn forbidden_positions(&self) -> Vec<(usize, usize)> {
+        if !self.renju {
+            return Vec::new();
+        }
+        (0..15)
+            .flat_map(|row| (0..15).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.is_forbidden_point(row, col))
+            .collect()
+    }
+    
+    /// Renju restrictions only bind Black
+    fn is_forbidden_for_current_player(&self, row: usize, col: usize) -> bool {
+        self.current_player == Player::Black && self.is_forbidden_point(row, col)
+    }
+    
+    /// Empty positions where a stone of `player` creates a matching threat (flattened pairs)
+    fn moves_creating(&self, player: Player, matches: impl Fn(&GomokuThreat) -> bool) -> Vec<usize> {
+        let mut moves = Vec::new();
+        for row in 0..15 {
+            for col in 0..15 {
+                if self.get_threats_after_move(row, col, player).iter().any(&matches) {
+                    moves.push(row);
+                    moves.push(col);
+                }
+            }
+        }
+        moves
+    }
+    
     /// Get board for player evaluation (internal use)
     pub fn get_board_for_player(&self, player: Player) -> &BitPackedBoard<15, 15, 2> {
         self.get_player_board(player)
     }
     
+    /// Most recent stone as (row, col)
+    pub fn last_move(&self) -> Option<(usize, usize)> {
+        self.move_history.last().copied()
+    }
+    
     /// Get geometry for evaluation (internal use)
     pub fn geometry(&self) -> &GomokuGrid {
         &self.geometry
@@ -599,6 +1120,13 @@ impl GomokuGame {
             winner: self.winner,
             move_count: self.move_count,
             move_history: self.move_history.clone(),
+            lines_closed: self.lines_closed,
+            misere: self.misere,
+            renju: self.renju,
+            exact_five: self.exact_five,
+            claimable_draw: self.claimable_draw,
+            adjudication: self.adjudication.clone(),
+            clock: AttachedClock::default(),
         };
         
         if game_copy.make_move(row, col).is_ok() {
@@ -662,6 +1190,183 @@ mod tests {
         assert_eq!(game.winner(), Some(Player::Black));
     }
     
+    #[test]
+    fn test_misere_five_loses() {
+        let mut game = GomokuGame::new();
+        game.set_misere_enabled(true);
+        
+        for col in 0..4 {
+            assert_eq!(game.make_move_internal(7, col), Ok(false)); // Black
+            assert_eq!(game.make_move_internal(8, col), Ok(false)); // White
+        }
+        assert_eq!(game.make_move_internal(7, 4), Ok(true)); // Black completes five
+        assert_eq!(game.winner(), Some(Player::White));
+        assert!(game.is_game_over());
+        
+        // The rule survives lookahead copies
+        game.reset();
+        assert!(game.make_move_copy(7, 7).unwrap().is_misere_enabled());
+    }
+    
+    #[test]
+    fn test_exact_five_overline() {
+        let mut game = GomokuGame::new();
+        game.set_exact_five_enabled(true);
+    
+        // Black: 0-3 and 5 on row 7, White far away
+        for (black, white) in [(0, 0), (1, 1), (2, 2), (3, 3), (5, 9)] {
+            game.make_move_internal(7, black).unwrap();
+            game.make_move_internal(0, white).unwrap();
+        }
+        assert!(!game.completes_five(7, 4, Player::Black)); // Would make six
+        assert_eq!(game.make_move_internal(7, 4), Ok(false));
+        assert_eq!(game.winner(), None);
+        assert!(game.get_completed_lines().is_empty());
+    
+        // White completes exactly five
+        assert!(game.completes_five(0, 4, Player::White));
+        assert_eq!(game.make_move_internal(0, 4), Ok(true));
+        assert_eq!(game.winner(), Some(Player::White));
+        let lines = game.get_completed_lines();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].player(), Player::White);
+    }
+    
+    #[test]
+    fn test_threat_queries() {
+        let mut game = GomokuGame::new();
+        for (row, col) in [(7, 5), (0, 0), (7, 6), (0, 14), (6, 7), (14, 0), (5, 7), (14, 14)] {
+            game.make_move_internal(row, col).unwrap();
+        }
+        
+        // (7, 7) joins the row and column twos into threes at the same time
+        let forks = game.detect_double_three_forks(Player::Black);
+        assert!(forks.chunks(2).any(|pair| pair == [7, 7]));
+        assert!(game.detect_open_three(Player::Black).chunks(2).any(|pair| pair == [7, 4]));
+        assert!(game.detect_closed_four(Player::Black).is_empty());
+        assert_eq!(game.get_threat_level(7, 7, Player::Black), 4);
+        
+        game.make_move_internal(7, 7).unwrap();
+        let threats = game.get_threats(Player::Black);
+        assert_eq!(threats.iter().filter(|t| t.kind() == ThreatKind::OpenThree).count(), 2);
+        assert_eq!(threats[0].cells(), vec![5, 7, 6, 7, 7, 7]);
+    }
+    
+    #[test]
+    fn test_structured_analysis() {
+        let mut game = GomokuGame::new();
+        for (row, col) in [(7, 5), (0, 0), (7, 6), (0, 14), (7, 7), (14, 0), (5, 7), (14, 14), (6, 7)] {
+            game.make_move_internal(row, col).unwrap();
+        }
+        
+        let analysis = game.analyze_position();
+        assert_eq!(analysis.current_player(), Player::White);
+        assert_eq!(analysis.black().open_threes, 2);
+        assert_eq!(analysis.white(), GomokuThreatCounts::default());
+        assert!(analysis.critical_positions().contains(&(7, 4)));
+        assert!(analysis.critical_positions().contains(&(4, 7)));
+        assert!(analysis.forbidden_positions().is_empty());
+        assert_eq!(analysis.game_phase(), GamePhase::Opening);
+    }
+    
+    #[test]
+    fn test_renju_forbidden_moves() {
+        let mut game = GomokuGame::new();
+        game.set_renju_enabled(true);
+        for (row, col) in [(7, 5), (0, 0), (7, 6), (0, 14), (5, 7), (14, 0), (6, 7), (14, 14)] {
+            game.make_move_internal(row, col).unwrap();
+        }
+        
+        // Black's double three at (7, 7) is forbidden, White may play there
+        assert!(game.is_forbidden_point(7, 7));
+        assert!(!game.is_valid_move(7, 7));
+        assert_eq!(game.make_move_internal(7, 7), Err(GameError::ForbiddenRenjuPoint));
+        assert_eq!(game.get_move_count(), 8); // The refused move is not played
+        assert!(game.get_forbidden_points().chunks(2).any(|pair| pair == [7, 7]));
+        assert!(game.analyze_position().forbidden_positions().contains(&(7, 7)));
+        
+        game.make_move_internal(7, 4).unwrap(); // Black
+        assert!(game.is_valid_move(7, 7));
+        
+        game.set_renju_enabled(false);
+        assert!(game.get_forbidden_points().is_empty());
+    }
+    
+    #[test]
+    fn test_winning_cells_for_double_five() {
+        let mut game = GomokuGame::new();
+        for i in 3..7 {
+            game.make_move_internal(7, i).unwrap(); // Black row
+            game.make_move_internal(0, i).unwrap(); // White
+            game.make_move_internal(i, 7).unwrap(); // Black column
+            game.make_move_internal(14, i).unwrap(); // White
+        }
+        assert!(game.get_winning_cells().is_empty());
+        
+        assert_eq!(game.make_move_internal(7, 7), Ok(true));
+        let lines = game.get_completed_lines();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.player() == Player::Black && line.length() == 5));
+        assert_eq!(game.get_winning_cells().len(), 2 * 9);
+    }
+    
+    #[test]
+    fn test_dead_position_draw() {
+        // Diagonal stripes two cells wide put both colours into every five-cell line
+        let mut black_board = BitPackedBoard::new();
+        let mut white_board = BitPackedBoard::new();
+        for row in 0..15 {
+            for col in 0..15 {
+                if (row, col) == (7, 7) {
+                    continue;
+                }
+                let board = if (row + 2 * col) % 4 < 2 { &mut black_board } else { &mut white_board };
+                board.set_cell(row, col, 1).unwrap();
+            }
+        }
+        
+        let mut game = GomokuGame::from_boards(black_board, white_board, Player::Black);
+        assert_eq!(game.move_count(), 224);
+        assert!(game.is_dead_position());
+        assert!(game.is_draw());
+        assert!(!game.is_valid_move(7, 7));
+        assert!(!game.can_claim_draw());
+        game.reset();
+        assert!(!game.is_dead_position());
+        
+        // An empty board has open lines everywhere
+        let mut game = GomokuGame::new();
+        game.set_claimable_draw_enabled(true);
+        assert!(!game.is_dead_position());
+        assert!(!game.can_claim_draw());
+        assert!(!game.claim_draw());
+    }
+    
+    #[test]
+    fn test_game_results() {
+        let mut game = GomokuGame::new();
+        for col in 0..4 {
+            game.make_move_internal(7, col).unwrap(); // Black
+            game.make_move_internal(8, col).unwrap(); // White
+        }
+        assert_eq!(game.get_result(), None);
+        game.make_move_internal(7, 4).unwrap();
+        assert_eq!(game.get_result().unwrap().to_record(), "Black|line|7,4");
+        
+        game.reset();
+        game.make_move_internal(7, 7).unwrap();
+        assert!(game.resign(Player::White));
+        assert!(game.make_move_internal(0, 0).is_err());
+        let result = game.get_result().unwrap();
+        assert_eq!(result.winner(), Some(Player::Black));
+        assert_eq!(result.reason(), TerminationReason::Resignation);
+        
+        game.reset();
+        assert!(game.offer_draw(Player::Black));
+        assert!(game.accept_draw(Player::White));
+        assert_eq!(game.get_result().unwrap().reason(), TerminationReason::AgreedDraw);
+    }
+    
     #[test]
     fn test_from_boards_constructor() {
         let mut black_board = BitPackedBoard::new();
@@ -770,4 +1475,24 @@ mod tests {
         game2.start_new_series_with_players(Player::Black, Player::White, Player::White);
         assert_eq!(game2.current_player(), Player::Black);
     }
+    
+    #[test]
+    fn test_ai_profile_survives_reset() {
+        let mut ai = GomokuAI::new();
+        ai.load_profile_json(r#"{"base": "child-friendly"}"#).unwrap();
+        
+        let mut game = GomokuGame::new();
+        game.set_ai(&ai);
+        for (row, col) in [(7, 7), (0, 0), (7, 6), (14, 14)] {
+            game.make_move(row, col).unwrap();
+        }
+        assert_eq!(game.get_ai_profile_name(), "child-friendly");
+        
+        // The child-friendly AI barely counts the opponent's open two
+        let defensive = GomokuAI::new().with_params(&crate::ai::EvaluatorParams::profile("defensive").unwrap());
+        assert!(ai.evaluate_position(&game, Player::White) > defensive.evaluate_position(&game, Player::White));
+        
+        game.reset();
+        assert_eq!(game.get_ai_profile_name(), "child-friendly");
+    }
 }
\ No newline at end of file
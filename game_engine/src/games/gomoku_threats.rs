@@ -0,0 +1,376 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use std::collections::BTreeMap;
+use crate::data::BitPackedBoard;
+use crate::geometry::{GomokuGrid, BoardGeometry, PatternProvider};
+use crate::Player;
+
+type GomokuBoard = BitPackedBoard<15, 15, 2>;
+
+/// Kind of a Gomoku threat, strongest first
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ThreatKind {
+    Five = 0,        // XXXXX - the line is complete
+    OpenFour = 1,    // _XXXX_ - two completion points, can't be stopped
+    Four = 2,        // XXXX_ / XX_XX - one completion point
+    DoubleThree = 3, // A single move creating two open threes (fork point)
+    OpenThree = 4,   // _XXX_ - becomes an open four
+    SplitThree = 5,  // _X_XX_ - becomes an open four by filling the gap
+}
+
+/// A threat of one player: the stones forming it and the empty cells that complete it
+/// For fours the completion points make five, for threes they make an open four,
+/// for a double three it is the fork point itself
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GomokuThreat {
+    kind: ThreatKind,
+    player: Player,
+    cells: Vec<(usize, usize)>,
+    completion_points: Vec<(usize, usize)>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GomokuThreat {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn kind(&self) -> ThreatKind {
+        self.kind
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn player(&self) -> Player {
+        self.player
+    }
+    
+    /// Stones forming the threat, flattened as (row, col) pairs
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn cells(&self) -> Vec<usize> {
+        self.cells.iter().flat_map(|&(row, col)| [row, col]).collect()
+    }
+    
+    /// Empty cells completing the threat, flattened as (row, col) pairs
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn completion_points(&self) -> Vec<usize> {
+        self.completion_points.iter().flat_map(|&(row, col)| [row, col]).collect()
+    }
+    
+    pub fn summary(&self) -> String {
+        format!(
+            "{:?} {:?}: stones {:?}, completes at {:?}",
+            self.player, self.kind, self.cells, self.completion_points
+        )
+    }
+}
+
+impl GomokuThreat {
+    /// Stones forming the threat as (row, col)
+    pub fn cell_positions(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+    
+    /// Empty cells completing the threat as (row, col)
+    pub fn completion_positions(&self) -> &[(usize, usize)] {
+        &self.completion_points
+    }
+    
+    /// True for fours and fives (threats that end the game unless answered at once)
+    pub fn is_four_or_better(&self) -> bool {
+        self.kind <= ThreatKind::Four
+    }
+    
+    /// True for open and split threes
+    pub fn is_three(&self) -> bool {
+        matches!(self.kind, ThreatKind::OpenThree | ThreatKind::SplitThree)
+    }
+}
+
+/// Threat detection on Gomoku bitboards using the grid's five-cell line masks
+pub struct ThreatDetector<'a> {
+    geometry: &'a GomokuGrid,
+    own: &'a GomokuBoard,
+    opponent: &'a GomokuBoard,
+    player: Player,
+}
+
+impl<'a> ThreatDetector<'a> {
+    pub fn new(geometry: &'a GomokuGrid, own: &'a GomokuBoard, opponent: &'a GomokuBoard, player: Player) -> Self {
+        Self { geometry, own, opponent, player }
+    }
+    
+    /// All fives, fours and threes of the player on the board
+    pub fn detect_all(&self) -> Vec<GomokuThreat> {
+        self.detect(None)
+    }
+    
+    /// Threats containing the stone at (row, col)
+    pub fn detect_through(&self, row: usize, col: usize) -> Vec<GomokuThreat> {
+        self.detect(self.geometry.to_index((row as i32, col as i32)))
+    }
+    
+    /// Scan all five-cell windows (optionally only those through one cell)
+    fn detect(&self, through: Option<usize>) -> Vec<GomokuThreat> {
+        let mut fives = Vec::new();
+        // Fours and threes are keyed by their stones: neighbouring windows share them
+        let mut fours: BTreeMap<Vec<usize>, Vec<usize>> = BTreeMap::new();
+        let mut threes: BTreeMap<Vec<usize>, Vec<usize>> = BTreeMap::new();
+        
+        for window in self.geometry.get_winning_lines(5) {
+            if through.is_some_and(|index| !window.get_bit(index)) {
+                continue;
+            }
+            if self.opponent.count_set_bits_in_mask(window) > 0 {
+                continue; // Blocked window
+            }
+            let stones = self.own.count_set_bits_in_mask(window);
+            if stones < 3 {
+                continue;
+            }
+            
+            // Window cells in line order (indices grow along every direction)
+            let cells: Vec<usize> = (0..225).filter(|&index| window.get_bit(index)).collect();
+            let (own_cells, empty_cells): (Vec<usize>, Vec<usize>) =
+                cells.iter().partition(|&&index| self.own.get_bit(index));
+            
+            match stones {
+                5 => fives.push(own_cells),
+                4 => fours.entry(own_cells).or_default().extend(empty_cells),
+                _ => {
+                    for &empty in &empty_cells {
+                        if self.makes_open_four(&cells, empty) {
+                            threes.entry(own_cells.clone()).or_default().push(empty);
+                        }
+                    }
+                }
+            }
+        }
+        
+        let mut threats: Vec<GomokuThreat> = fives
+            .into_iter()
+            .map(|stones| self.threat(ThreatKind::Five, &stones, Vec::new()))
+            .collect();
+        for (stones, points) in fours {
+            let kind = if points.len() >= 2 { ThreatKind::OpenFour } else { ThreatKind::Four };
+            threats.push(self.threat(kind, &stones, points));
+        }
+        for (stones, points) in threes {
+            let (first, last) = (self.position(stones[0]), self.position(stones[stones.len() - 1]));
+            let span = first.0.abs_diff(last.0).max(first.1.abs_diff(last.1));
+            let kind = if span == 2 { ThreatKind::OpenThree } else { ThreatKind::SplitThree };
+            threats.push(self.threat(kind, &stones, points));
+        }
+        
+        threats.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.cells.cmp(&b.cells)));
+        threats
+    }
+    
+    /// Check if filling `empty` turns the window's three stones into an open four (_XXXX_)
+    fn makes_open_four(&self, window: &[usize], empty: usize) -> bool {
+        let filled: Vec<usize> = (0..window.len())
+            .filter(|&i| window[i] == empty || self.own.get_bit(window[i]))
+            .collect();
+        let (first, last) = (filled[0], filled[filled.len() - 1]);
+        if last - first != 3 {
+            return false; // Four stones, but not consecutive
+        }
+        
+        // Both cells just outside the run must be empty board cells
+        let (row0, col0) = self.position(window[0]);
+        let (row1, col1) = self.position(window[1]);
+        let (dr, dc) = (row1 as i32 - row0 as i32, col1 as i32 - col0 as i32);
+        let (start_row, start_col) = self.position(window[first]);
+        let (end_row, end_col) = self.position(window[last]);
+        
+        [(start_row as i32 - dr, start_col as i32 - dc), (end_row as i32 + dr, end_col as i32 + dc)]
+            .iter()
+            .all(|&coord| {
+                self.geometry
+                    .to_index(coord)
+                    .is_some_and(|index| !self.own.get_bit(index) && !self.opponent.get_bit(index))
+            })
+    }
+    
+    fn threat(&self, kind: ThreatKind, stones: &[usize], mut points: Vec<usize>) -> GomokuThreat {
+        points.sort_unstable();
+        points.dedup();
+        GomokuThreat {
+            kind,
+            player: self.player,
+            cells: stones.iter().map(|&index| self.position(index)).collect(),
+            completion_points: points.into_iter().map(|index| self.position(index)).collect(),
+        }
+    }
+    
+    fn position(&self, index: usize) -> (usize, usize) {
+        (index / 15, index % 15)
+    }
+}
+
+/// Threats a player would create by placing a stone at (row, col)
+/// Counts each line once, so a double three is two threes through the cell
+pub fn threats_after_move(
+    geometry: &GomokuGrid,
+    own: &GomokuBoard,
+    opponent: &GomokuBoard,
+    player: Player,
+    row: usize,
+    col: usize,
+) -> Vec<GomokuThreat> {
+    let Some(index) = geometry.to_index((row as i32, col as i32)) else {
+        return Vec::new();
+    };
+    if own.get_bit(index) || opponent.get_bit(index) {
+        return Vec::new();
+    }
+    
+    let mut with_stone = own.clone();
+    with_stone.set_bit(index, true);
+    let mut threats = ThreatDetector::new(geometry, &with_stone, opponent, player).detect_through(row, col);
+    
+    // Two or more threes through the cell form a double-three fork
+    let three_stones: Vec<(usize, usize)> = threats
+        .iter()
+        .filter(|threat| threat.is_three())
+        .flat_map(|threat| threat.cells.iter().copied())
+        .collect();
+    if threats.iter().filter(|threat| threat.is_three()).count() >= 2 {
+        let mut cells = three_stones;
+        cells.sort_unstable();
+        cells.dedup();
+        threats.push(GomokuThreat {
+            kind: ThreatKind::DoubleThree,
+            player,
+            cells,
+            completion_points: vec![(row, col)],
+        });
+        threats.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.cells.cmp(&b.cells)));
+    }
+    
+    threats
+}
+
+/// Length of the unbroken line of `own` stones through (row, col) in each direction
+/// (horizontal, vertical, both diagonals), counting a stone placed there
+pub fn line_lengths_through(own: &GomokuBoard, row: usize, col: usize) -> [usize; 4] {
+    let stone_at = |r: i32, c: i32| (0..15).contains(&r) && (0..15).contains(&c) && own.get_bit(r as usize * 15 + c as usize);
+    
+    [(0, 1), (1, 0), (1, 1), (1, -1)].map(|(dr, dc)| {
+        let count_from = |sign: i32| {
+            (1..15).take_while(|&i| stone_at(row as i32 + sign * i * dr, col as i32 + sign * i * dc)).count()
+        };
+        1 + count_from(1) + count_from(-1)
+    })
+}
+
+/// Longest unbroken line of `own` stones through (row, col), counting a stone placed there
+pub fn longest_line_through(own: &GomokuBoard, row: usize, col: usize) -> usize {
+    line_lengths_through(own, row, col).into_iter().max().unwrap_or(1)
+}
+
+/// Renju restriction for Black at an empty cell: overline, double four or double three
+/// An exact five is always allowed; threes are not re-checked for being playable themselves
+pub fn is_renju_forbidden(geometry: &GomokuGrid, black: &GomokuBoard, white: &GomokuBoard, row: usize, col: usize) -> bool {
+    let Some(index) = geometry.to_index((row as i32, col as i32)) else {
+        return false;
+    };
+    if black.get_bit(index) || white.get_bit(index) {
+        return false;
+    }
+    
+    match longest_line_through(black, row, col) {
+        5 => return false,
+        length if length > 5 => return true,
+        _ => {}
+    }
+    
+    let threats = threats_after_move(geometry, black, white, Player::Black, row, col);
+    let fours = threats.iter().filter(|threat| matches!(threat.kind, ThreatKind::OpenFour | ThreatKind::Four)).count();
+    let threes = threats.iter().filter(|threat| threat.is_three()).count();
+    fours >= 2 || threes >= 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn boards(black: &[(usize, usize)], white: &[(usize, usize)]) -> (GomokuBoard, GomokuBoard) {
+        let mut black_board = GomokuBoard::new();
+        let mut white_board = GomokuBoard::new();
+        for &(row, col) in black {
+            black_board.set_bit(row * 15 + col, true);
+        }
+        for &(row, col) in white {
+            white_board.set_bit(row * 15 + col, true);
+        }
+        (black_board, white_board)
+    }
+    
+    #[test]
+    fn test_four_kinds() {
+        let grid = GomokuGrid::new();
+        // Open four in row 7, four blocked by White in row 3, split four in row 10
+        let (black, white) = boards(
+            &[(7, 4), (7, 5), (7, 6), (7, 7), (3, 1), (3, 2), (3, 3), (3, 4), (10, 2), (10, 3), (10, 5), (10, 6)],
+            &[(3, 0)],
+        );
+        let threats = ThreatDetector::new(&grid, &black, &white, Player::Black).detect_all();
+        
+        let open_four = threats.iter().find(|t| t.kind == ThreatKind::OpenFour).unwrap();
+        assert_eq!(open_four.completion_positions(), &[(7, 3), (7, 8)]);
+        
+        let fours: Vec<&GomokuThreat> = threats.iter().filter(|t| t.kind == ThreatKind::Four).collect();
+        assert_eq!(fours.len(), 2);
+        assert_eq!(fours[0].completion_positions(), &[(3, 5)]);
+        assert_eq!(fours[1].completion_positions(), &[(10, 4)]);
+    }
+    
+    #[test]
+    fn test_three_kinds() {
+        let grid = GomokuGrid::new();
+        let (black, white) = boards(&[(7, 5), (7, 6), (7, 7), (2, 2), (3, 3), (5, 5), (12, 0), (12, 1), (12, 2)], &[]);
+        let threats = ThreatDetector::new(&grid, &black, &white, Player::Black).detect_all();
+        
+        let open = threats.iter().find(|t| t.kind == ThreatKind::OpenThree).unwrap();
+        assert_eq!(open.cell_positions(), &[(7, 5), (7, 6), (7, 7)]);
+        assert_eq!(open.completion_positions(), &[(7, 4), (7, 8)]);
+        
+        let split = threats.iter().find(|t| t.kind == ThreatKind::SplitThree).unwrap();
+        assert_eq!(split.cell_positions(), &[(2, 2), (3, 3), (5, 5)]);
+        assert_eq!(split.completion_positions(), &[(4, 4)]);
+        
+        // Three against the edge can only become a closed four
+        assert_eq!(threats.iter().filter(|t| t.is_three()).count(), 2);
+    }
+    
+    #[test]
+    fn test_double_three_after_move() {
+        let grid = GomokuGrid::new();
+        let (black, white) = boards(&[(7, 5), (7, 6), (5, 7), (6, 7)], &[]);
+        let threats = threats_after_move(&grid, &black, &white, Player::Black, 7, 7);
+        
+        assert_eq!(threats[0].kind, ThreatKind::DoubleThree);
+        assert_eq!(threats[0].completion_positions(), &[(7, 7)]);
+        assert_eq!(threats.iter().filter(|t| t.kind == ThreatKind::OpenThree).count(), 2);
+        assert!(threats_after_move(&grid, &black, &white, Player::Black, 7, 5).is_empty());
+    }
+    
+    #[test]
+    fn test_renju_forbidden_points() {
+        let grid = GomokuGrid::new();
+        // Double three at (7, 7), double four at (3, 10), overline at (12, 5)
+        let (black, white) = boards(
+            &[(7, 5), (7, 6), (5, 7), (6, 7), (3, 7), (3, 8), (3, 9), (1, 10), (2, 10), (4, 10),
+              (12, 0), (12, 1), (12, 2), (12, 3), (12, 4), (12, 6)],
+            &[(3, 6)],
+        );
+        assert!(is_renju_forbidden(&grid, &black, &white, 7, 7));
+        assert!(is_renju_forbidden(&grid, &black, &white, 3, 10));
+        assert!(is_renju_forbidden(&grid, &black, &white, 12, 5));
+        assert!(!is_renju_forbidden(&grid, &black, &white, 7, 4));
+        
+        // An exact five beats the restrictions
+        let (black, white) = boards(&[(9, 9), (9, 10), (9, 11), (9, 12), (10, 13), (11, 13), (12, 13)], &[]);
+        assert!(!is_renju_forbidden(&grid, &black, &white, 9, 13));
+        assert_eq!(longest_line_through(&black, 9, 13), 5);
+    }
+}
@@ -1,6 +1,9 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use crate::data::BitPackedBoard;
 use crate::geometry::QuadraticGrid;
+use crate::games::game_result::{Adjudication, GameResult, TerminationReason};
+use crate::games::clock::{AttachedClock, GameClock};
 use crate::{Player, GameError, GamePhase, PositionAnalysis};
 
 /// L-Piece position and orientation
@@ -22,7 +25,7 @@ pub struct LGameMove {
 
 /// L-Game implementation using the Three-Layer Architecture
 /// Composes geometry and data layers for clean separation of concerns
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct LGame {
     // Composition: Geometry layer handles coordinate logic
@@ -45,12 +48,18 @@ pub struct LGame {
     
     // Move history for undo functionality
     move_history: Vec<LGameMove>,
+    
+    // Resignations and draw offers
+    adjudication: Adjudication,
+    
+    // Optional time control; flags are checked on every move
+    clock: AttachedClock,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl LGame {
     /// Create new L-Game with initial setup
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         Self::new_with_starting_player(Player::Yellow)
     }
@@ -70,6 +79,8 @@ impl LGame {
             player1_l_position: None,
             player2_l_position: None,
             move_history: Vec::new(),
+            adjudication: Adjudication::default(),
+            clock: AttachedClock::default(),
         };
         
         game.setup_initial_position();
@@ -105,25 +116,25 @@ impl LGame {
     }
     
     /// Get current player
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn current_player(&self) -> Player {
         self.current_player
     }
     
     /// Get move count
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn move_count(&self) -> u32 {
         self.move_count
     }
     
     /// Check if game is over
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn game_over(&self) -> bool {
         self.game_over
     }
     
     /// Get winner (if any)
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn winner(&self) -> Option<Player> {
         self.winner
     }
@@ -162,6 +173,27 @@ impl LGame {
         board
     }
     
+    /// Get board state as string for debugging (Y/R: L-pieces, N: neutral pieces)
+    pub fn board_string(&self) -> String {
+        let mut result = String::new();
+        
+        for row in 0..4 {
+            for col in 0..4 {
+                let char = match self.get_cell(row, col) {
+                    0 => '.',
+                    1 => 'Y',
+                    2 => 'R',
+                    3 => 'N',
+                    _ => '?',
+                };
+                result.push(char);
+            }
+            result.push('\n');
+        }
+        
+        result
+    }
+    
     /// Check if position is occupied by any piece
     fn is_occupied(&self, row: usize, col: usize) -> bool {
         if row >= 4 || col >= 4 {
@@ -269,24 +301,38 @@ impl LGame {
             return Err(GameError::GameAlreadyOver);
         }
         
+        // No move once the flag fell; the game is left as it was and `check_clock` records the loss
+        if self.is_out_of_time() {
+            return Err(GameError::TimeForfeit);
+        }
+        
         let new_l_pos = LPiecePosition {
             row: l_to_row,
             col: l_to_col,
             orientation: l_to_orientation,
         };
         
-        // Validate L-piece move
-        if !self.can_place_l_piece(new_l_pos, Some(self.current_player)) {
-            return Err(GameError::InvalidMove);
-        }
-        
         // Get coordinates before mutable borrows
         let current_pos = match self.current_player {
             Player::Yellow => self.player1_l_position.unwrap(),
             Player::Red => self.player2_l_position.unwrap(),
-            _ => return Err(GameError::InvalidMove),
+            _ => return Err(GameError::InvalidPlayer),
         };
         
+        // Validate L-piece move
+        if l_to_orientation > 7 {
+            return Err(GameError::InvalidOrientation);
+        }
+        if new_l_pos == current_pos {
+            return Err(GameError::LPieceNotMoved);
+        }
+        if self.get_l_piece_coordinates(new_l_pos).iter().any(|&(row, col)| row >= 4 || col >= 4) {
+            return Err(GameError::OutOfBounds);
+        }
+        if !self.can_place_l_piece(new_l_pos, Some(self.current_player)) {
+            return Err(GameError::PositionOccupied);
+        }
+        
         // Record move in history for undo functionality
         let game_move = LGameMove {
             l_piece_from: current_pos,
@@ -302,7 +348,7 @@ impl LGame {
         let current_board = match self.current_player {
             Player::Yellow => &mut self.player1_board,
             Player::Red => &mut self.player2_board,
-            _ => return Err(GameError::InvalidMove),
+            _ => return Err(GameError::InvalidPlayer),
         };
         
         // Remove old L-piece
@@ -319,17 +365,19 @@ impl LGame {
         match self.current_player {
             Player::Yellow => self.player1_l_position = Some(new_l_pos),
             Player::Red => self.player2_l_position = Some(new_l_pos),
-            _ => return Err(GameError::InvalidMove),
+            _ => return Err(GameError::InvalidPlayer),
         }
         
         // Store move in history
         self.move_history.push(game_move);
+        self.adjudication.on_move(self.current_player);
+        let mover = self.current_player;
         
         // Check for win condition (opponent blocked)
         self.current_player = match self.current_player {
             Player::Yellow => Player::Red,
             Player::Red => Player::Yellow,
-            _ => return Err(GameError::InvalidMove),
+            _ => return Err(GameError::InvalidPlayer),
         };
         
         if self.is_current_player_blocked() {
@@ -337,28 +385,135 @@ impl LGame {
             self.winner = Some(match self.current_player {
                 Player::Yellow => Player::Red, // Opponent wins
                 Player::Red => Player::Yellow,
-                _ => return Err(GameError::InvalidMove),
+                _ => return Err(GameError::InvalidPlayer),
             });
         }
         
         self.move_count += 1;
+        self.press_clock(mover);
         Ok(())
     }
     
+    /// Resign the game: the opponent wins; returns false if the game is over or the player is not Yellow/Red
+    pub fn resign(&mut self, player: Player) -> bool {
+        if self.game_over || !matches!(player, Player::Yellow | Player::Red) {
+            return false;
+        }
+        self.game_over = true;
+        self.winner = Some(player.opponent());
+        self.adjudication.end(self.winner, TerminationReason::Resignation);
+        true
+    }
+    
+    /// Offer a draw; it stands until the opponent accepts it or declines by moving
+    pub fn offer_draw(&mut self, player: Player) -> bool {
+        if self.game_over || !matches!(player, Player::Yellow | Player::Red) {
+            return false;
+        }
+        self.adjudication.offer_draw(player);
+        true
+    }
+    
+    /// Accept the opponent's pending draw offer; returns false if there is none
+    pub fn accept_draw(&mut self, player: Player) -> bool {
+        if self.game_over || !self.adjudication.accept_draw(player) {
+            return false;
+        }
+        self.game_over = true;
+        true
+    }
+    
+    /// Player with a pending draw offer (if any)
+    pub fn get_draw_offer(&self) -> Option<Player> {
+        self.adjudication.draw_offer()
+    }
+    
+    /// Attach a clock; it starts running for the player to move
+    pub fn attach_clock(&mut self, mut clock: GameClock) {
+        clock.start(self.current_player);
+        self.clock.set(clock);
+    }
+    
+    /// Remove the clock (games without a clock are untimed)
+    pub fn detach_clock(&mut self) -> Option<GameClock> {
+        self.clock.take()
+    }
+    
+    /// Snapshot of the attached clock for display
+    pub fn get_clock(&self) -> Option<GameClock> {
+        self.clock.get().cloned()
+    }
+    
+    /// Check whether the player to move ran out of time; if so they lose on time
+    /// Returns true if the game was lost on time
+    pub fn check_clock(&mut self) -> bool {
+        if self.game_over {
+            return false;
+        }
+        let Some(clock) = self.clock.get_mut() else {
+            return false;
+        };
+        if !clock.check_flag() || !clock.is_flagged(self.current_player) {
+            return false;
+        }
+        self.game_over = true;
+        self.winner = Some(self.current_player.opponent());
+        self.adjudication.end(self.winner, TerminationReason::Timeout);
+        true
+    }
+    
+    /// True if the player to move ran out of time (nothing is recorded)
+    fn is_out_of_time(&self) -> bool {
+        self.clock.get().is_some_and(|clock| clock.is_out_of_time(self.current_player))
+    }
+    
+    /// Hit the clock after a move: stop it if the game ended, otherwise start the opponent's time
+    /// The mover still loses on time if the move itself took too long
+    fn press_clock(&mut self, mover: Player) {
+        let Some(clock) = self.clock.get_mut() else {
+            return;
+        };
+        if self.game_over {
+            clock.stop();
+        } else if !clock.press(mover) {
+            self.game_over = true;
+            self.winner = Some(mover.opponent());
+            self.adjudication.end(self.winner, TerminationReason::Timeout);
+        }
+    }
+    
+    /// Final result with termination reason (None while the game is running)
+    /// The final move is the last L-piece placement as (row, col, orientation)
+    pub fn get_result(&self) -> Option<GameResult> {
+        if !self.game_over {
+            return None;
+        }
+        let final_move = self.move_history
+            .last()
+            .map(|last| vec![last.l_piece_to.row, last.l_piece_to.col, last.l_piece_to.orientation as usize])
+            .unwrap_or_default();
+        let (winner, reason) = self.adjudication.ending().unwrap_or((self.winner, TerminationReason::Blockade));
+        Some(GameResult::new(winner, reason, final_move))
+    }
+    
     /// Move neutral piece (optional part of move)
     pub fn move_neutral_piece(&mut self, from_row: usize, from_col: usize, to_row: usize, to_col: usize) -> Result<(), GameError> {
         if self.game_over {
             return Err(GameError::GameAlreadyOver);
         }
         
+        if from_row >= 4 || from_col >= 4 || to_row >= 4 || to_col >= 4 {
+            return Err(GameError::OutOfBounds);
+        }
+        
         // Validate from position has neutral piece
         if self.neutral_board.get_cell(from_row, from_col) != 1 {
-            return Err(GameError::InvalidMove);
+            return Err(GameError::NeutralNotFound);
         }
         
         // Validate to position is empty
         if self.is_occupied(to_row, to_col) {
-            return Err(GameError::InvalidMove);
+            return Err(GameError::PositionOccupied);
         }
         
         // Move neutral piece
@@ -383,7 +538,7 @@ impl LGame {
     // === MISSING API METHODS FOR FRONTEND COMPATIBILITY ===
     
     /// Get memory usage of the game state (for performance monitoring)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn memory_usage(&self) -> usize {
         // Calculate approximate memory usage
         let bitpacked_boards = std::mem::size_of::<BitPackedBoard<4, 4, 1>>() * 3; // 3 boards
@@ -394,46 +549,47 @@ impl LGame {
     }
     
     /// Get current player (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_current_player(&self) -> Player {
         self.current_player()
     }
     
     /// Get move count (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_move_count(&self) -> u32 {
         self.move_count()
     }
     
     /// Get winner (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_winner(&self) -> Option<Player> {
         self.winner()
     }
     
     /// Check if game is over (frontend naming convention)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn is_game_over(&self) -> bool {
         self.game_over()
     }
     
     /// Get board state as flat array for frontend (4 rows × 4 cols = 16 elements)
     /// Returns: 0=empty, 1=player1, 2=player2, 3=neutral
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_board(&self) -> Vec<u8> {
         self.get_board_state()
     }
     
     /// Check if undo is possible
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn can_undo(&self) -> bool {
         !self.move_history.is_empty() && !self.is_game_over()
     }
     
     /// Undo the last move
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn undo_move(&mut self) -> bool {
-        if !self.can_undo() {
+        // A player whose flag fell has lost and can't take a move back
+        if !self.can_undo() || self.check_clock() {
             return false;
         }
         
@@ -456,6 +612,9 @@ impl LGame {
             };
             self.game_over = false;
             self.winner = None;
+            if let Some(clock) = self.clock.get_mut() {
+                clock.take_back(self.current_player);
+            }
             
             true
         } else {
@@ -495,7 +654,7 @@ impl LGame {
     }
     
     /// Analyze current position comprehensively
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn analyze_position(&self) -> PositionAnalysis {
         let current_moves = self.get_valid_moves_count();
         let opponent_moves = self.get_opponent_moves_count();
@@ -529,7 +688,7 @@ impl LGame {
     }
     
     /// Get current game phase for AI strategy
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_game_phase(&self) -> GamePhase {
         match self.move_count {
             0..=10 => GamePhase::Opening,
@@ -551,29 +710,33 @@ impl LGame {
         self.player1_l_position = None;
         self.player2_l_position = None;
         self.move_history.clear();
+        self.adjudication.clear();
+        if let Some(clock) = self.clock.get_mut() {
+            clock.restart(starting_player);
+        }
         
         self.setup_initial_position();
     }
     
     /// Reset game to initial state
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn reset(&mut self) {
         self.reset_with_starting_player(Player::Yellow);
     }
     
     /// Frontend-friendly method aliases (Connect4 compatibility)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn newGame(&mut self) {
         self.reset();
     }
     
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn undoMove(&mut self) -> bool {
         self.undo_move()
     }
     
     /// Get valid L-piece moves for current player (for frontend)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_valid_l_moves_json(&self) -> String {
         let moves = self.get_valid_l_moves();
         let mut json = String::from("[");
@@ -593,14 +756,14 @@ impl LGame {
     }
     
     /// Check if a specific L-piece move is valid
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn is_valid_l_move(&self, row: usize, col: usize, orientation: u8) -> bool {
         let pos = LPiecePosition { row, col, orientation };
         self.can_place_l_piece(pos, Some(self.current_player))
     }
     
     /// Get neutral piece positions
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_neutral_positions(&self) -> Vec<u8> {
         let mut positions = Vec::new();
         for row in 0..4 {
@@ -615,7 +778,7 @@ impl LGame {
     }
     
     /// Get L-piece position for a specific player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_l_piece_position(&self, player: Player) -> Vec<u8> {
         let position = match player {
             Player::Yellow => self.player1_l_position,
@@ -653,6 +816,8 @@ impl LGame {
             player1_l_position: None,
             player2_l_position: None,
             move_history: Vec::new(),
+            adjudication: Adjudication::default(),
+            clock: AttachedClock::default(),
         };
         
         // Detect L-piece positions from board state
@@ -732,14 +897,10 @@ impl LGame {
         }
     }
     
-    /// Make a move for internal/test use (returns Result<bool, String>)
-    pub fn make_move_internal(&mut self, l_to_row: usize, l_to_col: usize, l_to_orientation: u8) -> Result<bool, String> {
-        match self.make_move(l_to_row, l_to_col, l_to_orientation) {
-            Ok(_) => Ok(self.game_over),
-            Err(GameError::GameAlreadyOver) => Err("Game is already over".to_string()),
-            Err(GameError::InvalidMove) => Err("Invalid move".to_string()),
-            Err(_) => Err("Unknown error".to_string()),
-        }
+    /// Make a move for internal/test use; returns true if the game is over afterwards
+    pub fn make_move_internal(&mut self, l_to_row: usize, l_to_col: usize, l_to_orientation: u8) -> Result<bool, GameError> {
+        self.make_move(l_to_row, l_to_col, l_to_orientation)?;
+        Ok(self.game_over)
     }
     
     /// Get board for AI evaluation (internal use)
@@ -803,6 +964,35 @@ impl LGame {
         }
     }
     
+    /// Two-ply mobility search: take a blocking move if there is one, otherwise the L-move
+    /// whose worst-case reply leaves the best evaluation (neutral pieces stay in place)
+    pub fn get_ai_move(&self) -> Option<(usize, usize, u8)> {
+        if self.game_over {
+            return None;
+        }
+        
+        let mut best: Option<((usize, usize, u8), i32)> = None;
+        for (row, col, orientation) in self.get_all_valid_moves() {
+            let Some(child) = self.make_move_copy(row, col, orientation) else {
+                continue;
+            };
+            if child.game_over {
+                return Some((row, col, orientation));
+            }
+            let score = child
+                .get_all_valid_moves()
+                .into_iter()
+                .filter_map(|(r, c, o)| child.make_move_copy(r, c, o))
+                .map(|grandchild| if grandchild.game_over { i32::MIN } else { grandchild.evaluate_position() })
+                .min()
+                .unwrap_or(i32::MAX);
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some(((row, col, orientation), score));
+            }
+        }
+        best.map(|(game_move, _)| game_move)
+    }
+    
     /// Check if the L-game state is valid (for testing)
     pub fn is_valid_state(&self) -> bool {
         // Check that each player has exactly one L-piece (4 cells each)
@@ -945,6 +1135,79 @@ mod tests {
         assert_eq!(game.get_cell(3, 0), 3);
     }
     
+    #[test]
+    fn test_resignation_and_draw_agreement() {
+        let mut game = LGame::new();
+        assert!(game.get_result().is_none());
+        assert!(game.resign(Player::Yellow));
+        assert!(game.is_game_over());
+        let result = game.get_result().unwrap();
+        assert_eq!(result.winner(), Some(Player::Red));
+        assert_eq!(result.reason(), TerminationReason::Resignation);
+        assert!(result.final_move().is_empty());
+        
+        game.reset();
+        assert!(game.offer_draw(Player::Red));
+        assert!(game.accept_draw(Player::Yellow));
+        assert!(game.is_game_over());
+        assert_eq!(game.get_result().unwrap().reason(), TerminationReason::AgreedDraw);
+    }
+    
+    #[test]
+    fn test_clock_flags_on_move() {
+        use crate::games::clock::{ManualTimeSource, TimeControl};
+        use std::sync::Arc;
+        
+        let time = ManualTimeSource::new(0);
+        let mut game = LGame::new();
+        game.attach_clock(GameClock::with_time_source(TimeControl::sudden_death(1_000), Arc::new(time.clone())));
+        
+        // Undo gives the turn and the clock back to the player who moved
+        let (row, col, orientation) = game.get_all_valid_moves()[0];
+        game.make_move(row, col, orientation).unwrap();
+        time.advance(300);
+        assert!(game.undo_move());
+        assert!(game.get_clock().unwrap().is_running(Player::Yellow));
+        game.make_move(row, col, orientation).unwrap();
+        assert!(!game.is_game_over());
+        assert!(game.undo_move());
+        
+        // The refused move leaves the game untouched until the loss is recorded
+        time.advance(1_001);
+        assert!(matches!(game.make_move(row, col, orientation), Err(GameError::TimeForfeit)));
+        assert!(!game.is_game_over());
+        assert!(game.check_clock());
+        let result = game.get_result().unwrap();
+        assert_eq!(result.winner(), Some(Player::Red));
+        assert_eq!(result.reason(), TerminationReason::Timeout);
+    }
+    
+    #[test]
+    fn test_move_errors() {
+        let mut game = LGame::new();
+        assert_eq!(game.make_move(0, 0, 9), Err(GameError::InvalidOrientation));
+        assert_eq!(game.make_move(0, 0, 0), Err(GameError::LPieceNotMoved)); // Yellow's start position
+        assert_eq!(game.make_move(3, 3, 0), Err(GameError::OutOfBounds));
+        assert_eq!(game.make_move(1, 2, 0), Err(GameError::PositionOccupied));
+        
+        let (row, col, orientation) = game.get_all_valid_moves()[0];
+        game.make_move(row, col, orientation).unwrap();
+        assert_eq!(game.move_neutral_piece(1, 1, 2, 2), Err(GameError::NeutralNotFound));
+        assert_eq!(game.move_neutral_piece(0, 3, 4, 0), Err(GameError::OutOfBounds));
+        assert_eq!(game.move_neutral_piece(0, 3, 3, 0), Err(GameError::PositionOccupied));
+    }
+    
+    #[test]
+    fn test_board_string_and_ai_move() {
+        let mut game = LGame::new();
+        assert_eq!(game.board_string(), "Y..N\nY.RR\nYY.R\nN..R\n");
+        
+        let (row, col, orientation) = game.get_ai_move().unwrap();
+        assert!(game.get_all_valid_moves().contains(&(row, col, orientation)));
+        game.make_move(row, col, orientation).unwrap();
+        assert!(!game.is_game_over());
+    }
+    
     #[test]
     fn test_l_piece_coordinates() {
         let game = LGame::new();
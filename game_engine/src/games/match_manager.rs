@@ -0,0 +1,550 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::{GameError, Player};
+use crate::games::{Connect4Game, GomokuGame, LGame, MnkGame};
+use crate::games::game_result::GameResult;
+
+/// The two competitors of a match (independent of the colors they play)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MatchParticipant {
+    A = 0,
+    B = 1,
+}
+
+impl MatchParticipant {
+    pub fn other(self) -> Self {
+        match self {
+            MatchParticipant::A => MatchParticipant::B,
+            MatchParticipant::B => MatchParticipant::A,
+        }
+    }
+}
+
+/// How the match is scored
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchFormat {
+    BestOf = 0,  // At most N games, decided by points (win 1, draw ½)
+    FirstTo = 1, // Play until a participant has K wins; draws are replayed
+}
+
+/// Who starts the next game
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartRule {
+    LoserStarts = 0,
+    Alternate = 1,
+    WinnerStarts = 2,
+}
+
+/// What happens when a best-of match ends level
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TiebreakRule {
+    Draw = 0,        // The match is drawn
+    SuddenDeath = 1, // Extra games until one is won
+    Armageddon = 2,  // One extra game; a draw goes to the participant moving second
+}
+
+impl TiebreakRule {
+    fn record_name(self) -> &'static str {
+        match self {
+            TiebreakRule::Draw => "draw",
+            TiebreakRule::SuddenDeath => "sudden_death",
+            TiebreakRule::Armageddon => "armageddon",
+        }
+    }
+}
+
+/// One played game of a match
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchGame {
+    starter: MatchParticipant,
+    a_plays_first_color: bool,
+    tiebreak: bool,
+    winner: Option<MatchParticipant>,
+    result: GameResult,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl MatchGame {
+    /// Participant who made the first move
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn starter(&self) -> MatchParticipant { self.starter }
+    
+    /// True if A played the first color (Yellow/Black)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn a_plays_first_color(&self) -> bool { self.a_plays_first_color }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn tiebreak(&self) -> bool { self.tiebreak }
+    
+    /// Winning participant (None for a draw, also for a drawn Armageddon game)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn winner(&self) -> Option<MatchParticipant> { self.winner }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn result(&self) -> GameResult { self.result.clone() }
+}
+
+/// Series of games between two participants with scoring, start order, colors and tiebreaks
+/// Colors are tracked as the first color (Yellow/Black) and the second color (Red/White), so one manager fits every game
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchManager {
+    format: MatchFormat,
+    target: u32, // N games for best-of, K wins for first-to
+    start_rule: StartRule,
+    color_retention: bool, // Participants keep their colors; otherwise the starter takes the first color
+    tiebreak: TiebreakRule,
+    games: Vec<MatchGame>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl MatchManager {
+    /// Create a match; A starts the first game with the first color
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(format: MatchFormat, target: u32) -> MatchManager {
+        Self {
+            format,
+            target: target.max(1),
+            start_rule: StartRule::LoserStarts,
+            color_retention: false,
+            tiebreak: TiebreakRule::Draw,
+            games: Vec::new(),
+        }
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn best_of(games: u32) -> MatchManager {
+        Self::new(MatchFormat::BestOf, games)
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn first_to(wins: u32) -> MatchManager {
+        Self::new(MatchFormat::FirstTo, wins)
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_start_rule(&mut self, rule: StartRule) {
+        self.start_rule = rule;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_start_rule(&self) -> StartRule {
+        self.start_rule
+    }
+    
+    /// Enable or disable color retention (participants keep their colors whoever starts)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_color_retention_enabled(&mut self, enabled: bool) {
+        self.color_retention = enabled;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_color_retention_enabled(&self) -> bool {
+        self.color_retention
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_tiebreak(&mut self, rule: TiebreakRule) {
+        self.tiebreak = rule;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_tiebreak(&self) -> TiebreakRule {
+        self.tiebreak
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_format(&self) -> MatchFormat {
+        self.format
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_target(&self) -> u32 {
+        self.target
+    }
+    
+    /// Participant who starts the next game
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn next_starter(&self) -> MatchParticipant {
+        let Some(last) = self.games.last() else {
+            return MatchParticipant::A;
+        };
+        match (self.start_rule, last.winner) {
+            (StartRule::LoserStarts, Some(winner)) => winner.other(),
+            (StartRule::WinnerStarts, Some(winner)) => winner,
+            // Alternate, and after a draw under the other rules
+            _ => last.starter.other(),
+        }
+    }
+    
+    /// Color a participant plays in the next game, given the game's first color (Yellow or Black)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn color_of(&self, participant: MatchParticipant, first_color: Player) -> Player {
+        if (participant == MatchParticipant::A) == self.a_plays_first_color() {
+            first_color
+        } else {
+            first_color.opponent()
+        }
+    }
+    
+    /// Color that moves first in the next game (pass it to `reset_with_starting_player`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn next_starting_color(&self, first_color: Player) -> Player {
+        self.color_of(self.next_starter(), first_color)
+    }
+    
+    /// Record a finished game; returns true if the match is now decided
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn record_result(&mut self, result: &GameResult) -> Result<bool, GameError> {
+        self.record_result_internal(result)
+    }
+    
+    /// True if the next game is a tiebreak game
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_tiebreak(&self) -> bool {
+        self.format == MatchFormat::BestOf
+            && self.tiebreak != TiebreakRule::Draw
+            && self.regular_games() >= self.target as usize
+            && self.regular_points(MatchParticipant::A) == self.regular_points(MatchParticipant::B)
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some() || self.is_drawn()
+    }
+    
+    /// Match winner (None while running or if drawn)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn winner(&self) -> Option<MatchParticipant> {
+        if let Some(decider) = self.games.iter().find(|game| game.tiebreak && game.winner.is_some()) {
+            return decider.winner;
+        }
+        if let Some(armageddon) = self.games.iter().find(|game| game.tiebreak) {
+            // Only a drawn Armageddon game is left undecided above
+            return (self.tiebreak == TiebreakRule::Armageddon).then(|| armageddon.starter.other());
+        }
+    
+        let (a, b) = (MatchParticipant::A, MatchParticipant::B);
+        match self.format {
+            MatchFormat::FirstTo => [a, b].into_iter().find(|&participant| self.wins(participant) >= self.target),
+            MatchFormat::BestOf => {
+                // Decided once the trailing participant can't catch up in the remaining games
+                let remaining = 2 * (self.target as usize).saturating_sub(self.regular_games());
+                let (points_a, points_b) = (self.regular_points(a), self.regular_points(b));
+                if points_a > points_b + remaining {
+                    Some(a)
+                } else if points_b > points_a + remaining {
+                    Some(b)
+                } else {
+                    None
+                }
+            },
+        }
+    }
+    
+    /// True if a best-of match ended level without a tiebreak
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_drawn(&self) -> bool {
+        self.format == MatchFormat::BestOf
+            && self.tiebreak == TiebreakRule::Draw
+            && self.regular_games() >= self.target as usize
+            && self.regular_points(MatchParticipant::A) == self.regular_points(MatchParticipant::B)
+    }
+    
+    /// Match points of a participant in regular games (win 1, draw ½)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn score(&self, participant: MatchParticipant) -> f64 {
+        self.regular_points(participant) as f64 / 2.0
+    }
+    
+    /// Games won by a participant, tiebreak games included
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn wins(&self, participant: MatchParticipant) -> u32 {
+        self.games.iter().filter(|game| game.winner == Some(participant)).count() as u32
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn games_played(&self) -> usize {
+        self.games.len()
+    }
+    
+    /// Played games in order
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_games(&self) -> Vec<MatchGame> {
+        self.games.clone()
+    }
+    
+    /// Start a new match with the same settings
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn reset(&mut self) {
+        self.games.clear();
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn summary(&self) -> String {
+        let score = format!("{}-{}", self.score(MatchParticipant::A), self.score(MatchParticipant::B));
+        match self.winner() {
+            Some(winner) => format!("{:?} wins the match ({})", winner, score),
+            None if self.is_drawn() => format!("Match drawn ({})", score),
+            None if self.is_tiebreak() => format!("Tiebreak ({})", score),
+            None => format!("Game {} ({})", self.games.len() + 1, score),
+        }
+    }
+    
+    /// Serialize settings and results: a header line, then one `GameResult` record per game
+    /// Starters and colors are derived from the rules, so replaying the results restores the match
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn to_record(&self) -> String {
+        let format = match self.format {
+            MatchFormat::BestOf => "best_of",
+            MatchFormat::FirstTo => "first_to",
+        };
+        let start_rule = match self.start_rule {
+            StartRule::LoserStarts => "loser_starts",
+            StartRule::Alternate => "alternate",
+            StartRule::WinnerStarts => "winner_starts",
+        };
+        let colors = if self.color_retention { "retain_colors" } else { "starter_first_color" };
+        let mut lines = vec![format!("{} {} {} {} {}", format, self.target, start_rule, colors, self.tiebreak.record_name())];
+        lines.extend(self.games.iter().map(|game| game.result.to_record()));
+        lines.join("\n")
+    }
+    
+    /// Parse a match written by `to_record` (None if malformed)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn from_record(record: &str) -> Option<MatchManager> {
+        let mut lines = record.lines().map(str::trim).filter(|line| !line.is_empty());
+        let header: Vec<&str> = lines.next()?.split_whitespace().collect();
+        let [format, target, start_rule, colors, tiebreak] = header[..] else {
+            return None;
+        };
+    
+        let format = match format {
+            "best_of" => MatchFormat::BestOf,
+            "first_to" => MatchFormat::FirstTo,
+            _ => return None,
+        };
+        let mut manager = Self::new(format, target.parse().ok()?);
+        manager.start_rule = match start_rule {
+            "loser_starts" => StartRule::LoserStarts,
+            "alternate" => StartRule::Alternate,
+            "winner_starts" => StartRule::WinnerStarts,
+            _ => return None,
+        };
+        manager.color_retention = match colors {
+            "retain_colors" => true,
+            "starter_first_color" => false,
+            _ => return None,
+        };
+        manager.tiebreak = [TiebreakRule::Draw, TiebreakRule::SuddenDeath, TiebreakRule::Armageddon]
+            .into_iter()
+            .find(|rule| rule.record_name() == tiebreak)?;
+    
+        for line in lines {
+            manager.record_result_internal(&GameResult::from_record(line)?).ok()?;
+        }
+        Some(manager)
+    }
+}
+
+impl MatchManager {
+    /// Record a finished game (internal API)
+    pub fn record_result_internal(&mut self, result: &GameResult) -> Result<bool, GameError> {
+        if self.is_over() {
+            return Err(GameError::MatchAlreadyOver);
+        }
+    
+        let a_plays_first_color = self.a_plays_first_color();
+        let winner = result.winner().map(|color| {
+            if Self::is_first_color(color) == a_plays_first_color { MatchParticipant::A } else { MatchParticipant::B }
+        });
+        self.games.push(MatchGame {
+            starter: self.next_starter(),
+            a_plays_first_color,
+            tiebreak: self.is_tiebreak(),
+            winner,
+            result: result.clone(),
+        });
+        Ok(self.is_over())
+    }
+    
+    /// Reset a game for the next match game with the right starting color
+    pub fn start_game<G: SeriesGame>(&self, game: &mut G) {
+        let first_color = game.first_color();
+        game.restart(self.next_starting_color(first_color));
+    }
+    
+    /// Record the result of a finished game; returns true if the match is now decided
+    pub fn record_game<G: SeriesGame>(&mut self, game: &G) -> Result<bool, GameError> {
+        let result = game.final_result().ok_or(GameError::GameNotOver)?;
+        self.record_result_internal(&result)
+    }
+    
+    /// True if A plays the first color (Yellow/Black) in the next game
+    fn a_plays_first_color(&self) -> bool {
+        if self.color_retention {
+            true
+        } else {
+            self.next_starter() == MatchParticipant::A
+        }
+    }
+    
+    fn is_first_color(color: Player) -> bool {
+        matches!(color, Player::Yellow | Player::Black)
+    }
+    
+    fn regular_games(&self) -> usize {
+        self.games.iter().filter(|game| !game.tiebreak).count()
+    }
+    
+    /// Points in half-point units (win 2, draw 1) so draws stay exact
+    fn regular_points(&self, participant: MatchParticipant) -> usize {
+        self.games
+            .iter()
+            .filter(|game| !game.tiebreak)
+            .map(|game| match game.winner {
+                Some(winner) if winner == participant => 2,
+                Some(_) => 0,
+                None => 1,
+            })
+            .sum()
+    }
+}
+
+/// Games a `MatchManager` can run directly
+pub trait SeriesGame {
+    /// Color that moves first by default (Yellow or Black)
+    fn first_color(&self) -> Player;
+    fn restart(&mut self, starting_player: Player);
+    fn final_result(&self) -> Option<GameResult>;
+}
+
+impl SeriesGame for Connect4Game {
+    fn first_color(&self) -> Player { Player::Yellow }
+    fn restart(&mut self, starting_player: Player) { self.reset_with_starting_player(starting_player) }
+    fn final_result(&self) -> Option<GameResult> { self.get_result() }
+}
+
+impl SeriesGame for GomokuGame {
+    fn first_color(&self) -> Player { Player::Black }
+    fn restart(&mut self, starting_player: Player) { self.reset_with_starting_player(starting_player) }
+    fn final_result(&self) -> Option<GameResult> { self.get_result() }
+}
+
+impl SeriesGame for LGame {
+    fn first_color(&self) -> Player { Player::Yellow }
+    fn restart(&mut self, starting_player: Player) { self.reset_with_starting_player(starting_player) }
+    fn final_result(&self) -> Option<GameResult> { self.get_result() }
+}
+
+impl SeriesGame for MnkGame {
+    fn first_color(&self) -> Player { self.first_player() }
+    fn restart(&mut self, starting_player: Player) { self.reset_with_starting_player(starting_player) }
+    fn final_result(&self) -> Option<GameResult> { self.get_result() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::TerminationReason;
+    
+    fn win(color: Player) -> GameResult {
+        GameResult::new(Some(color), TerminationReason::LineCompleted, vec![3])
+    }
+    
+    fn draw() -> GameResult {
+        GameResult::new(None, TerminationReason::BoardFull, Vec::new())
+    }
+    
+    #[test]
+    fn test_best_of_with_loser_starts() {
+        let mut manager = MatchManager::best_of(3);
+        assert_eq!(manager.next_starting_color(Player::Yellow), Player::Yellow);
+    
+        // A wins with Yellow, so B starts game two and takes Yellow
+        assert_eq!(manager.record_result_internal(&win(Player::Yellow)), Ok(false));
+        assert_eq!(manager.next_starter(), MatchParticipant::B);
+        assert_eq!(manager.color_of(MatchParticipant::B, Player::Yellow), Player::Yellow);
+    
+        // A wins again with Red and clinches 2-0
+        assert_eq!(manager.record_result_internal(&win(Player::Red)), Ok(true));
+        assert_eq!(manager.winner(), Some(MatchParticipant::A));
+        assert_eq!(manager.score(MatchParticipant::A), 2.0);
+        assert!(manager.record_result_internal(&draw()).is_err());
+        assert_eq!(manager.summary(), "A wins the match (2-0)");
+    }
+    
+    #[test]
+    fn test_color_retention_and_start_rules() {
+        let mut manager = MatchManager::first_to(2);
+        manager.set_color_retention_enabled(true);
+        manager.set_start_rule(StartRule::WinnerStarts);
+    
+        manager.record_result_internal(&win(Player::Red)).unwrap(); // B wins with White/Red
+        assert_eq!(manager.next_starter(), MatchParticipant::B);
+        assert_eq!(manager.next_starting_color(Player::Black), Player::White); // B keeps the second color
+    
+        manager.record_result_internal(&draw()).unwrap(); // Draws alternate the start
+        assert_eq!(manager.next_starter(), MatchParticipant::A);
+        manager.record_result_internal(&win(Player::Red)).unwrap();
+        assert_eq!(manager.winner(), Some(MatchParticipant::B));
+        assert_eq!(manager.games_played(), 3);
+    }
+    
+    #[test]
+    fn test_tiebreaks() {
+        let mut manager = MatchManager::best_of(2);
+        manager.record_result_internal(&win(Player::Yellow)).unwrap(); // A
+        manager.record_result_internal(&win(Player::Yellow)).unwrap(); // B started with Yellow
+        assert!(manager.is_drawn());
+    
+        manager.reset();
+        manager.set_tiebreak(TiebreakRule::SuddenDeath);
+        manager.record_result_internal(&draw()).unwrap();
+        manager.record_result_internal(&draw()).unwrap();
+        assert!(manager.is_tiebreak());
+        assert_eq!(manager.record_result_internal(&draw()), Ok(false));
+        assert_eq!(manager.record_result_internal(&win(Player::Red)), Ok(true));
+        assert_eq!(manager.winner(), Some(MatchParticipant::A)); // B started the second tiebreak game with Yellow
+    
+        // Armageddon: a drawn game goes to the participant moving second
+        manager.reset();
+        manager.set_tiebreak(TiebreakRule::Armageddon);
+        manager.record_result_internal(&draw()).unwrap();
+        manager.record_result_internal(&draw()).unwrap();
+        assert_eq!(manager.next_starter(), MatchParticipant::A);
+        assert_eq!(manager.record_result_internal(&draw()), Ok(true));
+        assert_eq!(manager.winner(), Some(MatchParticipant::B));
+        assert!(manager.get_games()[2].tiebreak());
+    }
+    
+    #[test]
+    fn test_record_round_trip_and_games() {
+        let mut manager = MatchManager::best_of(5);
+        manager.set_start_rule(StartRule::Alternate);
+        manager.set_tiebreak(TiebreakRule::Armageddon);
+    
+        let mut game = Connect4Game::new();
+        manager.start_game(&mut game);
+        assert!(manager.record_game(&game).is_err()); // Still running
+        for column in [0, 1, 0, 1, 0, 1, 0] {
+            game.make_move_internal(column).unwrap();
+        }
+        manager.record_game(&game).unwrap();
+    
+        manager.start_game(&mut game);
+        assert_eq!(game.current_player(), Player::Yellow); // B starts with Yellow
+        game.resign(Player::Yellow);
+        manager.record_game(&game).unwrap();
+        assert_eq!(manager.score(MatchParticipant::A), 2.0);
+    
+        let restored = MatchManager::from_record(&manager.to_record()).unwrap();
+        assert_eq!(restored, manager);
+        assert_eq!(MatchManager::from_record("best_of 5 alternate"), None);
+    }
+}
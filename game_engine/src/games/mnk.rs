@@ -0,0 +1,649 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::data::DynamicBitPackedBoard;
+use crate::geometry::{DynamicQuadraticGrid, BoardGeometry};
+use crate::ai::MnkAI;
+use crate::games::game_result::{Adjudication, GameResult, TerminationReason};
+use crate::{GameError, GamePhase, Player, WinningLine};
+
+/// Well-known m,n,k games (rows × cols, k in a row, gravity)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MnkVariant {
+    TicTacToe = 0, // 3×3, three in a row
+    Connect4 = 1,  // 7×6, four in a row, gravity
+    Gomoku = 2,    // 15×15, five in a row
+}
+
+impl MnkVariant {
+    /// Configuration of the variant as (rows, cols, win_length, gravity)
+    pub fn config(self) -> (usize, usize, usize, bool) {
+        match self {
+            MnkVariant::TicTacToe => (3, 3, 3, false),
+            MnkVariant::Connect4 => (6, 7, 4, true),
+            MnkVariant::Gomoku => (15, 15, 5, false),
+        }
+    }
+}
+
+/// Supported board sides and win lengths
+const MIN_BOARD_SIDE: usize = 3;
+const MAX_BOARD_SIDE: usize = 19;
+const MIN_WIN_LENGTH: usize = 3;
+
+/// Generic m,n,k game: any board size, k in a row wins, optional gravity
+/// Built on the Three-Layer Architecture like the dedicated games
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct MnkGame {
+    // Composition: Geometry layer handles coordinate logic and winning lines
+    geometry: DynamicQuadraticGrid,
+    
+    // Composition: Data layer (one board per player)
+    first_board: DynamicBitPackedBoard,
+    second_board: DynamicBitPackedBoard,
+    
+    // Composition: AI layer shared by all configurations
+    ai: MnkAI,
+    
+    // Game-specific state
+    current_player: Player,
+    winner: Option<Player>,
+    move_history: Vec<usize>, // Played cell indices for undo functionality
+    adjudication: Adjudication, // Resignations and draw offers
+    
+    // Board configuration
+    rows: usize,
+    cols: usize,
+    win_length: usize,
+    gravity: bool,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl MnkGame {
+    /// Create an m×n board where k in a row wins, with or without gravity
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(rows: usize, cols: usize, win_length: usize, gravity: bool) -> Result<MnkGame, GameError> {
+        Self::new_internal(rows, cols, win_length, gravity)
+    }
+    
+    /// Create one of the well-known variants
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn new_variant(variant: MnkVariant) -> MnkGame {
+        let (rows, cols, win_length, gravity) = variant.config();
+        Self::with_config(rows, cols, win_length, gravity)
+    }
+    
+    /// Number of rows on the board
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_rows(&self) -> usize {
+        self.rows
+    }
+    
+    /// Number of columns on the board
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_cols(&self) -> usize {
+        self.cols
+    }
+    
+    /// Pieces in a row needed to win
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_win_length(&self) -> usize {
+        self.win_length
+    }
+    
+    /// True if pieces fall to the lowest free cell of their column
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_gravity_enabled(&self) -> bool {
+        self.gravity
+    }
+    
+    /// Player moving first (Yellow with gravity, Black without)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn first_player(&self) -> Player {
+        if self.gravity { Player::Yellow } else { Player::Black }
+    }
+    
+    /// Place a piece at (row, col); with gravity the cell must be the lowest free one
+    /// Returns true if the move won the game
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn make_move(&mut self, row: usize, col: usize) -> Result<bool, GameError> {
+        self.make_move_internal(row, col)
+    }
+    
+    /// Drop a piece into a column (gravity games only)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn drop_piece(&mut self, col: usize) -> Result<bool, GameError> {
+        self.drop_piece_internal(col)
+    }
+    
+    /// Check if (row, col) is a legal move for the current player
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_valid_move(&self, row: usize, col: usize) -> bool {
+        if row >= self.rows || col >= self.cols || self.is_game_over() {
+            return false;
+        }
+        if self.gravity {
+            self.landing_row(col) == Some(row)
+        } else {
+            self.get_cell(row, col) == 0
+        }
+    }
+    
+    /// Lowest free row of a column (None if full or out of range)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn landing_row(&self, col: usize) -> Option<usize> {
+        if col >= self.cols {
+            return None;
+        }
+        (0..self.rows).rev().find(|&row| self.get_cell(row, col) == 0)
+    }
+    
+    /// All legal moves as cell indices (`row * cols + col`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_legal_moves(&self) -> Vec<usize> {
+        if self.is_game_over() {
+            return Vec::new();
+        }
+        if self.gravity {
+            (0..self.cols)
+                .filter_map(|col| self.landing_row(col).map(|row| row * self.cols + col))
+                .collect()
+        } else {
+            (0..self.rows * self.cols)
+                .filter(|&index| !self.is_occupied(index))
+                .collect()
+        }
+    }
+    
+    /// Get cell value at position (0 = empty, 1 = first player, 2 = second player)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_cell(&self, row: usize, col: usize) -> u8 {
+        match self.geometry.to_index((row as i32, col as i32)) {
+            Some(index) if self.first_board.get_bit(index) => 1,
+            Some(index) if self.second_board.get_bit(index) => 2,
+            _ => 0,
+        }
+    }
+    
+    /// Get board state as flat array for frontend (rows × cols elements)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_board(&self) -> Vec<u8> {
+        let mut board = vec![0u8; self.rows * self.cols];
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                board[row * self.cols + col] = self.get_cell(row, col);
+            }
+        }
+        board
+    }
+    
+    /// Get current player
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn current_player(&self) -> Player {
+        self.current_player
+    }
+    
+    /// Get winner (if any)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn winner(&self) -> Option<Player> {
+        self.winner
+    }
+    
+    /// Get move count
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn move_count(&self) -> usize {
+        self.move_history.len()
+    }
+    
+    /// Cells of every completed line, flattened as (row, col) pairs (empty while the game is running)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_winning_cells(&self) -> Vec<usize> {
+        WinningLine::flatten_cells(&self.get_completed_lines())
+    }
+    
+    /// Every completed line on the board (one move may complete several)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_completed_lines(&self) -> Vec<WinningLine> {
+        [self.first_player(), self.first_player().opponent()]
+            .into_iter()
+            .flat_map(|player| {
+                let board = self.get_board_for_player(player);
+                let windows = self.geometry
+                    .get_winning_lines(self.win_length)
+                    .iter()
+                    .filter(|line| board.count_set_bits_in_mask(line) >= self.win_length as u32)
+                    .map(|line| {
+                        (0..self.rows * self.cols)
+                            .filter(|&index| line.get_bit(index))
+                            .map(|index| (index / self.cols, index % self.cols))
+                            .collect()
+                    })
+                    .collect();
+                WinningLine::from_windows(player, windows)
+            })
+            .collect()
+    }
+    
+    /// Check if game is draw (board full or agreed draw, no winner)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_draw(&self) -> bool {
+        self.winner.is_none() && (self.move_history.len() >= self.rows * self.cols || self.adjudication.is_draw())
+    }
+    
+    /// Resign the game: the opponent wins; returns false if the game is over or the player is not playing
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn resign(&mut self, player: Player) -> bool {
+        if self.is_game_over() || !self.is_participant(player) {
+            return false;
+        }
+        self.winner = Some(player.opponent());
+        self.adjudication.end(self.winner, TerminationReason::Resignation);
+        true
+    }
+    
+    /// Offer a draw; it stands until the opponent accepts it or declines by moving
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn offer_draw(&mut self, player: Player) -> bool {
+        if self.is_game_over() || !self.is_participant(player) {
+            return false;
+        }
+        self.adjudication.offer_draw(player);
+        true
+    }
+    
+    /// Accept the opponent's pending draw offer; returns false if there is none
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn accept_draw(&mut self, player: Player) -> bool {
+        !self.is_game_over() && self.adjudication.accept_draw(player)
+    }
+    
+    /// Player with a pending draw offer (if any)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_draw_offer(&self) -> Option<Player> {
+        self.adjudication.draw_offer()
+    }
+    
+    /// Final result with termination reason (None while the game is running)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_result(&self) -> Option<GameResult> {
+        let final_move = self.move_history.last().map(|&index| vec![index / self.cols, index % self.cols]).unwrap_or_default();
+        let (winner, reason) = if let Some(ending) = self.adjudication.ending() {
+            ending
+        } else if self.winner.is_some() {
+            (self.winner, TerminationReason::LineCompleted)
+        } else if self.is_draw() {
+            (None, TerminationReason::BoardFull)
+        } else {
+            return None;
+        };
+        Some(GameResult::new(winner, reason, final_move))
+    }
+    
+    /// Check if game is over (win or draw)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_game_over(&self) -> bool {
+        self.winner.is_some() || self.is_draw()
+    }
+    
+    /// Get current game phase (scaled by board size)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_game_phase(&self) -> GamePhase {
+        let cells = self.rows * self.cols;
+        let moves = self.move_history.len();
+        if moves * 4 <= cells {
+            GamePhase::Opening
+        } else if moves * 4 <= cells * 3 {
+            GamePhase::Middle
+        } else {
+            GamePhase::Endgame
+        }
+    }
+    
+    /// Check if undo is possible
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn can_undo(&self) -> bool {
+        !self.move_history.is_empty()
+    }
+    
+    /// Undo the last move
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn undo_move(&mut self) -> bool {
+        let Some(index) = self.move_history.pop() else {
+            return false;
+        };
+        
+        let player = if self.first_board.get_bit(index) { self.first_player() } else { self.first_player().opponent() };
+        self.get_player_board_mut(player).set_bit(index, false);
+        self.current_player = player;
+        self.winner = None;
+        self.adjudication.clear();
+        true
+    }
+    
+    /// Reset game to initial state
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn reset(&mut self) {
+        self.reset_with_starting_player(self.first_player());
+    }
+    
+    /// Reset game with a specific starting player
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn reset_with_starting_player(&mut self, starting_player: Player) {
+        self.first_board.clear();
+        self.second_board.clear();
+        // Only the two players of this configuration can start
+        self.current_player = if starting_player == self.first_player().opponent() {
+            starting_player
+        } else {
+            self.first_player()
+        };
+        self.winner = None;
+        self.move_history.clear();
+        self.adjudication.clear();
+    }
+    
+    /// Set the AI search depth
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_ai_depth(&mut self, depth: usize) {
+        self.ai = MnkAI::new_with_depth(depth);
+    }
+    
+    /// Get AI move suggestion as [row, col] (empty if the game is over)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_ai_move(&self) -> Vec<usize> {
+        match self.ai.get_best_move(self) {
+            Some(index) => vec![index / self.cols, index % self.cols],
+            None => vec![],
+        }
+    }
+    
+    /// Evaluate position for the current player
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn evaluate_position(&self) -> i32 {
+        self.ai.evaluate_position(self, self.current_player)
+    }
+    
+    /// Get board state as string for debugging
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn board_string(&self) -> String {
+        let mut result = String::new();
+        
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let char = match self.get_cell(row, col) {
+                    0 => '.',
+                    1 => 'X',
+                    2 => 'O',
+                    _ => '?',
+                };
+                result.push(char);
+            }
+            result.push('\n');
+        }
+        
+        result
+    }
+    
+    /// Get memory usage of the game state (for performance monitoring)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn memory_usage(&self) -> usize {
+        let bitpacked_boards = self.first_board.memory_usage() + self.second_board.memory_usage();
+        let game_state = std::mem::size_of::<MnkGame>();
+        let move_history = self.move_history.len() * std::mem::size_of::<usize>();
+        
+        bitpacked_boards + game_state + move_history
+    }
+}
+
+// Internal implementation for AI access
+impl MnkGame {
+    /// Create a game with validated configuration (pure Rust, testable without a JS runtime)
+    pub fn new_internal(rows: usize, cols: usize, win_length: usize, gravity: bool) -> Result<MnkGame, GameError> {
+        if !(MIN_BOARD_SIDE..=MAX_BOARD_SIDE).contains(&rows) || !(MIN_BOARD_SIDE..=MAX_BOARD_SIDE).contains(&cols) {
+            return Err(GameError::InvalidBoardSize { rows, cols, min: MIN_BOARD_SIDE, max: MAX_BOARD_SIDE });
+        }
+        if win_length < MIN_WIN_LENGTH || win_length > rows.max(cols) {
+            return Err(GameError::InvalidWinLength { win_length, rows, cols });
+        }
+        
+        Ok(Self::with_config(rows, cols, win_length, gravity))
+    }
+    
+    /// Empty game with the given (already validated) configuration
+    fn with_config(rows: usize, cols: usize, win_length: usize, gravity: bool) -> Self {
+        let geometry = DynamicQuadraticGrid::with_line_length(rows, cols, 2, win_length);
+        Self {
+            first_board: geometry.empty_board(),
+            second_board: geometry.empty_board(),
+            geometry,
+            ai: MnkAI::new(),
+            current_player: if gravity { Player::Yellow } else { Player::Black },
+            winner: None,
+            move_history: Vec::new(),
+            adjudication: Adjudication::default(),
+            rows,
+            cols,
+            win_length,
+            gravity,
+        }
+    }
+    
+    /// Place a piece for internal/test use
+    pub fn make_move_internal(&mut self, row: usize, col: usize) -> Result<bool, GameError> {
+        if row >= self.rows || col >= self.cols {
+            return Err(GameError::OutOfBounds);
+        }
+        if self.is_game_over() {
+            return Err(GameError::GameAlreadyOver);
+        }
+        if self.get_cell(row, col) != 0 {
+            return Err(GameError::PositionOccupied);
+        }
+        if self.gravity && self.landing_row(col) != Some(row) {
+            return Err(GameError::FloatingPiece);
+        }
+        
+        Ok(self.place(row * self.cols + col))
+    }
+    
+    /// Play a cell index (`row * cols + col`) as returned by `get_legal_moves`
+    pub fn make_move_index(&mut self, index: usize) -> Result<bool, GameError> {
+        self.make_move_internal(index / self.cols, index % self.cols)
+    }
+    
+    /// Drop a piece into a column for internal/test use
+    pub fn drop_piece_internal(&mut self, col: usize) -> Result<bool, GameError> {
+        if !self.gravity {
+            return Err(GameError::GravityDisabled);
+        }
+        if col >= self.cols {
+            return Err(GameError::InvalidColumn);
+        }
+        let row = self.landing_row(col).ok_or(GameError::ColumnFull)?;
+        self.make_move_internal(row, col)
+    }
+    
+    /// Place the current player's piece on a (legal) cell and update the game state
+    fn place(&mut self, index: usize) -> bool {
+        let player = self.current_player;
+        self.get_player_board_mut(player).set_bit(index, true);
+        self.move_history.push(index);
+        self.adjudication.on_move(player);
+        
+        let won = self.completes_line(player, index);
+        if won {
+            self.winner = Some(player);
+        } else {
+            self.current_player = player.opponent();
+        }
+        won
+    }
+    
+    /// Check if the piece at `index` is part of a complete line of `player`
+    /// Only lines through the last move need checking
+    pub fn completes_line(&self, player: Player, index: usize) -> bool {
+        let board = self.get_board_for_player(player);
+        self.geometry
+            .get_winning_lines(self.win_length)
+            .iter()
+            .filter(|line| line.get_bit(index))
+            .any(|line| board.count_set_bits_in_mask(line) >= self.win_length as u32)
+    }
+    
+    /// Check if placing a piece of `player` on the empty cell `index` would complete a line
+    pub fn would_complete_line(&self, player: Player, index: usize) -> bool {
+        if self.is_occupied(index) {
+            return false;
+        }
+        let board = self.get_board_for_player(player);
+        let needed = self.win_length as u32 - 1;
+        self.geometry
+            .get_winning_lines(self.win_length)
+            .iter()
+            .filter(|line| line.get_bit(index))
+            .any(|line| board.count_set_bits_in_mask(line) >= needed)
+    }
+    
+    /// Create a copy of the game with a move (cell index) applied (for AI lookahead)
+    pub fn make_move_copy(&self, index: usize) -> Option<MnkGame> {
+        if index >= self.rows * self.cols || !self.is_valid_move(index / self.cols, index % self.cols) {
+            return None;
+        }
+        
+        let mut game_copy = self.clone();
+        game_copy.place(index);
+        Some(game_copy)
+    }
+    
+    /// Get board for player evaluation (internal use)
+    pub fn get_board_for_player(&self, player: Player) -> &DynamicBitPackedBoard {
+        if player == self.first_player() { &self.first_board } else { &self.second_board }
+    }
+    
+    /// Get geometry for evaluation (internal use)
+    pub fn geometry(&self) -> &DynamicQuadraticGrid {
+        &self.geometry
+    }
+    
+    /// Played moves as cell indices (oldest first)
+    pub fn move_history(&self) -> &[usize] {
+        &self.move_history
+    }
+    
+    /// True if the player takes part in this configuration
+    fn is_participant(&self, player: Player) -> bool {
+        player == self.first_player() || player == self.first_player().opponent()
+    }
+    
+    /// True if a cell index holds a piece of either player
+    pub fn is_occupied(&self, index: usize) -> bool {
+        self.first_board.get_bit(index) || self.second_board.get_bit(index)
+    }
+    
+    fn get_player_board_mut(&mut self, player: Player) -> &mut DynamicBitPackedBoard {
+        if player == self.first_player() { &mut self.first_board } else { &mut self.second_board }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_configuration_validation() {
+        assert!(MnkGame::new_internal(2, 3, 3, false).is_err());
+        assert!(MnkGame::new_internal(3, 3, 4, false).is_err());
+        assert!(MnkGame::new_internal(20, 19, 5, false).is_err());
+        
+        let game = MnkGame::new_internal(4, 9, 6, true).unwrap();
+        assert_eq!((game.get_rows(), game.get_cols(), game.get_win_length()), (4, 9, 6));
+        assert_eq!(game.current_player(), Player::Yellow);
+        assert_eq!(MnkGame::new_variant(MnkVariant::TicTacToe).current_player(), Player::Black);
+    }
+    
+    #[test]
+    fn test_tic_tac_toe_win_and_undo() {
+        let mut game = MnkGame::new_variant(MnkVariant::TicTacToe);
+        for (row, col) in [(0, 0), (1, 0), (1, 1), (2, 0)] {
+            assert_eq!(game.make_move_internal(row, col), Ok(false));
+        }
+        assert_eq!(game.make_move_internal(2, 2), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Black));
+        assert!(game.make_move_internal(0, 1).is_err());
+        
+        assert!(game.undo_move());
+        assert_eq!(game.winner(), None);
+        assert_eq!(game.current_player(), Player::Black);
+        assert_eq!(game.move_count(), 4);
+    }
+    
+    #[test]
+    fn test_winning_cells() {
+        let mut game = MnkGame::new_variant(MnkVariant::TicTacToe);
+        for (row, col) in [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)] {
+            game.make_move_internal(row, col).unwrap();
+        }
+        assert_eq!(game.get_winning_cells(), vec![0, 0, 1, 1, 2, 2]);
+        assert_eq!(game.get_completed_lines()[0].player(), Player::Black);
+        
+        game.undo_move();
+        assert!(game.get_completed_lines().is_empty());
+    }
+    
+    #[test]
+    fn test_game_results() {
+        let mut game = MnkGame::new_variant(MnkVariant::TicTacToe);
+        for (row, col) in [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)] {
+            game.make_move_internal(row, col).unwrap();
+        }
+        let result = game.get_result().unwrap();
+        assert_eq!(result.reason(), TerminationReason::LineCompleted);
+        assert_eq!(result.final_move(), vec![2, 2]);
+        
+        game.reset();
+        assert!(!game.resign(Player::Yellow)); // Not playing in tic-tac-toe
+        assert!(game.resign(Player::Black));
+        assert_eq!(game.get_result().unwrap().winner(), Some(Player::White));
+    }
+    
+    #[test]
+    fn test_tic_tac_toe_draw() {
+        let mut game = MnkGame::new_variant(MnkVariant::TicTacToe);
+        for (row, col) in [(0, 0), (0, 1), (0, 2), (1, 1), (1, 0), (1, 2), (2, 1), (2, 0), (2, 2)] {
+            assert_eq!(game.make_move_internal(row, col), Ok(false));
+        }
+        assert!(game.is_draw());
+        assert!(game.get_legal_moves().is_empty());
+    }
+    
+    #[test]
+    fn test_gravity_rules() {
+        let mut game = MnkGame::new_variant(MnkVariant::Connect4);
+        assert!(game.make_move_internal(0, 3).is_err()); // Pieces can't float
+        assert_eq!(game.get_legal_moves(), vec![35, 36, 37, 38, 39, 40, 41]);
+        
+        for col in [3, 3, 4, 4, 5, 5] {
+            assert_eq!(game.drop_piece_internal(col), Ok(false));
+        }
+        assert_eq!(game.landing_row(3), Some(3));
+        assert_eq!(game.drop_piece_internal(6), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Yellow));
+        
+        let mut free = MnkGame::new_variant(MnkVariant::Gomoku);
+        assert!(free.drop_piece_internal(3).is_err());
+        assert_eq!(free.make_move_internal(0, 3), Ok(false));
+    }
+    
+    #[test]
+    fn test_k_larger_than_one_side() {
+        // Six in a row on a 4-row board: only horizontal lines can win
+        let mut game = MnkGame::new_internal(4, 9, 6, false).unwrap();
+        for col in 0..5 {
+            game.make_move_internal(0, col).unwrap();
+            game.make_move_internal(3, col).unwrap();
+        }
+        assert_eq!(game.make_move_internal(0, 5), Ok(true));
+        assert_eq!(game.winner(), Some(Player::Black));
+    }
+}
@@ -1,9 +1,23 @@
+pub mod clock;
 pub mod connect4;
+pub mod game_result;
 pub mod gomoku;
+pub mod gomoku_threats;
 pub mod lgame;
+pub mod match_manager;
+pub mod mnk;
 pub mod trio;
+pub mod trio_competition;
+pub mod verifier;
 
+pub use clock::{GameClock, ManualTimeSource, TimeControl, TimeControlMode, TimeSource};
 pub use connect4::Connect4Game;
+pub use game_result::{GameResult, TerminationReason};
 pub use gomoku::GomokuGame;
+pub use gomoku_threats::{GomokuThreat, ThreatKind};
 pub use lgame::LGame;
-pub use trio::TrioGame;
\ No newline at end of file
+pub use match_manager::{MatchFormat, MatchManager, MatchParticipant, SeriesGame, StartRule, TiebreakRule};
+pub use mnk::MnkGame;
+pub use trio::TrioGame;
+pub use trio_competition::TrioCompetition;
+pub use verifier::{verify_game, verify_trio_claims, RecordedMove, TrioClaimRecord, VerificationIssue, VerificationReport};
@@ -1,40 +1,49 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
+use std::collections::BTreeMap;
 use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng};
-use crate::data::BitPackedBoard;
-use crate::Player;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use crate::data::DynamicBitPackedBoard;
+use crate::{GameError, Player, ReachabilityAnalysis};
 
 /// Trio Game using 3-Layer Architecture for clean separation of concerns
-/// 
+///
 /// Trio is a mathematical puzzle game where players find combinations
 /// of three LINEAR numbers (a, b, c) that satisfy: a×b+c = target OR a×b-c = target
-/// 
+///
 /// Features:
-/// - 7×7 board filled with numbers 1-9
+/// - 7×7 board filled with numbers 1-9 (classic rules, see `TrioRules` for variants)
 /// - BitPacked storage: 4 bits per cell (supports 0-15, perfect for 1-9)
 /// - Linear constraints: Only straight lines (horizontal/vertical/diagonal) allowed
 /// - Optimized algorithm: 120 linear patterns instead of 117,649 brute force
 /// - Memory efficient: 25 bytes vs 49 bytes naive implementation (49% reduction)
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct TrioGame {
     // Composition: Geometry layer handles adjacency logic
     geometry: TrioGrid,
     
     // Composition: Data layer handles efficient storage
-    board: BitPackedBoard<7, 7, 4>,
+    board: DynamicBitPackedBoard,
+    
+    // Rule set: board size, number range and operation templates
+    rules: TrioRules,
     
     // Game-specific state
-    target_number: u8,
+    target_number: u16,
     difficulty: u8,
     move_count: usize,
     found_solutions: Vec<TrioSolution>,
     current_player: Player, // For UI consistency
+    generation_report: Option<TrioGenerationReport>,
 }
 
 /// Trio-specific geometry layer for adjacency calculations
 #[derive(Clone)]
 pub struct TrioGrid {
+    rows: usize,
+    cols: usize,
     adjacent_triplets: Vec<[(usize, usize); 3]>,
 }
 
@@ -47,15 +56,30 @@ pub struct TrioSolution {
     pub operation: TrioOperation,
 }
 
-/// Mathematical operations in Trio
-#[derive(Clone, Debug, PartialEq)]
+/// Mathematical operation templates in Trio (a, b, c in cell order)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TrioOperation {
-    Addition,   // a×b+c = target
-    Subtraction // a×b-c = target
+    Addition = 0,    // a×b+c = target
+    Subtraction = 1, // a×b-c = target
+    Sum = 2,         // a+b+c = target
+    Product = 3,     // a×b×c = target
+    SumTimes = 4,    // (a+b)×c = target
+    Division = 5,    // a×b÷c = target (only when exact)
 }
 
+/// Number of operation templates (size of per-operation count arrays)
+pub const TRIO_OPERATION_COUNT: usize = 6;
+
+/// Largest number a configurable board may contain (keeps a×b×c inside i16 for analysis)
+pub const TRIO_MAX_NUMBER: u8 = 30;
+
+/// Smallest and largest configurable board side
+const TRIO_MIN_SIDE: usize = 3;
+const TRIO_MAX_SIDE: usize = 15;
+
 /// Difficulty levels for board generation
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TrioDifficultyNew {
     Kinderfreundlich = 1,    // Easy: more small numbers, simple calculations
@@ -64,6 +88,387 @@ pub enum TrioDifficultyNew {
     Analytisch = 4,          // Expert: complex patterns, advanced calculations
 }
 
+/// Configurable Trio rule set for classroom variants
+///
+/// Classic rules: 7×7 board, numbers 1-9, a×b+c / a×b-c in cell order.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrioRules {
+    rows: usize,
+    cols: usize,
+    min_number: u8,
+    max_number: u8,
+    operations: u16, // Bit per TrioOperation discriminant
+    allow_permutations: bool,
+}
+
+/// Puzzle code format version for classic boards (stored in the high nibble of the first byte)
+const PUZZLE_CODE_VERSION: u8 = 1;
+
+/// Puzzle code format version for boards with custom rules (header carries the rules)
+const PUZZLE_CODE_VERSION_RULES: u8 = 2;
+
+/// Puzzle code payload: version/difficulty, target, 25 bytes of nibbles, 2 checksum bytes
+const PUZZLE_CODE_BYTES: usize = 29;
+
+/// Header of a custom-rules code: version/difficulty, rows, cols, min, max, operations, target (2)
+const PUZZLE_CODE_RULES_HEADER: usize = 8;
+
+/// Crockford Base32 alphabet (no I, L, O, U to avoid misreading when shared)
+const PUZZLE_CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+impl TrioOperation {
+    /// All templates in evaluation order (the first match names a solution)
+    pub const ALL: [TrioOperation; TRIO_OPERATION_COUNT] = [
+        TrioOperation::Addition,
+        TrioOperation::Subtraction,
+        TrioOperation::Sum,
+        TrioOperation::Product,
+        TrioOperation::SumTimes,
+        TrioOperation::Division,
+    ];
+    
+    /// Evaluate the template; None for non-positive results and inexact division
+    pub fn apply(self, a: u32, b: u32, c: u32) -> Option<u32> {
+        let result = match self {
+            TrioOperation::Addition => a * b + c,
+            TrioOperation::Subtraction => (a * b).checked_sub(c)?,
+            TrioOperation::Sum => a + b + c,
+            TrioOperation::Product => a * b * c,
+            TrioOperation::SumTimes => (a + b) * c,
+            TrioOperation::Division => {
+                if c == 0 || !(a * b).is_multiple_of(c) {
+                    return None;
+                }
+                a * b / c
+            }
+        };
+        
+        if result > 0 { Some(result) } else { None }
+    }
+    
+    /// Human-readable formula, e.g. "3×4+2"
+    pub fn formula(self, a: u8, b: u8, c: u8) -> String {
+        match self {
+            TrioOperation::Addition => format!("{}×{}+{}", a, b, c),
+            TrioOperation::Subtraction => format!("{}×{}-{}", a, b, c),
+            TrioOperation::Sum => format!("{}+{}+{}", a, b, c),
+            TrioOperation::Product => format!("{}×{}×{}", a, b, c),
+            TrioOperation::SumTimes => format!("({}+{})×{}", a, b, c),
+            TrioOperation::Division => format!("{}×{}÷{}", a, b, c),
+        }
+    }
+    
+    /// Largest result the template produces for numbers in min..=max
+    fn max_result(self, min: u32, max: u32) -> u32 {
+        match self {
+            TrioOperation::Addition => max * max + max,
+            TrioOperation::Subtraction => max * max - min.min(max * max - 1),
+            TrioOperation::Sum => 3 * max,
+            TrioOperation::Product => max * max * max,
+            TrioOperation::SumTimes => 2 * max * max,
+            TrioOperation::Division => max * max / min.max(1),
+        }
+    }
+    
+    fn bit(self) -> u16 {
+        1 << (self as u16)
+    }
+}
+
+impl Default for TrioRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TrioRules {
+    /// Classic rules: 7×7, numbers 1-9, a×b+c and a×b-c in cell order
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new() -> Self {
+        Self {
+            rows: 7,
+            cols: 7,
+            min_number: 1,
+            max_number: 9,
+            operations: TrioOperation::Addition.bit() | TrioOperation::Subtraction.bit(),
+            allow_permutations: false,
+        }
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_board_size(&mut self, rows: usize, cols: usize) {
+        self.rows = rows;
+        self.cols = cols;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_number_range(&mut self, min_number: u8, max_number: u8) {
+        self.min_number = min_number;
+        self.max_number = max_number;
+    }
+    
+    /// Enable or disable one operation template
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_operation_enabled(&mut self, operation: TrioOperation, enabled: bool) {
+        if enabled {
+            self.operations |= operation.bit();
+        } else {
+            self.operations &= !operation.bit();
+        }
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_operation_enabled(&self, operation: TrioOperation) -> bool {
+        self.operations & operation.bit() != 0
+    }
+    
+    /// Allow the three cells to be used in any order (a, b, c may be any permutation)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_allow_permutations(&mut self, allow: bool) {
+        self.allow_permutations = allow;
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_allow_permutations(&self) -> bool {
+        self.allow_permutations
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_rows(&self) -> usize {
+        self.rows
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_cols(&self) -> usize {
+        self.cols
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_min_number(&self) -> u8 {
+        self.min_number
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_max_number(&self) -> u8 {
+        self.max_number
+    }
+    
+    /// True for the original 7×7 / 1-9 / a×b±c rule set
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_classic(&self) -> bool {
+        *self == Self::new()
+    }
+    
+    /// Largest target any enabled template can reach
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn max_result(&self) -> u16 {
+        self.enabled_operations()
+            .map(|op| op.max_result(self.min_number as u32, self.max_number as u32))
+            .max()
+            .unwrap_or(0)
+            .min(u16::MAX as u32) as u16
+    }
+}
+
+impl TrioRules {
+    /// Check the rule set before a board is generated from it
+    pub fn validate(&self) -> Result<(), GameError> {
+        if !(TRIO_MIN_SIDE..=TRIO_MAX_SIDE).contains(&self.rows) || !(TRIO_MIN_SIDE..=TRIO_MAX_SIDE).contains(&self.cols) {
+            return Err(GameError::InvalidBoardSize { rows: self.rows, cols: self.cols, min: TRIO_MIN_SIDE, max: TRIO_MAX_SIDE });
+        }
+        if self.min_number == 0 || self.min_number > self.max_number || self.max_number > TRIO_MAX_NUMBER {
+            return Err(GameError::InvalidNumberRange { min: self.min_number, max: self.max_number, limit: TRIO_MAX_NUMBER });
+        }
+        if self.operations == 0 {
+            return Err(GameError::NoOperationsEnabled);
+        }
+        Ok(())
+    }
+    
+    /// Enabled operation templates in evaluation order
+    pub fn enabled_operations(&self) -> impl Iterator<Item = TrioOperation> + '_ {
+        TrioOperation::ALL.into_iter().filter(|&op| self.is_operation_enabled(op))
+    }
+    
+    /// Target numbers that fit the difficulty
+    /// Classic rules keep the hand-tuned ranges, custom rules allow every reachable value
+    pub fn target_range(&self, difficulty: TrioDifficultyNew) -> std::ops::RangeInclusive<u16> {
+        if self.is_classic() {
+            difficulty.target_range()
+        } else {
+            1..=self.max_result()
+        }
+    }
+    
+    /// Bits per cell needed to store the largest number
+    fn bits_per_cell(&self) -> usize {
+        DynamicBitPackedBoard::bits_for_value(self.max_number)
+    }
+    
+    /// Cell orders to try for a triplet: identity only, or all six permutations
+    fn orderings(&self) -> &'static [[usize; 3]] {
+        const IDENTITY: [[usize; 3]; 1] = [[0, 1, 2]];
+        const ALL: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+        if self.allow_permutations { &ALL } else { &IDENTITY }
+    }
+    
+    /// Every distinct result of a triplet with the first operation that produces it
+    fn triplet_results(&self, values: [u8; 3]) -> Vec<(u16, TrioOperation)> {
+        let mut results: Vec<(u16, TrioOperation)> = Vec::new();
+        
+        for op in self.enabled_operations() {
+            for order in self.orderings() {
+                let [a, b, c] = order.map(|i| values[i] as u32);
+                let Some(result) = op.apply(a, b, c).and_then(|r| u16::try_from(r).ok()) else {
+                    continue;
+                };
+                if !results.iter().any(|&(r, _)| r == result) {
+                    results.push((result, op));
+                }
+            }
+        }
+        
+        results
+    }
+    
+    /// First operation (and cell order) that turns the values into the target
+    fn solve(&self, values: [u8; 3], target: u16) -> Option<TrioOperation> {
+        self.enabled_operations().find(|&op| {
+            self.orderings().iter().any(|order| {
+                let [a, b, c] = order.map(|i| values[i] as u32);
+                op.apply(a, b, c) == Some(target as u32)
+            })
+        })
+    }
+}
+
+impl TrioDifficultyNew {
+    /// Map the numeric difficulty used by `TrioGame::new` onto the enum
+    pub fn from_level(level: u8) -> Self {
+        match level {
+            1 => TrioDifficultyNew::Kinderfreundlich,
+            3 => TrioDifficultyNew::Strategisch,
+            4 => TrioDifficultyNew::Analytisch,
+            _ => TrioDifficultyNew::Vollspektrum,
+        }
+    }
+    
+    /// Default number of solutions a generated board should offer
+    /// Easy boards are forgiving, expert boards hide only a handful of trios
+    pub fn solution_window(self) -> (usize, usize) {
+        match self {
+            TrioDifficultyNew::Kinderfreundlich => (8, 20),
+            TrioDifficultyNew::Vollspektrum => (4, 12),
+            TrioDifficultyNew::Strategisch => (2, 6),
+            TrioDifficultyNew::Analytisch => (1, 3),
+        }
+    }
+    
+    /// Target numbers that fit the difficulty (same ranges as the random generator)
+    pub fn target_range(self) -> std::ops::RangeInclusive<u16> {
+        match self {
+            TrioDifficultyNew::Kinderfreundlich => 3..=15,
+            TrioDifficultyNew::Vollspektrum => 5..=25,
+            TrioDifficultyNew::Strategisch => 10..=40,
+            TrioDifficultyNew::Analytisch => 15..=60,
+        }
+    }
+}
+
+/// Outcome of a solution-count controlled board generation
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug)]
+pub struct TrioGenerationReport {
+    solution_count: usize,
+    operation_counts: [usize; TRIO_OPERATION_COUNT],
+    min_solutions: usize,
+    max_solutions: usize,
+    attempts: usize,
+    repairs: usize,
+    within_window: bool,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TrioGenerationReport {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn solution_count(&self) -> usize {
+        self.solution_count
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn addition_solutions(&self) -> usize {
+        self.operation_counts[TrioOperation::Addition as usize]
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn subtraction_solutions(&self) -> usize {
+        self.operation_counts[TrioOperation::Subtraction as usize]
+    }
+    
+    /// Solutions per operation template, indexed by `TrioOperation` value
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn operation_counts(&self) -> Vec<usize> {
+        self.operation_counts.to_vec()
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn min_solutions(&self) -> usize {
+        self.min_solutions
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn max_solutions(&self) -> usize {
+        self.max_solutions
+    }
+    
+    /// Number of freshly shuffled boards that were tried
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+    
+    /// Number of single-cell repairs applied after re-rolling gave up
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn repairs(&self) -> usize {
+        self.repairs
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn within_window(&self) -> bool {
+        self.within_window
+    }
+    
+    pub fn summary(&self) -> String {
+        let mix: Vec<String> = TrioOperation::ALL
+            .iter()
+            .filter(|&&op| self.operation_counts[op as usize] > 0)
+            .map(|&op| format!("{} {:?}", self.operation_counts[op as usize], op))
+            .collect();
+        
+        format!(
+            "{} solutions ({}), window {}-{}, {} attempts, {} repairs{}",
+            self.solution_count,
+            mix.join(", "),
+            self.min_solutions,
+            self.max_solutions,
+            self.attempts,
+            self.repairs,
+            if self.within_window { "" } else { " (window missed)" }
+        )
+    }
+}
+
+/// Fresh boards tried before falling back to repairing the best one
+const MAX_GENERATION_REROLLS: usize = 64;
+
+/// Single-cell mutations tried while repairing a board
+const MAX_GENERATION_REPAIRS: usize = 2000;
+
+/// Solutions per target, split by the operation that names them
+type SolutionHistogram = BTreeMap<u16, [usize; TRIO_OPERATION_COUNT]>;
+
 impl Default for TrioGrid {
     fn default() -> Self {
         Self::new()
@@ -73,35 +478,40 @@ impl Default for TrioGrid {
 impl TrioGrid {
     /// Create new TrioGrid with all valid adjacency patterns
     pub fn new() -> Self {
-        let adjacent_triplets = Self::generate_adjacent_triplets();
-        Self { adjacent_triplets }
+        Self::with_size(7, 7)
+    }
+    
+    /// Create a TrioGrid for a custom board size
+    pub fn with_size(rows: usize, cols: usize) -> Self {
+        let adjacent_triplets = Self::generate_adjacent_triplets(rows, cols);
+        Self { rows, cols, adjacent_triplets }
     }
     
-    /// Generate all possible linear triplets on a 7x7 board
+    /// Generate all possible linear triplets on a rows×cols board
     /// Trio rules: Only straight lines allowed (horizontal, vertical, diagonal)
-    fn generate_adjacent_triplets() -> Vec<[(usize, usize); 3]> {
+    fn generate_adjacent_triplets(rows: usize, cols: usize) -> Vec<[(usize, usize); 3]> {
         let mut triplets = Vec::new();
         
         // Linear patterns only: horizontal, vertical, diagonal
-        for row in 0..7 {
-            for col in 0..7 {
+        for row in 0..rows {
+            for col in 0..cols {
                 // Horizontal triplets (left to right)
-                if col + 2 < 7 {
+                if col + 2 < cols {
                     triplets.push([(row, col), (row, col + 1), (row, col + 2)]);
                 }
                 
                 // Vertical triplets (top to bottom)
-                if row + 2 < 7 {
+                if row + 2 < rows {
                     triplets.push([(row, col), (row + 1, col), (row + 2, col)]);
                 }
                 
                 // Diagonal triplets (top-left to bottom-right)
-                if row + 2 < 7 && col + 2 < 7 {
+                if row + 2 < rows && col + 2 < cols {
                     triplets.push([(row, col), (row + 1, col + 1), (row + 2, col + 2)]);
                 }
                 
                 // Diagonal triplets (top-right to bottom-left)
-                if row + 2 < 7 && col >= 2 {
+                if row + 2 < rows && col >= 2 {
                     triplets.push([(row, col), (row + 1, col - 1), (row + 2, col - 2)]);
                 }
             }
@@ -115,6 +525,11 @@ impl TrioGrid {
         &self.adjacent_triplets
     }
     
+    /// Board dimensions the triplets were generated for
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+    
     /// Validate if three positions form an adjacent pattern
     pub fn validate_adjacency(&self, pos1: (usize, usize), pos2: (usize, usize), pos3: (usize, usize)) -> bool {
         let positions = [pos1, pos2, pos3];
@@ -134,156 +549,265 @@ impl TrioGrid {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TrioGame {
     /// Create new Trio game with specified difficulty
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new(difficulty: u8) -> Self {
-        let geometry = TrioGrid::new();
-        let mut board = BitPackedBoard::new();
-        let target = Self::generate_board_and_target(&mut board, difficulty);
+        let rules = TrioRules::new();
+        let mut board = Self::empty_board(&rules);
+        let target = Self::generate_board_and_target(&mut board, &rules, difficulty);
+        Self::from_parts(board, rules, target, difficulty)
+    }
+    
+    /// Create a game with a custom rule set (board size, number range, operations)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn new_with_rules(rules: TrioRules, difficulty: u8) -> Result<TrioGame, GameError> {
+        Self::new_with_rules_internal(rules, difficulty)
+    }
+    
+    /// Create a game whose board offers between `min_solutions` and `max_solutions` trios
+    /// Boards are re-rolled and, if necessary, repaired cell by cell until they qualify
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn new_with_solution_window(difficulty: TrioDifficultyNew, min_solutions: usize, max_solutions: usize) -> TrioGame {
+        Self::generate_game_with_solution_window(TrioRules::new(), difficulty, min_solutions, max_solutions, &mut thread_rng())
+    }
+    
+    /// Create a game using the default solution window of the difficulty
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn new_guaranteed(difficulty: TrioDifficultyNew) -> TrioGame {
+        let (min_solutions, max_solutions) = difficulty.solution_window();
+        Self::new_with_solution_window(difficulty, min_solutions, max_solutions)
+    }
+    
+    /// Report of the solution-count controlled generation (None for plain random boards)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_generation_report(&self) -> Option<TrioGenerationReport> {
+        self.generation_report.clone()
+    }
+    
+    /// Recreate a game from a shared puzzle code (see `to_code`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn from_code(code: &str) -> Result<TrioGame, GameError> {
+        Self::from_code_internal(code)
+    }
+    
+    /// Deterministic "daily puzzle": every player gets the same board for a given date
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn daily_puzzle(year: i32, month: u32, day: u32, difficulty: u8) -> Result<TrioGame, GameError> {
+        Self::daily_puzzle_internal(year, month, day, difficulty)
+    }
+    
+    /// Encode board, target and difficulty as a compact, checksummed puzzle code
+    ///
+    /// Classic layout: 29 bytes (version/difficulty, target, 49 cells à 4 bits, Fletcher-16)
+    /// rendered as 47 Crockford Base32 characters. Custom rules add an 8-byte rules header.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn to_code(&self) -> String {
+        let mut bytes = Vec::new();
         
-        Self {
-            geometry,
-            board,
-            target_number: target,
-            difficulty,
-            move_count: 0,
-            found_solutions: Vec::new(),
-            current_player: Player::Yellow, // Default for UI consistency
+        if self.rules.is_classic() && self.target_number <= u8::MAX as u16 {
+            bytes.push((PUZZLE_CODE_VERSION << 4) | (self.difficulty & 0x0F));
+            bytes.push(self.target_number as u8);
+        } else {
+            let operations = self.rules.operations as u8 | ((self.rules.allow_permutations as u8) << 7);
+            bytes.push((PUZZLE_CODE_VERSION_RULES << 4) | (self.difficulty & 0x0F));
+            bytes.extend_from_slice(&[
+                self.rules.rows as u8,
+                self.rules.cols as u8,
+                self.rules.min_number,
+                self.rules.max_number,
+                operations,
+            ]);
+            bytes.extend_from_slice(&self.target_number.to_be_bytes());
+        }
+        
+        // Same cell layout as the bit-packed board, MSB first (two cells per byte for 4 bits)
+        let bits_per_cell = self.board.bits_per_cell();
+        let mut buffer: u32 = 0;
+        let mut bits = 0;
+        for index in 0..self.board.total_cells() {
+            let (row, col) = (index / self.rules.cols, index % self.rules.cols);
+            buffer = (buffer << bits_per_cell) | self.board.get_cell(row, col) as u32;
+            bits += bits_per_cell;
+            while bits >= 8 {
+                bits -= 8;
+                bytes.push((buffer >> bits) as u8);
+            }
         }
+        if bits > 0 {
+            bytes.push((buffer << (8 - bits)) as u8);
+        }
+        
+        let checksum = Self::fletcher16(&bytes);
+        bytes.extend_from_slice(&checksum.to_be_bytes());
+        
+        Self::encode_base32(&bytes)
     }
     
     /// Get number at specific board position
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_number(&self, row: usize, col: usize) -> u8 {
         self.board.get_cell(row, col)
     }
     
+    /// Get board state as string for debugging (numbers right-aligned, one row per line)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn board_string(&self) -> String {
+        let mut result = String::new();
+        
+        for row in 0..self.rules.rows {
+            for col in 0..self.rules.cols {
+                result.push_str(&format!("{:>3}", self.get_number(row, col)));
+            }
+            result.push('\n');
+        }
+        
+        result
+    }
+    
     /// Get the current target number to achieve
-    #[wasm_bindgen]
-    pub fn get_target_number(&self) -> u8 {
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_target_number(&self) -> u16 {
         self.target_number
     }
     
+    /// Set a new target number for the same board (e.g. next round of a competition)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_target_number(&mut self, target: u16) {
+        self.target_number = target;
+    }
+    
     /// Get current difficulty level
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_difficulty(&self) -> u8 {
         self.difficulty
     }
     
+    /// Get the active rule set
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_rules(&self) -> TrioRules {
+        self.rules.clone()
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_rows(&self) -> usize {
+        self.rules.rows
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_cols(&self) -> usize {
+        self.rules.cols
+    }
+    
     /// Validate a trio combination with adjacency check
     /// Returns the calculated result if valid, or -1 if invalid
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn validate_trio(&self, row1: usize, col1: usize, row2: usize, col2: usize, row3: usize, col3: usize) -> i32 {
         // First check adjacency constraint
         if !self.geometry.validate_adjacency((row1, col1), (row2, col2), (row3, col3)) {
             return -1; // Not adjacent - invalid
         }
         
-        // Get the three numbers
-        let a = self.board.get_cell(row1, col1);
-        let b = self.board.get_cell(row2, col2);
-        let c = self.board.get_cell(row3, col3);
-        
-        // Ensure we have valid numbers (1-9)
-        if a == 0 || b == 0 || c == 0 || a > 9 || b > 9 || c > 9 {
-            return -1;
-        }
-        
-        // Calculate both possible results
-        let result1 = (a as u16) * (b as u16) + (c as u16);
-        let result2 = (a as u16) * (b as u16) - (c as u16);
-        
-        // Check if either result matches the target
-        if result1 == self.target_number as u16 {
-            return result1 as i32;
-        } else if result2 == self.target_number as u16 {
-            return result2 as i32;
+        match self.triplet_operation([(row1, col1), (row2, col2), (row3, col3)]) {
+            Some(_) => self.target_number as i32,
+            None => -1, // Invalid combination
         }
-        
-        -1 // Invalid combination
     }
     
     /// Generate new board with specified difficulty
-    #[wasm_bindgen]
-    pub fn generate_new_board(&mut self, difficulty: u8) -> u8 {
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn generate_new_board(&mut self, difficulty: u8) -> u16 {
         self.difficulty = difficulty;
-        self.target_number = Self::generate_board_and_target(&mut self.board, difficulty);
+        self.target_number = Self::generate_board_and_target(&mut self.board, &self.rules, difficulty);
+        self.generation_report = None;
         self.target_number
     }
     
     /// Find all possible trio solutions using optimized adjacency algorithm
     /// Optimization: Only check valid adjacent triplets (~200) instead of all combinations (117,649)
-    #[wasm_bindgen]
-    pub fn find_all_solutions(&self) -> Vec<u8> {
+    /// Flat layout, 7 values per solution: row1, col1, row2, col2, row3, col3, result
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn find_all_solutions(&self) -> Vec<u16> {
         let mut solutions = Vec::new();
         
         // Use geometry layer to get only valid adjacent triplets
         let adjacent_triplets = self.geometry.get_adjacent_triplets();
         
         for &triplet in adjacent_triplets {
-            let [(row1, col1), (row2, col2), (row3, col3)] = triplet;
-            
-            // Get the three numbers
-            let a = self.board.get_cell(row1, col1);
-            let b = self.board.get_cell(row2, col2);
-            let c = self.board.get_cell(row3, col3);
-            
-            // Ensure we have valid numbers (1-9)
-            if a == 0 || b == 0 || c == 0 || a > 9 || b > 9 || c > 9 {
-                continue;
-            }
-            
-            // Calculate both possible results
-            let result1 = (a as u16) * (b as u16) + (c as u16);
-            let result2 = (a as u16) * (b as u16) - (c as u16);
-            
-            // Check if either result matches the target
-            if result1 == self.target_number as u16 {
-                solutions.push(row1 as u8);
-                solutions.push(col1 as u8);
-                solutions.push(row2 as u8);
-                solutions.push(col2 as u8);
-                solutions.push(row3 as u8);
-                solutions.push(col3 as u8);
-                solutions.push(result1 as u8);
-            } else if result2 == self.target_number as u16 {
-                solutions.push(row1 as u8);
-                solutions.push(col1 as u8);
-                solutions.push(row2 as u8);
-                solutions.push(col2 as u8);
-                solutions.push(row3 as u8);
-                solutions.push(col3 as u8);
-                solutions.push(result2 as u8);
+            if self.triplet_operation(triplet).is_some() {
+                let [(row1, col1), (row2, col2), (row3, col3)] = triplet;
+                solutions.push(row1 as u16);
+                solutions.push(col1 as u16);
+                solutions.push(row2 as u16);
+                solutions.push(col2 as u16);
+                solutions.push(row3 as u16);
+                solutions.push(col3 as u16);
+                solutions.push(self.target_number);
             }
         }
         
         solutions
     }
     
+    /// Analyze which targets the current board can reach under the active rules
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn analyze_reachable_targets(&self) -> ReachabilityAnalysis {
+        let histogram = Self::solution_histogram(&self.geometry, &self.board, &self.rules, &[]);
+        let max_result = self.rules.max_result().min(i16::MAX as u16) as i16;
+        
+        let (reachable, unreachable): (Vec<i16>, Vec<i16>) =
+            (1..=max_result).partition(|&target| histogram.contains_key(&(target as u16)));
+        
+        let total_possible = max_result.max(1) as usize;
+        let total_reachable = reachable.len();
+        let coverage_percentage = (total_reachable as f32 / total_possible as f32) * 100.0;
+        
+        let min_reachable = reachable.iter().min().copied().unwrap_or(0);
+        let max_reachable = reachable.iter().max().copied().unwrap_or(0);
+        
+        ReachabilityAnalysis {
+            reachable_targets: reachable,
+            unreachable_targets: unreachable,
+            total_reachable,
+            coverage_percentage,
+            min_reachable,
+            max_reachable,
+        }
+    }
+    
+    /// Check if the board has at least one solution for the current target
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn has_valid_solutions(&self) -> bool {
+        // Use optimized adjacency-based search
+        self.geometry
+            .get_adjacent_triplets()
+            .iter()
+            .any(|&triplet| self.triplet_operation(triplet).is_some())
+    }
+    
     /// Get memory usage of the BitPacked board
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn memory_usage(&self) -> usize {
-        // BitPackedBoard<7,7,4> uses (7*7*4 + 63) / 64 = 4 u64s = 32 bytes
+        // Classic BitPacked board uses (7*7*4 + 63) / 64 = 4 u64s = 32 bytes
         // Plus metadata overhead
-        32 + std::mem::size_of::<Self>() - std::mem::size_of::<BitPackedBoard<7,7,4>>()
+        self.board.memory_usage() + std::mem::size_of::<Self>() - std::mem::size_of::<DynamicBitPackedBoard>()
     }
     
     /// Get memory efficiency compared to naive implementation
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn memory_efficiency(&self) -> f32 {
-        let naive_size = 7 * 7; // 49 bytes for u8 array
+        let naive_size = self.board.total_cells(); // one byte per cell for u8 array
         let bitpacked_size = self.memory_usage();
         ((naive_size as f32 - bitpacked_size as f32) / naive_size as f32) * 100.0
     }
     
     /// Get entire board as flat array for JavaScript
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_board_array(&self) -> Vec<u8> {
-        let mut result = Vec::with_capacity(49);
-        for row in 0..7 {
-            for col in 0..7 {
+        let mut result = Vec::with_capacity(self.board.total_cells());
+        for row in 0..self.rules.rows {
+            for col in 0..self.rules.cols {
                 result.push(self.board.get_cell(row, col));
             }
         }
@@ -291,13 +815,13 @@ impl TrioGame {
     }
     
     /// Get count of adjacent patterns for performance info
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_adjacency_pattern_count(&self) -> usize {
         self.geometry.count_adjacent_patterns()
     }
     
     /// Connect4-compatible API: Get current player
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_current_player(&self) -> u8 {
         match self.current_player {
             Player::Yellow => 1,
@@ -307,8 +831,11 @@ impl TrioGame {
     }
     
     /// Connect4-compatible API: Make a move (mark found solution)
-    #[wasm_bindgen]
-    pub fn make_move(&mut self, row1: usize, col1: usize, row2: usize, col2: usize, row3: usize, col3: usize) -> bool {
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn make_move(&mut self, row1: usize, col1: usize, row2: usize, col2: usize, row3: usize, col3: usize) -> Result<(), GameError> {
+        if [row1, row2, row3].iter().any(|&row| row >= self.rules.rows) || [col1, col2, col3].iter().any(|&col| col >= self.rules.cols) {
+            return Err(GameError::OutOfBounds);
+        }
         let result = self.validate_trio(row1, col1, row2, col2, row3, col3);
         if result != -1 {
             // Store found solution
@@ -320,39 +847,37 @@ impl TrioGame {
                     self.board.get_cell(row3, col3)
                 ],
                 result: result as u16,
-                operation: if (self.board.get_cell(row1, col1) as u16) * (self.board.get_cell(row2, col2) as u16) + (self.board.get_cell(row3, col3) as u16) == result as u16 {
-                    TrioOperation::Addition
-                } else {
-                    TrioOperation::Subtraction
-                }
+                operation: self.triplet_operation([(row1, col1), (row2, col2), (row3, col3)])
+                    .unwrap_or(TrioOperation::Addition),
             };
             
             self.found_solutions.push(solution);
             self.move_count += 1;
-            true
+            Ok(())
         } else {
-            false
+            Err(GameError::NotATrio)
         }
     }
     
     /// Connect4-compatible API: Reset game
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn reset(&mut self) {
         self.found_solutions.clear();
         self.move_count = 0;
         self.current_player = Player::Yellow;
         // Generate new board
-        self.target_number = Self::generate_board_and_target(&mut self.board, self.difficulty);
+        self.target_number = Self::generate_board_and_target(&mut self.board, &self.rules, self.difficulty);
+        self.generation_report = None;
     }
     
     /// Connect4-compatible API: Get move count
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_move_count(&self) -> usize {
         self.move_count
     }
     
     /// Connect4-compatible API: Get winner (puzzle completed when all solutions found)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_winner(&self) -> u8 {
         let all_solutions = self.find_all_solutions();
         let total_solutions = all_solutions.len() / 7; // Each solution has 7 elements
@@ -365,7 +890,7 @@ impl TrioGame {
     }
     
     /// Get game phase for UI consistency
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get_game_phase(&self) -> u8 {
         let all_solutions = self.find_all_solutions();
         let total_solutions = all_solutions.len() / 7;
@@ -385,11 +910,423 @@ impl TrioGame {
     }
 }
 
+// Puzzle code implementation (pure Rust, testable without a JS runtime)
+impl TrioGame {
+    /// Create a game with custom rules (pure Rust, testable without a JS runtime)
+    pub fn new_with_rules_internal(rules: TrioRules, difficulty: u8) -> Result<TrioGame, GameError> {
+        rules.validate()?;
+        let mut board = Self::empty_board(&rules);
+        let target = Self::generate_board_and_target(&mut board, &rules, difficulty);
+        Ok(Self::from_parts(board, rules, target, difficulty))
+    }
+    
+    /// Decode a puzzle code produced by `to_code`
+    pub fn from_code_internal(code: &str) -> Result<TrioGame, GameError> {
+        let bytes = Self::decode_base32(code)?;
+        if bytes.len() < 3 {
+            return Err(GameError::InvalidPuzzleCode);
+        }
+        
+        let (payload, stored) = bytes.split_at(bytes.len() - 2);
+        let stored = u16::from_be_bytes([stored[0], stored[1]]);
+        if Self::fletcher16(payload) != stored {
+            return Err(GameError::PuzzleCodeChecksum);
+        }
+        
+        let difficulty = payload[0] & 0x0F;
+        if !(1..=4).contains(&difficulty) {
+            return Err(GameError::InvalidDifficulty(difficulty));
+        }
+        
+        let (rules, target, cells) = match payload[0] >> 4 {
+            PUZZLE_CODE_VERSION => {
+                if bytes.len() != PUZZLE_CODE_BYTES {
+                    return Err(GameError::InvalidPuzzleCode);
+                }
+                (TrioRules::new(), payload[1] as u16, &payload[2..])
+            }
+            PUZZLE_CODE_VERSION_RULES => {
+                if payload.len() < PUZZLE_CODE_RULES_HEADER {
+                    return Err(GameError::InvalidPuzzleCode);
+                }
+                let mut rules = TrioRules::new();
+                rules.set_board_size(payload[1] as usize, payload[2] as usize);
+                rules.set_number_range(payload[3], payload[4]);
+                rules.operations = (payload[5] & 0x3F) as u16;
+                rules.allow_permutations = payload[5] & 0x80 != 0;
+                rules.validate()?;
+                
+                let target = u16::from_be_bytes([payload[6], payload[7]]);
+                (rules, target, &payload[PUZZLE_CODE_RULES_HEADER..])
+            }
+            version => return Err(GameError::UnsupportedPuzzleCodeVersion(version)),
+        };
+        
+        let bits_per_cell = rules.bits_per_cell();
+        let total_cells = rules.rows * rules.cols;
+        if cells.len() != (total_cells * bits_per_cell).div_ceil(8) {
+            return Err(GameError::InvalidPuzzleCode);
+        }
+        
+        let mut board = Self::empty_board(&rules);
+        for index in 0..total_cells {
+            // Read `bits_per_cell` bits starting at bit offset index * bits_per_cell (MSB first)
+            let mut value = 0u8;
+            for bit in index * bits_per_cell..(index + 1) * bits_per_cell {
+                value = (value << 1) | ((cells[bit / 8] >> (7 - bit % 8)) & 1);
+            }
+            if !(rules.min_number..=rules.max_number).contains(&value) {
+                return Err(GameError::InvalidPuzzleCode);
+            }
+            board.set_cell(index / rules.cols, index % rules.cols, value).map_err(|_| GameError::InvalidPuzzleCode)?;
+        }
+        
+        Ok(Self::from_parts(board, rules, target, difficulty))
+    }
+    
+    /// Build the daily puzzle for a calendar date
+    /// Board and target are derived from a seed, so all clients agree without a server
+    pub fn daily_puzzle_internal(year: i32, month: u32, day: u32, difficulty: u8) -> Result<TrioGame, GameError> {
+        let day_number = Self::days_from_civil(year, month, day)
+            .ok_or(GameError::InvalidDate { year, month, day })?;
+        if !(1..=4).contains(&difficulty) {
+            return Err(GameError::InvalidDifficulty(difficulty));
+        }
+        
+        // Mix difficulty into the seed so every level gets its own daily board
+        // ChaCha8 has a fixed output stream, unlike StdRng which may change between rand releases
+        let seed = ((day_number as u64) << 8) | difficulty as u64;
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        
+        let rules = TrioRules::new();
+        let mut board = Self::empty_board(&rules);
+        let target = Self::fill_board_and_pick_target(&mut board, &rules, difficulty, &mut rng);
+        
+        Ok(Self::from_parts(board, rules, target, difficulty))
+    }
+    
+    /// Days since 1970-01-01 for a proleptic Gregorian date (None if the date doesn't exist)
+    fn days_from_civil(year: i32, month: u32, day: u32) -> Option<i64> {
+        if !(1..=12).contains(&month) || day == 0 {
+            return None;
+        }
+        
+        let is_leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days_in_month = match month {
+            2 if is_leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if day > days_in_month {
+            return None;
+        }
+        
+        // Howard Hinnant's days_from_civil
+        let y = if month <= 2 { year as i64 - 1 } else { year as i64 };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let mp = (month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        Some(era * 146_097 + doe - 719_468)
+    }
+    
+    /// Fletcher-16 checksum over the code payload
+    fn fletcher16(data: &[u8]) -> u16 {
+        let mut sum1: u16 = 0;
+        let mut sum2: u16 = 0;
+        for &byte in data {
+            sum1 = (sum1 + byte as u16) % 255;
+            sum2 = (sum2 + sum1) % 255;
+        }
+        (sum2 << 8) | sum1
+    }
+    
+    /// Encode bytes as Crockford Base32 (MSB first, final group zero-padded)
+    fn encode_base32(bytes: &[u8]) -> String {
+        let mut code = String::with_capacity((bytes.len() * 8).div_ceil(5));
+        let mut buffer: u32 = 0;
+        let mut bits = 0;
+        
+        for &byte in bytes {
+            buffer = (buffer << 8) | byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                code.push(PUZZLE_CODE_ALPHABET[((buffer >> bits) & 0x1F) as usize] as char);
+            }
+        }
+        if bits > 0 {
+            code.push(PUZZLE_CODE_ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize] as char);
+        }
+        
+        code
+    }
+    
+    /// Decode Crockford Base32, tolerating lowercase, separators and O/I/L look-alikes
+    fn decode_base32(code: &str) -> Result<Vec<u8>, GameError> {
+        let mut bytes = Vec::new();
+        let mut buffer: u32 = 0;
+        let mut bits = 0;
+        
+        for ch in code.chars().filter(|c| !c.is_whitespace() && *c != '-') {
+            let ch = match ch.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                other => other,
+            };
+            let value = PUZZLE_CODE_ALPHABET
+                .iter()
+                .position(|&c| c as char == ch)
+                .ok_or(GameError::InvalidPuzzleCode)?;
+            
+            buffer = (buffer << 5) | value as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((buffer >> bits) as u8);
+            }
+        }
+        
+        Ok(bytes)
+    }
+}
+
+// Solution-count controlled board generation
+impl TrioGame {
+    /// Count solutions per target in one pass over all triplets
+    /// Each triplet counts once per reachable target, under the first operation that reaches it
+    /// Triplets touching a cell flagged in `blocked` (row-major, may be empty) are skipped
+    fn solution_histogram(geometry: &TrioGrid, board: &DynamicBitPackedBoard, rules: &TrioRules, blocked: &[bool]) -> SolutionHistogram {
+        let mut histogram = SolutionHistogram::new();
+        let cols = geometry.dimensions().1;
+        
+        for &triplet in geometry.get_adjacent_triplets() {
+            if triplet.iter().any(|&(row, col)| blocked.get(row * cols + col).copied().unwrap_or(false)) {
+                continue;
+            }
+            
+            let values = triplet.map(|(row, col)| board.get_cell(row, col));
+            if values.contains(&0) {
+                continue;
+            }
+            
+            for (result, op) in rules.triplet_results(values) {
+                histogram.entry(result).or_insert([0; TRIO_OPERATION_COUNT])[op as usize] += 1;
+            }
+        }
+        
+        histogram
+    }
+    
+    /// Targets with at least one solution that avoids the blocked cells (row-major flags)
+    /// Returns (target, solution count) pairs in ascending target order
+    pub(crate) fn reachable_targets_avoiding(&self, blocked: &[bool]) -> Vec<(u16, usize)> {
+        Self::solution_histogram(&self.geometry, &self.board, &self.rules, blocked)
+            .iter()
+            .map(|(&target, counts)| (target, counts.iter().sum()))
+            .collect()
+    }
+    
+    /// Distance of a solution count from the requested window (0 = inside)
+    fn window_distance(count: usize, min_solutions: usize, max_solutions: usize) -> usize {
+        if count < min_solutions {
+            min_solutions - count
+        } else { count.saturating_sub(max_solutions) }
+    }
+    
+    /// Best target of the difficulty's range for this board: (distance, target, operation counts)
+    /// Ties are broken randomly so boards don't always get the lowest qualifying target
+    fn best_target_for_window<R: Rng>(
+        histogram: &SolutionHistogram,
+        targets: std::ops::RangeInclusive<u16>,
+        min_solutions: usize,
+        max_solutions: usize,
+        rng: &mut R,
+    ) -> (usize, u16, [usize; TRIO_OPERATION_COUNT]) {
+        let mut best: Option<(usize, u16, [usize; TRIO_OPERATION_COUNT])> = None;
+        let mut ties = 0;
+        
+        for target in targets {
+            let counts = histogram.get(&target).copied().unwrap_or([0; TRIO_OPERATION_COUNT]);
+            let distance = Self::window_distance(counts.iter().sum(), min_solutions, max_solutions);
+            
+            match best {
+                Some((best_distance, ..)) if distance > best_distance => {}
+                Some((best_distance, ..)) if distance == best_distance => {
+                    // Reservoir sampling among equally good targets
+                    ties += 1;
+                    if rng.gen_range(0..=ties) == 0 {
+                        best = Some((distance, target, counts));
+                    }
+                }
+                _ => {
+                    ties = 0;
+                    best = Some((distance, target, counts));
+                }
+            }
+        }
+        
+        best.unwrap_or((min_solutions, 0, [0; TRIO_OPERATION_COUNT]))
+    }
+    
+    /// Re-roll boards until one has a target inside the window, then repair the best
+    /// candidate cell by cell if re-rolling alone didn't succeed
+    fn generate_game_with_solution_window<R: Rng>(
+        rules: TrioRules,
+        difficulty: TrioDifficultyNew,
+        min_solutions: usize,
+        max_solutions: usize,
+        rng: &mut R,
+    ) -> TrioGame {
+        let geometry = TrioGrid::with_size(rules.rows, rules.cols);
+        let max_solutions = max_solutions.max(min_solutions);
+        let targets = rules.target_range(difficulty);
+        
+        let mut best_board = Self::empty_board(&rules);
+        let mut best = (usize::MAX, 0u16, [0usize; TRIO_OPERATION_COUNT]);
+        let mut attempts = 0;
+        
+        for _ in 0..MAX_GENERATION_REROLLS {
+            attempts += 1;
+            let mut candidate = Self::empty_board(&rules);
+            Self::fill_board(&mut candidate, &rules, difficulty as u8, rng);
+            
+            let histogram = Self::solution_histogram(&geometry, &candidate, &rules, &[]);
+            let result = Self::best_target_for_window(&histogram, targets.clone(), min_solutions, max_solutions, rng);
+            if result.0 < best.0 {
+                best = result;
+                best_board = candidate;
+            }
+            if best.0 == 0 {
+                break;
+            }
+        }
+        
+        // Repair: mutate single cells, keep mutations that don't move away from the window
+        let mut repairs = 0;
+        for _ in 0..MAX_GENERATION_REPAIRS {
+            if best.0 == 0 {
+                break;
+            }
+            
+            let row = rng.gen_range(0..rules.rows);
+            let col = rng.gen_range(0..rules.cols);
+            let previous = best_board.get_cell(row, col);
+            let _ = best_board.set_cell(row, col, rng.gen_range(rules.min_number..=rules.max_number));
+            
+            let histogram = Self::solution_histogram(&geometry, &best_board, &rules, &[]);
+            let result = Self::best_target_for_window(&histogram, targets.clone(), min_solutions, max_solutions, rng);
+            if result.0 <= best.0 {
+                if result.0 < best.0 {
+                    repairs += 1;
+                }
+                best = result;
+            } else {
+                let _ = best_board.set_cell(row, col, previous);
+            }
+        }
+        
+        let (distance, target, operation_counts) = best;
+        let report = TrioGenerationReport {
+            solution_count: operation_counts.iter().sum(),
+            operation_counts,
+            min_solutions,
+            max_solutions,
+            attempts,
+            repairs,
+            within_window: distance == 0,
+        };
+        
+        let mut game = Self::from_parts(best_board, rules, target, difficulty as u8);
+        game.generation_report = Some(report);
+        game
+    }
+}
+
 // Private implementation methods
 impl TrioGame {
+    /// Assemble a fresh game around an already generated board
+    fn from_parts(board: DynamicBitPackedBoard, rules: TrioRules, target_number: u16, difficulty: u8) -> Self {
+        Self {
+            geometry: TrioGrid::with_size(rules.rows, rules.cols),
+            board,
+            rules,
+            target_number,
+            difficulty,
+            move_count: 0,
+            found_solutions: Vec::new(),
+            current_player: Player::Yellow, // Default for UI consistency
+            generation_report: None,
+        }
+    }
+    
+    /// Empty bit-packed board sized for the rules (4 bits per cell for classic 1-9)
+    fn empty_board(rules: &TrioRules) -> DynamicBitPackedBoard {
+        DynamicBitPackedBoard::new(rules.rows, rules.cols, rules.bits_per_cell())
+    }
+    
+    /// Which operation (if any) turns the triplet into the target under the active rules
+    pub(crate) fn triplet_operation(&self, triplet: [(usize, usize); 3]) -> Option<TrioOperation> {
+        let values = triplet.map(|(row, col)| self.board.get_cell(row, col));
+        
+        // Ensure we have valid numbers for the rule set
+        if values.iter().any(|value| !(self.rules.min_number..=self.rules.max_number).contains(value)) {
+            return None;
+        }
+        
+        self.rules.solve(values, self.target_number)
+    }
+    
     /// Generate a balanced board with guaranteed solutions
-    fn generate_board_and_target(board: &mut BitPackedBoard<7, 7, 4>, difficulty: u8) -> u8 {
-        let mut rng = thread_rng();
+    fn generate_board_and_target(board: &mut DynamicBitPackedBoard, rules: &TrioRules, difficulty: u8) -> u16 {
+        Self::fill_board_and_pick_target(board, rules, difficulty, &mut thread_rng())
+    }
+    
+    /// Fill the board for the difficulty and pick a target using the given RNG
+    /// Classic rules pick from the hand-tuned target ranges; custom rules pick a reachable
+    /// target whose solution count matches the difficulty (many for easy, few for expert)
+    fn fill_board_and_pick_target<R: Rng>(board: &mut DynamicBitPackedBoard, rules: &TrioRules, difficulty: u8, rng: &mut R) -> u16 {
+        Self::fill_board(board, rules, difficulty, rng);
+        
+        if rules.is_classic() {
+            // Generate target based on difficulty
+            return match difficulty {
+                1 => rng.gen_range(3..=15), // Easy targets
+                2 => rng.gen_range(5..=25), // Medium targets
+                3 => rng.gen_range(10..=40), // Hard targets
+                4 => rng.gen_range(15..=60), // Expert targets
+                _ => rng.gen_range(5..=25),   // Default
+            };
+        }
+        
+        let geometry = TrioGrid::with_size(rules.rows, rules.cols);
+        let histogram = Self::solution_histogram(&geometry, board, rules, &[]);
+        let mut reachable: Vec<(u16, usize)> = histogram
+            .iter()
+            .map(|(&target, counts)| (target, counts.iter().sum()))
+            .collect();
+        if reachable.is_empty() {
+            return rules.min_number as u16;
+        }
+        
+        // Most solutions first, then take the quarter that matches the difficulty
+        reachable.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let level = (difficulty.clamp(1, 4) - 1) as usize;
+        let band_size = reachable.len().div_ceil(4);
+        let start = (level * band_size).min(reachable.len() - 1);
+        let end = (start + band_size).min(reachable.len());
+        
+        reachable[start..end].choose(rng).map_or(reachable[0].0, |&(target, _)| target)
+    }
+    
+    /// Fill the board with numbers matching the difficulty
+    fn fill_board<R: Rng>(board: &mut DynamicBitPackedBoard, rules: &TrioRules, difficulty: u8, rng: &mut R) {
+        if !rules.is_classic() {
+            Self::fill_board_weighted(board, rules, difficulty, rng);
+            return;
+        }
         
         // Generate number distribution based on difficulty
         let mut numbers_pool = match difficulty {
@@ -452,7 +1389,7 @@ impl TrioGame {
         };
         
         // Shuffle the numbers
-        numbers_pool.shuffle(&mut rng);
+        numbers_pool.shuffle(rng);
         
         // Fill the board
         let mut index = 0;
@@ -466,48 +1403,42 @@ impl TrioGame {
                 }
             }
         }
-        
-        // Generate target based on difficulty
-        
-        
-        match difficulty {
-            1 => rng.gen_range(3..=15), // Easy targets
-            2 => rng.gen_range(5..=25), // Medium targets
-            3 => rng.gen_range(10..=40), // Hard targets
-            4 => rng.gen_range(15..=60), // Expert targets
-            _ => rng.gen_range(5..=25),   // Default
-        }
     }
     
-    /// Check if the generated board has at least one solution using optimized algorithm
-    fn has_valid_solutions(&self) -> bool {
-        // Use optimized adjacency-based search
-        let adjacent_triplets = self.geometry.get_adjacent_triplets();
-        
-        for &triplet in adjacent_triplets {
-            let [(row1, col1), (row2, col2), (row3, col3)] = triplet;
-            
-            let a = self.board.get_cell(row1, col1);
-            let b = self.board.get_cell(row2, col2);
-            let c = self.board.get_cell(row3, col3);
-            
-            if a == 0 || b == 0 || c == 0 || a > 9 || b > 9 || c > 9 {
-                continue;
+    /// Weighted random fill for custom rules
+    /// Easy boards lean towards small numbers, expert boards towards large ones
+    fn fill_board_weighted<R: Rng>(board: &mut DynamicBitPackedBoard, rules: &TrioRules, difficulty: u8, rng: &mut R) {
+        let numbers: Vec<u8> = (rules.min_number..=rules.max_number).collect();
+        let span = numbers.len() as u32;
+        let weight = |position: u32| -> u32 {
+            match difficulty {
+                1 => span - position,           // Kinderfreundlich: small numbers
+                3 | 4 => position + span / 2,   // Strategisch/Analytisch: larger numbers
+                _ => 1,                         // Vollspektrum: uniform
             }
-            
-            let result1 = (a as u16) * (b as u16) + (c as u16);
-            let result2 = (a as u16) * (b as u16) - (c as u16);
-            
-            if result1 == self.target_number as u16 || result2 == self.target_number as u16 {
-                return true;
+        };
+        let total: u32 = (0..span).map(weight).sum();
+        
+        for row in 0..rules.rows {
+            for col in 0..rules.cols {
+                let mut pick = rng.gen_range(0..total);
+                let mut value = numbers[0];
+                for (position, &number) in numbers.iter().enumerate() {
+                    let w = weight(position as u32);
+                    if pick < w {
+                        value = number;
+                        break;
+                    }
+                    pick -= w;
+                }
+                let _ = board.set_cell(row, col, value);
             }
         }
-        false
     }
 }
 
 /// Helper function to convert difficulty string to number
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub fn difficulty_to_number(difficulty: &str) -> u8 {
     match difficulty {
         "kinderfreundlich" => 1,
@@ -519,7 +1450,7 @@ pub fn difficulty_to_number(difficulty: &str) -> u8 {
 }
 
 /// Helper function to convert difficulty number to string
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub fn difficulty_to_string(difficulty: u8) -> String {
     match difficulty {
         1 => "kinderfreundlich".to_string(),
@@ -528,4 +1459,172 @@ pub fn difficulty_to_string(difficulty: u8) -> String {
         4 => "analytisch".to_string(),
         _ => "vollspektrum".to_string(),
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_puzzle_code_roundtrip() {
+        let game = TrioGame::new(3);
+        let code = game.to_code();
+        assert_eq!(code.len(), 47);
+        
+        let restored = TrioGame::from_code_internal(&code).unwrap();
+        assert_eq!(restored.get_board_array(), game.get_board_array());
+        assert_eq!(restored.get_target_number(), game.get_target_number());
+        assert_eq!(restored.get_difficulty(), 3);
+        
+        // Codes survive being typed in lowercase with separators
+        let typed = format!("{}-{}", &code[..20], &code[20..]).to_lowercase();
+        assert!(TrioGame::from_code_internal(&typed).is_ok());
+    }
+    
+    #[test]
+    fn test_puzzle_code_rejects_corruption() {
+        let code = TrioGame::new(2).to_code();
+        
+        // Flip one character of the board payload
+        let mut chars: Vec<char> = code.chars().collect();
+        chars[10] = if chars[10] == '2' { '3' } else { '2' };
+        let corrupted: String = chars.into_iter().collect();
+        assert!(TrioGame::from_code_internal(&corrupted).is_err());
+        
+        assert!(TrioGame::from_code_internal(&code[..40]).is_err());
+        assert!(TrioGame::from_code_internal("not a code").is_err());
+    }
+    
+    #[test]
+    fn test_daily_puzzle_is_deterministic() {
+        let first = TrioGame::daily_puzzle_internal(2025, 7, 14, 2).unwrap();
+        let second = TrioGame::daily_puzzle_internal(2025, 7, 14, 2).unwrap();
+        assert_eq!(first.to_code(), second.to_code());
+        assert_eq!(first.to_code(), "285J2TA5519K28K46XGP6T422N4SAMBM8DTQANW9JP009S8"); // Pinned: the daily board must not change between releases
+        
+        let next_day = TrioGame::daily_puzzle_internal(2025, 7, 15, 2).unwrap();
+        assert_ne!(first.to_code(), next_day.to_code());
+        
+        assert!(TrioGame::daily_puzzle_internal(2025, 2, 29, 2).is_err());
+        assert!(TrioGame::daily_puzzle_internal(2024, 2, 29, 2).is_ok());
+        assert_eq!(TrioGame::days_from_civil(1970, 1, 1), Some(0));
+    }
+    
+    #[test]
+    fn test_generator_hits_solution_window() {
+        for difficulty in [
+            TrioDifficultyNew::Kinderfreundlich,
+            TrioDifficultyNew::Vollspektrum,
+            TrioDifficultyNew::Strategisch,
+            TrioDifficultyNew::Analytisch,
+        ] {
+            let (min_solutions, max_solutions) = difficulty.solution_window();
+            let mut rng = ChaCha8Rng::seed_from_u64(7);
+            let game = TrioGame::generate_game_with_solution_window(TrioRules::new(), difficulty, min_solutions, max_solutions, &mut rng);
+            let report = game.get_generation_report().unwrap();
+            
+            assert!(report.within_window(), "{:?}: {}", difficulty, report.summary());
+            assert!(game.has_valid_solutions());
+            assert!(difficulty.target_range().contains(&game.get_target_number()));
+            
+            // The report must agree with the solver
+            let solutions = game.find_all_solutions().len() / 7;
+            assert_eq!(solutions, report.solution_count());
+            assert_eq!(report.addition_solutions() + report.subtraction_solutions(), solutions);
+            assert!((min_solutions..=max_solutions).contains(&solutions));
+        }
+    }
+    
+    #[test]
+    fn test_generator_narrow_window_with_seeded_rng() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let game = TrioGame::generate_game_with_solution_window(
+            TrioRules::new(), TrioDifficultyNew::Analytisch, 1, 1, &mut rng,
+        );
+        
+        assert!(game.get_generation_report().unwrap().within_window());
+        assert_eq!(game.find_all_solutions().len() / 7, 1);
+    }
+    
+    #[test]
+    fn test_operation_templates() {
+        assert_eq!(TrioOperation::Addition.apply(3, 4, 2), Some(14));
+        assert_eq!(TrioOperation::Subtraction.apply(1, 1, 2), None);
+        assert_eq!(TrioOperation::Sum.apply(3, 4, 2), Some(9));
+        assert_eq!(TrioOperation::Product.apply(3, 4, 2), Some(24));
+        assert_eq!(TrioOperation::SumTimes.apply(3, 4, 2), Some(14));
+        assert_eq!(TrioOperation::Division.apply(3, 4, 6), Some(2));
+        assert_eq!(TrioOperation::Division.apply(3, 4, 5), None);
+        assert_eq!(TrioOperation::SumTimes.formula(3, 4, 2), "(3+4)×2");
+    }
+    
+    #[test]
+    fn test_custom_rules_board_and_solutions() {
+        let mut rules = TrioRules::new();
+        rules.set_board_size(5, 9);
+        rules.set_number_range(1, 20);
+        rules.set_operation_enabled(TrioOperation::Subtraction, false);
+        rules.set_operation_enabled(TrioOperation::Product, true);
+        rules.set_allow_permutations(true);
+        assert!(!rules.is_classic());
+        assert_eq!(rules.max_result(), 8000);
+        
+        let game = TrioGame::new_with_rules_internal(rules, 2).unwrap();
+        assert_eq!(game.get_board_array().len(), 45);
+        assert!(game.get_board_array().iter().all(|&n| (1..=20).contains(&n)));
+        
+        // Custom boards always get a reachable target
+        assert!(game.has_valid_solutions());
+        for solution in game.find_all_solutions().chunks(7) {
+            let cells: Vec<usize> = solution[..6].iter().map(|&v| v as usize).collect();
+            assert_ne!(game.validate_trio(cells[0], cells[1], cells[2], cells[3], cells[4], cells[5]), -1);
+        }
+        
+        let analysis = game.analyze_reachable_targets();
+        assert!(analysis.get_reachable_targets().contains(&(game.get_target_number() as i16)));
+        
+        // Custom rules travel inside the puzzle code
+        let restored = TrioGame::from_code_internal(&game.to_code()).unwrap();
+        assert_eq!(restored.get_rules(), game.get_rules());
+        assert_eq!(restored.get_board_array(), game.get_board_array());
+        assert_eq!(restored.get_target_number(), game.get_target_number());
+    }
+    
+    #[test]
+    fn test_permutations_find_more_solutions() {
+        let mut rules = TrioRules::new();
+        let mut game = TrioGame::new_with_rules_internal(rules.clone(), 2).unwrap();
+        
+        // 2×9+3 = 21 only reads left to right; 9,3,2 needs a permutation
+        for (col, value) in [(0, 9), (1, 3), (2, 2)] {
+            game.board.set_cell(0, col, value).unwrap();
+        }
+        game.set_target_number(21);
+        assert_eq!(game.validate_trio(0, 0, 0, 1, 0, 2), -1);
+        
+        rules.set_allow_permutations(true);
+        game.rules = rules;
+        assert_eq!(game.validate_trio(0, 0, 0, 1, 0, 2), 21);
+    }
+    
+    #[test]
+    fn test_invalid_rules_rejected() {
+        let mut rules = TrioRules::new();
+        rules.set_board_size(2, 7);
+        assert!(matches!(TrioGame::new_with_rules_internal(rules.clone(), 2), Err(GameError::InvalidBoardSize { rows: 2, .. })));
+        
+        rules.set_board_size(7, 7);
+        rules.set_number_range(1, 40);
+        assert!(matches!(TrioGame::new_with_rules_internal(rules.clone(), 2), Err(GameError::InvalidNumberRange { .. })));
+        
+        rules.set_number_range(1, 9);
+        for op in TrioOperation::ALL {
+            rules.set_operation_enabled(op, false);
+        }
+        assert!(matches!(TrioGame::new_with_rules_internal(rules, 2), Err(GameError::NoOperationsEnabled)));
+        
+        let mut game = TrioGame::new_guaranteed(TrioDifficultyNew::Kinderfreundlich);
+        assert_eq!(game.make_move(0, 0, 3, 3, 6, 6), Err(GameError::NotATrio));
+        assert_eq!(game.make_move(0, 0, 0, 1, 0, 9), Err(GameError::OutOfBounds));
+    }
+}
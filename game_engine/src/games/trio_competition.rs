@@ -0,0 +1,452 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use crate::games::trio::{TrioDifficultyNew, TrioGame, TrioRules};
+use crate::GameError;
+
+/// Outcome of a single claim in a Trio competition round
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrioClaimResult {
+    Accepted = 0,       // First valid claim: round won, next round starts
+    WrongSolution = 1,  // Cells don't form a trio for the target: penalty + locked out
+    CellBlocked = 2,    // A cell was already used by an earlier claimed solution
+    LockedOut = 3,      // Player already made a wrong claim this round
+    InvalidPlayer = 4,  // Player index outside 0..player_count
+    MatchOver = 5,      // All rounds played
+    MalformedClaim = 6, // Positions array doesn't hold exactly three (row, col) pairs
+}
+
+/// Round-based multiplayer Trio for 2–6 players
+///
+/// Rules:
+/// - All players share one board; every round reveals a new target number
+/// - First valid claim wins the round (first-claim rule)
+/// - A wrong claim costs a penalty and locks the player out for the rest of the round
+/// - Cells of a claimed solution are blocked for the remainder of the match
+/// - The match ends after the configured number of rounds or when no target is reachable
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct TrioCompetition {
+    game: TrioGame,
+    player_count: usize,
+    total_rounds: usize,
+    current_round: usize,
+    points_per_claim: i32,
+    wrong_claim_penalty: i32,
+    
+    // Per-player score table
+    scores: Vec<i32>,
+    claims_won: Vec<usize>,
+    wrong_claims: Vec<usize>,
+    locked_out: Vec<bool>,
+    
+    // Row-major flags for cells used by claimed solutions
+    blocked_cells: Vec<bool>,
+    round_winners: Vec<Option<usize>>,
+    match_over: bool,
+    
+    // Picks each round's target; seeded for reproducible matches
+    rng: ChaCha8Rng,
+}
+
+/// Player limits for a competition
+const MIN_PLAYERS: usize = 2;
+const MAX_PLAYERS: usize = 6;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TrioCompetition {
+    /// Create a competition with default scoring (1 point per claim, 1 point penalty)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(player_count: usize, rounds: usize, difficulty: u8) -> Result<TrioCompetition, GameError> {
+        Self::new_internal(player_count, rounds, difficulty)
+    }
+    
+    /// Create a competition on a board with custom rules (board size, numbers, operations)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn new_with_rules(player_count: usize, rounds: usize, rules: TrioRules, difficulty: u8) -> Result<TrioCompetition, GameError> {
+        Self::new_with_rules_internal(player_count, rounds, rules, difficulty)
+    }
+    
+    /// Create a competition on a given board (e.g. a daily puzzle or shared code) with targets from `seed`;
+    /// every client using the same board and seed plays the same match
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn new_seeded(player_count: usize, rounds: usize, game: &TrioGame, seed: u64) -> Result<TrioCompetition, GameError> {
+        Self::with_game(player_count, rounds, game.clone(), seed)
+    }
+    
+    /// Configure points for a valid claim and the penalty for a wrong one
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_scoring(&mut self, points_per_claim: i32, wrong_claim_penalty: i32) {
+        self.points_per_claim = points_per_claim;
+        self.wrong_claim_penalty = wrong_claim_penalty;
+    }
+    
+    /// Claim a trio for the current target on behalf of a player
+    /// `positions` is flat like `find_all_solutions`: [row1, col1, row2, col2, row3, col3]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn claim(&mut self, player: usize, positions: &[usize]) -> TrioClaimResult {
+        let &[row1, col1, row2, col2, row3, col3] = positions else {
+            return TrioClaimResult::MalformedClaim;
+        };
+        if self.match_over {
+            return TrioClaimResult::MatchOver;
+        }
+        if player >= self.player_count {
+            return TrioClaimResult::InvalidPlayer;
+        }
+        if self.locked_out[player] {
+            return TrioClaimResult::LockedOut;
+        }
+        
+        let cells = [(row1, col1), (row2, col2), (row3, col3)];
+        if cells.iter().any(|&(row, col)| self.is_cell_blocked(row, col)) {
+            return TrioClaimResult::CellBlocked;
+        }
+        
+        if self.game.validate_trio(row1, col1, row2, col2, row3, col3) == -1 {
+            self.scores[player] -= self.wrong_claim_penalty;
+            self.wrong_claims[player] += 1;
+            self.locked_out[player] = true;
+            
+            // Nobody left who may claim: the round is lost for everyone
+            if self.locked_out.iter().all(|&locked| locked) {
+                self.finish_round(None);
+            }
+            return TrioClaimResult::WrongSolution;
+        }
+        
+        self.game.make_move(row1, col1, row2, col2, row3, col3).expect("claim was validated");
+        for &(row, col) in &cells {
+            self.blocked_cells[row * self.game.get_cols() + col] = true;
+        }
+        self.scores[player] += self.points_per_claim;
+        self.claims_won[player] += 1;
+        self.finish_round(Some(player));
+        
+        TrioClaimResult::Accepted
+    }
+    
+    /// End the current round without a winner (nobody found a trio)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn skip_round(&mut self) {
+        if !self.match_over {
+            self.finish_round(None);
+        }
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_player_count(&self) -> usize {
+        self.player_count
+    }
+    
+    /// Current round (1-based)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_current_round(&self) -> usize {
+        self.current_round
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_total_rounds(&self) -> usize {
+        self.total_rounds
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_target_number(&self) -> u16 {
+        self.game.get_target_number()
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_board_array(&self) -> Vec<u8> {
+        self.game.get_board_array()
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_cell_blocked(&self, row: usize, col: usize) -> bool {
+        row < self.game.get_rows() && col < self.game.get_cols() && self.blocked_cells[row * self.game.get_cols() + col]
+    }
+    
+    /// Blocked flags for all cells (row-major, 1 = blocked)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_blocked_cells(&self) -> Vec<u8> {
+        self.blocked_cells.iter().map(|&blocked| blocked as u8).collect()
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_locked_out(&self, player: usize) -> bool {
+        self.locked_out.get(player).copied().unwrap_or(false)
+    }
+    
+    /// Score table indexed by player
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_scores(&self) -> Vec<i32> {
+        self.scores.clone()
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_claims_won(&self) -> Vec<usize> {
+        self.claims_won.clone()
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_wrong_claims(&self) -> Vec<usize> {
+        self.wrong_claims.clone()
+    }
+    
+    /// Winner per finished round (-1 = round without winner)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_round_winners(&self) -> Vec<i32> {
+        self.round_winners
+            .iter()
+            .map(|winner| winner.map_or(-1, |player| player as i32))
+            .collect()
+    }
+    
+    /// Number of trios still available for the current target
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_open_solution_count(&self) -> usize {
+        let target = self.game.get_target_number();
+        self.game
+            .reachable_targets_avoiding(&self.blocked_cells)
+            .iter()
+            .find(|&&(reachable, _)| reachable == target)
+            .map_or(0, |&(_, count)| count)
+    }
+    
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_match_over(&self) -> bool {
+        self.match_over
+    }
+    
+    /// Players sharing the highest score (several on a tie)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_leaders(&self) -> Vec<usize> {
+        let best = self.scores.iter().copied().max().unwrap_or(0);
+        (0..self.player_count).filter(|&player| self.scores[player] == best).collect()
+    }
+    
+    /// Match winner once the match is over (None while running or on a tie)
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_winner(&self) -> Option<usize> {
+        if !self.match_over {
+            return None;
+        }
+        match self.get_leaders().as_slice() {
+            [winner] => Some(*winner),
+            _ => None,
+        }
+    }
+}
+
+impl TrioCompetition {
+    /// Create a competition (pure Rust, testable without a JS runtime)
+    pub fn new_internal(player_count: usize, rounds: usize, difficulty: u8) -> Result<TrioCompetition, GameError> {
+        Self::with_game(player_count, rounds, TrioGame::new(difficulty), rand::random())
+    }
+    
+    /// Create a competition on a board with custom rules (pure Rust)
+    pub fn new_with_rules_internal(player_count: usize, rounds: usize, rules: TrioRules, difficulty: u8) -> Result<TrioCompetition, GameError> {
+        let game = TrioGame::new_with_rules_internal(rules, difficulty)?;
+        Self::with_game(player_count, rounds, game, rand::random())
+    }
+    
+    /// Create a competition on a given board whose round targets follow from `seed`
+    /// (with a daily puzzle or shared code, every client plays the same match)
+    pub fn with_game(player_count: usize, rounds: usize, game: TrioGame, seed: u64) -> Result<TrioCompetition, GameError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&player_count) {
+            return Err(GameError::InvalidPlayerCount { count: player_count, min: MIN_PLAYERS, max: MAX_PLAYERS });
+        }
+        if rounds == 0 {
+            return Err(GameError::NoRounds);
+        }
+        
+        let mut competition = Self {
+            blocked_cells: vec![false; game.get_rows() * game.get_cols()],
+            game,
+            player_count,
+            total_rounds: rounds,
+            current_round: 0,
+            points_per_claim: 1,
+            wrong_claim_penalty: 1,
+            scores: vec![0; player_count],
+            claims_won: vec![0; player_count],
+            wrong_claims: vec![0; player_count],
+            locked_out: vec![false; player_count],
+            round_winners: Vec::new(),
+            match_over: false,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        };
+        competition.start_round();
+        
+        Ok(competition)
+    }
+    
+    /// Access the underlying board (for AI hints and tests)
+    pub fn game(&self) -> &TrioGame {
+        &self.game
+    }
+    
+    /// Record the round result and move on
+    fn finish_round(&mut self, winner: Option<usize>) {
+        self.round_winners.push(winner);
+        
+        if self.current_round >= self.total_rounds {
+            self.match_over = true;
+        } else {
+            self.start_round();
+        }
+    }
+    
+    /// Reveal a new target that is still solvable with the unblocked cells
+    /// Prefers targets of the difficulty's range, ends the match if nothing is reachable
+    fn start_round(&mut self) {
+        let reachable = self.game.reachable_targets_avoiding(&self.blocked_cells);
+        if reachable.is_empty() {
+            self.match_over = true;
+            return;
+        }
+        
+        let difficulty = TrioDifficultyNew::from_level(self.game.get_difficulty());
+        let range = self.game.get_rules().target_range(difficulty);
+        let preferred: Vec<u16> = reachable
+            .iter()
+            .map(|&(target, _)| target)
+            .filter(|target| range.contains(target))
+            .collect();
+        let fallback: Vec<u16> = reachable.iter().map(|&(target, _)| target).collect();
+        let candidates = if preferred.is_empty() { &fallback } else { &preferred };
+        
+        let target = *candidates.choose(&mut self.rng).expect("reachable targets are not empty");
+        self.game.set_target_number(target);
+        self.current_round += 1;
+        self.locked_out.fill(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Find an open trio for the current target (row1, col1, row2, col2, row3, col3)
+    fn open_solution(competition: &TrioCompetition) -> [usize; 6] {
+        let solutions = competition.game().find_all_solutions();
+        solutions
+            .chunks(7)
+            .map(|s| [s[0], s[1], s[2], s[3], s[4], s[5]].map(|v| v as usize))
+            .find(|s| (0..3).all(|i| !competition.is_cell_blocked(s[2 * i], s[2 * i + 1])))
+            .expect("round target must have an open solution")
+    }
+    
+    /// Competition on a fixed board with seeded targets
+    fn seeded(player_count: usize, rounds: usize) -> TrioCompetition {
+        let board = TrioGame::daily_puzzle_internal(2025, 7, 14, 2).unwrap();
+        TrioCompetition::with_game(player_count, rounds, board, 42).unwrap()
+    }
+    
+    #[test]
+    fn test_seeded_matches_repeat() {
+        let board = TrioGame::daily_puzzle_internal(2025, 7, 14, 2).unwrap();
+        let targets = |seed| {
+            let mut competition = TrioCompetition::new_seeded(2, 5, &board, seed).unwrap();
+            let mut targets = vec![competition.get_target_number()];
+            for _ in 1..5 {
+                competition.skip_round();
+                targets.push(competition.get_target_number());
+            }
+            targets
+        };
+        assert_eq!(targets(7), targets(7));
+        assert_eq!(TrioCompetition::new_seeded(2, 5, &board, 7).unwrap().get_board_array(), board.get_board_array());
+    }
+    
+    #[test]
+    fn test_player_limits() {
+        assert!(TrioCompetition::new_internal(1, 5, 2).is_err());
+        assert!(TrioCompetition::new_internal(7, 5, 2).is_err());
+        assert!(TrioCompetition::new_internal(4, 0, 2).is_err());
+        assert!(TrioCompetition::new_internal(6, 5, 2).is_ok());
+    }
+    
+    #[test]
+    fn test_first_claim_wins_and_blocks_cells() {
+        let mut competition = seeded(3, 3);
+        assert_eq!(competition.get_current_round(), 1);
+        
+        let solution = open_solution(&competition);
+        assert_eq!(competition.claim(1, &solution), TrioClaimResult::Accepted);
+        assert_eq!(competition.get_scores(), vec![0, 1, 0]);
+        assert_eq!(competition.get_round_winners(), vec![1]);
+        assert!(competition.is_cell_blocked(solution[0], solution[1]));
+        
+        // Same cells can't be reused in the next round
+        assert_eq!(competition.get_current_round(), 2);
+        assert_eq!(competition.claim(0, &solution), TrioClaimResult::CellBlocked);
+        assert_eq!(competition.claim(0, &solution[..4]), TrioClaimResult::MalformedClaim);
+    }
+    
+    #[test]
+    fn test_wrong_claim_penalty_and_lockout() {
+        let mut competition = seeded(2, 2);
+        
+        // Two cells in an L shape never form a straight trio
+        assert_eq!(competition.claim(0, &[0, 0, 0, 1, 1, 0]), TrioClaimResult::WrongSolution);
+        assert_eq!(competition.get_scores(), vec![-1, 0]);
+        assert!(competition.is_locked_out(0));
+        assert_eq!(competition.claim(0, &[0, 0, 0, 1, 1, 0]), TrioClaimResult::LockedOut);
+        
+        // Once every player is locked out the round ends without a winner
+        assert_eq!(competition.claim(1, &[0, 0, 0, 1, 1, 0]), TrioClaimResult::WrongSolution);
+        assert_eq!(competition.get_round_winners(), vec![-1]);
+        assert!(!competition.is_locked_out(0));
+        assert_eq!(competition.claim(9, &[0, 0, 0, 1, 1, 0]), TrioClaimResult::InvalidPlayer);
+    }
+    
+    #[test]
+    fn test_match_ends_after_configured_rounds() {
+        let mut competition = seeded(2, 2);
+        
+        while !competition.is_match_over() {
+            let solution = open_solution(&competition);
+            assert_eq!(competition.claim(0, &solution), TrioClaimResult::Accepted);
+        }
+        
+        assert_eq!(competition.get_round_winners(), vec![0, 0]);
+        assert_eq!(competition.get_winner(), Some(0));
+        assert_eq!(competition.claim(1, &[0, 0, 0, 1, 0, 2]), TrioClaimResult::MatchOver);
+    }
+    
+    #[test]
+    fn test_seed_fixes_round_targets() {
+        let targets = |seed| {
+            let board = TrioGame::daily_puzzle_internal(2025, 7, 14, 2).unwrap();
+            let mut competition = TrioCompetition::with_game(2, 4, board, seed).unwrap();
+            let mut targets = Vec::new();
+            while !competition.is_match_over() {
+                targets.push(competition.get_target_number());
+                competition.skip_round();
+            }
+            targets
+        };
+        
+        assert_eq!(targets(42).len(), 4);
+        assert_eq!(targets(42), targets(42));
+        assert_ne!((1..6).map(targets).collect::<Vec<_>>(), vec![targets(42); 5]);
+    }
+    
+    #[test]
+    fn test_competition_on_custom_board() {
+        let mut rules = TrioRules::new();
+        rules.set_board_size(5, 8);
+        rules.set_operation_enabled(crate::games::trio::TrioOperation::Sum, true);
+        
+        let mut competition = TrioCompetition::new_with_rules_internal(2, 3, rules, 2).unwrap();
+        assert_eq!(competition.get_blocked_cells().len(), 40);
+        
+        let solution = open_solution(&competition);
+        assert_eq!(competition.claim(0, &solution), TrioClaimResult::Accepted);
+        assert!(competition.is_cell_blocked(solution[4], solution[5]));
+        assert!(!competition.is_cell_blocked(5, 0));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::games::game_result::{GameResult, TerminationReason};
+use crate::games::match_manager::SeriesGame;
+use crate::games::trio_competition::{TrioClaimResult, TrioCompetition};
+use crate::net::LockstepGame;
+use crate::GameError;
+
+/// One recorded move with the time it was made
+/// Moves use the lockstep protocol encoding (Connect4 move code, (row, col), L-Game (row, col, orientation[, neutral move]))
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedMove {
+    pub game_move: Vec<usize>,
+    pub timestamp_ms: u64,
+}
+
+impl RecordedMove {
+    pub fn new(game_move: Vec<usize>, timestamp_ms: u64) -> Self {
+        Self { game_move, timestamp_ms }
+    }
+}
+
+/// One recorded Trio claim: player index, round (1-based) and flat positions [row1, col1, row2, col2, row3, col3]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrioClaimRecord {
+    pub player: usize,
+    pub round: usize,
+    pub positions: Vec<usize>,
+    pub timestamp_ms: u64,
+}
+
+/// Why a game record was rejected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationIssue {
+    TimestampOutOfOrder,      // Earlier than the previous move
+    RoundOutOfOrder,          // Trio claim for a round that already ended
+    MoveAfterGameOver,
+    IllegalMove(GameError),   // Rejected by the rules
+    GameNotOver,              // The moves don't end the game and no off-board ending was declared
+    ResultMismatch(String),   // Declared and replayed result differ
+}
+
+/// Outcome of replaying a record
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationReport {
+    pub moves_checked: usize,
+    pub first_invalid_move: Option<usize>, // Index into the move list
+    pub issue: Option<VerificationIssue>,
+    pub result: Option<GameResult>,        // Replayed result (board games only)
+}
+
+impl VerificationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issue.is_none()
+    }
+    
+    fn rejected_move(index: usize, issue: VerificationIssue) -> Self {
+        Self { moves_checked: index, first_invalid_move: Some(index), issue: Some(issue), result: None }
+    }
+}
+
+/// Replay a Connect4, Gomoku or L-Game record from its starting position and check the declared result
+/// Resignations, draw agreements and claimed draws are reproduced after the last move; since the log carries
+/// no clock and no rejected moves, a timeout or illegal-move forfeit is accepted while the game is running,
+/// but only as a loss for the player to move
+pub fn verify_game<G: LockstepGame + SeriesGame>(initial: &G, moves: &[RecordedMove], declared: &GameResult) -> VerificationReport {
+    let mut game = initial.clone();
+    let mut last_timestamp = 0;
+    for (index, recorded) in moves.iter().enumerate() {
+        if recorded.timestamp_ms < last_timestamp {
+            return VerificationReport::rejected_move(index, VerificationIssue::TimestampOutOfOrder);
+        }
+        if game.final_result().is_some() {
+            return VerificationReport::rejected_move(index, VerificationIssue::MoveAfterGameOver);
+        }
+        if let Err(reason) = game.apply_move(&recorded.game_move) {
+            return VerificationReport::rejected_move(index, VerificationIssue::IllegalMove(reason));
+        }
+        last_timestamp = recorded.timestamp_ms;
+    }
+    
+    let replayed = match (declared.reason(), declared.winner()) {
+        _ if game.final_result().is_some() => game.final_result(),
+        (TerminationReason::Resignation, Some(winner)) => {
+            game.resign(winner.opponent());
+            game.final_result()
+        },
+        (TerminationReason::AgreedDraw, None) => {
+            let offering = game.current_player();
+            let _ = game.offer_draw(offering) && game.accept_draw(offering.opponent());
+            game.final_result()
+        },
+        (TerminationReason::ClaimedDraw, None) => {
+            game.claim_draw();
+            game.final_result()
+        },
+        (reason @ (TerminationReason::Timeout | TerminationReason::IllegalMove), Some(_)) => {
+            // Resigning for the player to move yields the game's own encoding of the final move
+            let forfeiting = game.current_player();
+            game.resign(forfeiting);
+            game.final_result().map(|result| GameResult::new(Some(forfeiting.opponent()), reason, result.final_move()))
+        },
+        _ => None,
+    };
+    
+    let issue = match &replayed {
+        None => Some(VerificationIssue::GameNotOver),
+        Some(result) if result != declared => Some(VerificationIssue::ResultMismatch(format!(
+            "declared {}, replayed {}",
+            declared.to_record(),
+            result.to_record()
+        ))),
+        Some(_) => None,
+    };
+    VerificationReport { moves_checked: moves.len(), first_invalid_move: None, issue, result: replayed }
+}
+
+/// Replay Trio claims round by round from a competition's starting state
+/// (e.g. `TrioCompetition::with_game` with the match's board and seed, which fix every round's target):
+/// every claim must be a valid trio for its round's target and may not reuse cells of an earlier claim.
+/// Rounds before a claim's round that have no recorded claim are skipped.
+/// `declared_claims_won` holds the accepted claims per player.
+pub fn verify_trio_claims(initial: &TrioCompetition, claims: &[TrioClaimRecord], declared_claims_won: &[usize]) -> VerificationReport {
+    let mut competition = initial.clone();
+    let mut last_timestamp = 0;
+    for (index, claim) in claims.iter().enumerate() {
+        if claim.timestamp_ms < last_timestamp {
+            return VerificationReport::rejected_move(index, VerificationIssue::TimestampOutOfOrder);
+        }
+        if claim.round < competition.get_current_round() {
+            return VerificationReport::rejected_move(index, VerificationIssue::RoundOutOfOrder);
+        }
+        while claim.round > competition.get_current_round() && !competition.is_match_over() {
+            competition.skip_round();
+        }
+    
+        let illegal = |error| VerificationReport::rejected_move(index, VerificationIssue::IllegalMove(error));
+        match competition.claim(claim.player, &claim.positions) {
+            TrioClaimResult::Accepted => {},
+            TrioClaimResult::MatchOver => return VerificationReport::rejected_move(index, VerificationIssue::MoveAfterGameOver),
+            TrioClaimResult::CellBlocked => return illegal(GameError::PositionOccupied),
+            TrioClaimResult::WrongSolution | TrioClaimResult::LockedOut => return illegal(GameError::NotATrio),
+            TrioClaimResult::InvalidPlayer => return illegal(GameError::InvalidPlayer),
+            TrioClaimResult::MalformedClaim => return illegal(GameError::InvalidMove),
+        }
+        last_timestamp = claim.timestamp_ms;
+    }
+    
+    let claims_won = competition.get_claims_won();
+    let issue = (claims_won != declared_claims_won).then(|| {
+        VerificationIssue::ResultMismatch(format!("declared claims {:?}, replayed {:?}", declared_claims_won, claims_won))
+    });
+    VerificationReport { moves_checked: claims.len(), first_invalid_move: None, issue, result: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Player;
+    use crate::games::{Connect4Game, GomokuGame, LGame};
+    use crate::games::trio::TrioGame;
+    
+    fn timed(moves: &[&[usize]]) -> Vec<RecordedMove> {
+        moves.iter().enumerate().map(|(index, game_move)| RecordedMove::new(game_move.to_vec(), 1_000 * index as u64)).collect()
+    }
+    
+    #[test]
+    fn test_verify_connect4_records() {
+        let game = Connect4Game::new();
+        let moves = timed(&[&[0], &[1], &[0], &[1], &[0], &[1], &[0]]);
+        let declared = GameResult::new(Some(Player::Yellow), TerminationReason::LineCompleted, vec![0]);
+        let report = verify_game(&game, &moves, &declared);
+        assert!(report.is_valid());
+        assert_eq!(report.result, Some(declared.clone()));
+    
+        // Wrong winner
+        let claimed = GameResult::new(Some(Player::Red), TerminationReason::LineCompleted, vec![0]);
+        assert!(matches!(verify_game(&game, &moves, &claimed).issue, Some(VerificationIssue::ResultMismatch(_))));
+    
+        // Moves after the win, out-of-order timestamps and illegal moves are pinpointed
+        let mut extra = moves.clone();
+        extra.push(RecordedMove::new(vec![2], 10_000));
+        assert_eq!(verify_game(&game, &extra, &declared).first_invalid_move, Some(7));
+        let mut late = moves.clone();
+        late[3].timestamp_ms = 500;
+        let report = verify_game(&game, &late, &declared);
+        assert_eq!((report.first_invalid_move, report.issue), (Some(3), Some(VerificationIssue::TimestampOutOfOrder)));
+        let report = verify_game(&game, &timed(&[&[3], &[20]]), &declared);
+        assert_eq!(report.first_invalid_move, Some(1));
+        assert_eq!(report.issue, Some(VerificationIssue::IllegalMove(GameError::InvalidMove)));
+    }
+    
+    #[test]
+    fn test_verify_off_board_endings() {
+        let gomoku = GomokuGame::new();
+        let moves = timed(&[&[7, 7], &[7, 8]]);
+        let resigned = GameResult::new(Some(Player::White), TerminationReason::Resignation, vec![7, 8]);
+        assert!(verify_game(&gomoku, &moves, &resigned).is_valid());
+        let drawn = GameResult::new(None, TerminationReason::AgreedDraw, vec![7, 8]);
+        assert!(verify_game(&gomoku, &moves, &drawn).is_valid());
+        let unfinished = GameResult::new(None, TerminationReason::BoardFull, vec![7, 8]);
+        assert_eq!(verify_game(&gomoku, &moves, &unfinished).issue, Some(VerificationIssue::GameNotOver));
+    
+        let l_game = LGame::new();
+        let (row, col, orientation) = l_game.get_all_valid_moves()[0];
+        let moves = timed(&[&[row, col, orientation as usize]]);
+        let timeout = GameResult::new(Some(Player::Yellow), TerminationReason::Timeout, vec![row, col, orientation as usize]);
+        assert!(verify_game(&l_game, &moves, &timeout).is_valid());
+    
+        // Only the player to move can lose on time, and the final move must be the last one played
+        let forged = GameResult::new(Some(Player::Red), TerminationReason::Timeout, vec![row, col, orientation as usize]);
+        assert!(matches!(verify_game(&l_game, &moves, &forged).issue, Some(VerificationIssue::ResultMismatch(_))));
+        let wrong_move = GameResult::new(Some(Player::Yellow), TerminationReason::Timeout, vec![0, 0, 0]);
+        assert!(matches!(verify_game(&l_game, &moves, &wrong_move).issue, Some(VerificationIssue::ResultMismatch(_))));
+    }
+    
+    #[test]
+    fn test_verify_claimed_draw() {
+        let mut game = Connect4Game::new_with_size_internal(4, 4, 4).unwrap();
+        game.set_claimable_draw_enabled(true);
+        let moves = timed(&[&[0], &[1], &[0], &[1], &[2], &[3], &[2], &[3], &[1], &[0], &[1], &[0], &[3]]);
+        let claimed = GameResult::new(None, TerminationReason::ClaimedDraw, vec![3]);
+        assert!(verify_game(&game, &moves, &claimed).is_valid());
+        
+        // The claim is only valid where the rules allow it
+        assert_eq!(verify_game(&game, &moves[..12], &claimed).issue, Some(VerificationIssue::GameNotOver));
+        assert_eq!(verify_game(&LGame::new(), &[], &claimed).issue, Some(VerificationIssue::GameNotOver));
+    }
+    
+    #[test]
+    fn test_verify_illegal_move_forfeit() {
+        let gomoku = GomokuGame::new();
+        let moves = timed(&[&[7, 7], &[7, 8], &[8, 8]]);
+        let forfeit = GameResult::new(Some(Player::Black), TerminationReason::IllegalMove, vec![8, 8]);
+        assert!(verify_game(&gomoku, &moves, &forfeit).is_valid());
+        
+        // Only the player to move can forfeit
+        let forged = GameResult::new(Some(Player::White), TerminationReason::IllegalMove, vec![8, 8]);
+        assert!(matches!(verify_game(&gomoku, &moves, &forged).issue, Some(VerificationIssue::ResultMismatch(_))));
+    }
+    
+    /// An unblocked solution for the competition's current target
+    fn open_solution(competition: &TrioCompetition) -> Vec<usize> {
+        competition.game().find_all_solutions()
+            .chunks(7)
+            .map(|solution| solution[..6].iter().map(|&value| value as usize).collect::<Vec<usize>>())
+            .find(|cells| cells.chunks(2).all(|cell| !competition.is_cell_blocked(cell[0], cell[1])))
+            .expect("round target must have an open solution")
+    }
+    
+    #[test]
+    fn test_verify_trio_claims() {
+        let board = TrioGame::daily_puzzle_internal(2025, 7, 14, 2).unwrap();
+        let initial = TrioCompetition::with_game(2, 3, board, 42).unwrap();
+        let claim = |player, round, positions: Vec<usize>, timestamp_ms| TrioClaimRecord { player, round, positions, timestamp_ms };
+    
+        // Player 1 wins round 1, nobody finds round 2, player 0 wins round 3
+        let mut live = initial.clone();
+        let first = open_solution(&live);
+        assert_eq!(live.claim(1, &first), TrioClaimResult::Accepted);
+        live.skip_round();
+        let third = open_solution(&live);
+        assert_eq!(live.claim(0, &third), TrioClaimResult::Accepted);
+    
+        let claims = vec![claim(1, 1, first.clone(), 100), claim(0, 3, third.clone(), 300)];
+        assert!(verify_trio_claims(&initial, &claims, &[1, 1]).is_valid());
+        assert!(matches!(verify_trio_claims(&initial, &claims, &[0, 2]).issue, Some(VerificationIssue::ResultMismatch(_))));
+    
+        // Claims for an ended round and reused cells are rejected
+        let claims = vec![claim(0, 3, third.clone(), 300), claim(1, 1, first.clone(), 400)];
+        assert_eq!(verify_trio_claims(&initial, &claims, &[1, 1]).issue, Some(VerificationIssue::RoundOutOfOrder));
+        let claims = vec![claim(1, 1, first.clone(), 100), claim(0, 2, first, 200)];
+        let report = verify_trio_claims(&initial, &claims, &[1, 1]);
+        assert_eq!(report.first_invalid_move, Some(1));
+        assert_eq!(report.issue, Some(VerificationIssue::IllegalMove(GameError::PositionOccupied)));
+    
+        // Cells that are not adjacent never form a trio
+        let claims = vec![claim(0, 1, vec![0, 0, 3, 3, 6, 6], 100)];
+        assert_eq!(verify_trio_claims(&initial, &claims, &[1, 0]).first_invalid_move, Some(0));
+    
+        // No claims after the last round
+        let claims = vec![claim(0, 4, third, 100)];
+        assert_eq!(verify_trio_claims(&initial, &claims, &[1, 0]).issue, Some(VerificationIssue::MoveAfterGameOver));
+    }
+}
@@ -0,0 +1,220 @@
+use super::BoardGeometry;
+use crate::data::DynamicBitPackedBoard;
+
+/// Quadratic grid geometry with dimensions chosen at runtime
+/// Same line/mask layout as `QuadraticGrid`, used for configurable board sizes
+#[derive(Debug, Clone)]
+pub struct DynamicQuadraticGrid {
+    rows: usize,
+    cols: usize,
+    bits_per_cell: usize,
+    
+    // Pre-computed pattern masks for AI evaluation, indexed by line length
+    lines: Vec<Vec<DynamicBitPackedBoard>>,
+    center_mask: DynamicBitPackedBoard,
+    edge_mask: DynamicBitPackedBoard,
+}
+
+/// Line lengths that are always pre-computed (same set as `QuadraticGrid`)
+const DEFAULT_LINE_LENGTHS: [usize; 3] = [3, 4, 5];
+
+impl DynamicQuadraticGrid {
+    /// Create grid with lines of length 3, 4 and 5
+    pub fn new(rows: usize, cols: usize, bits_per_cell: usize) -> Self {
+        Self::with_line_length(rows, cols, bits_per_cell, 5)
+    }
+    
+    /// Create grid that additionally pre-computes lines up to `max_line_length`
+    pub fn with_line_length(rows: usize, cols: usize, bits_per_cell: usize, max_line_length: usize) -> Self {
+        let mut grid = Self {
+            rows,
+            cols,
+            bits_per_cell,
+            lines: Vec::new(),
+            center_mask: DynamicBitPackedBoard::new(rows, cols, bits_per_cell),
+            edge_mask: DynamicBitPackedBoard::new(rows, cols, bits_per_cell),
+        };
+        
+        let max_length = max_line_length.max(DEFAULT_LINE_LENGTHS[DEFAULT_LINE_LENGTHS.len() - 1]);
+        grid.lines = (0..=max_length)
+            .map(|length| {
+                if length >= DEFAULT_LINE_LENGTHS[0] {
+                    grid.generate_all_lines_of_length(length)
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
+        grid.center_mask = grid.generate_center_mask();
+        grid.edge_mask = grid.generate_edge_mask();
+        grid
+    }
+    
+    /// Get all lines of specified length (empty for lengths that weren't pre-computed)
+    pub fn get_winning_lines(&self, length: usize) -> &[DynamicBitPackedBoard] {
+        self.lines.get(length).map_or(&[], |lines| lines.as_slice())
+    }
+    
+    /// Get center control mask
+    pub fn get_center_mask(&self) -> &DynamicBitPackedBoard {
+        &self.center_mask
+    }
+    
+    /// Get edge control mask
+    pub fn get_edge_mask(&self) -> &DynamicBitPackedBoard {
+        &self.edge_mask
+    }
+    
+    /// Empty board with the grid's shape (for data layer allocation)
+    pub fn empty_board(&self) -> DynamicBitPackedBoard {
+        DynamicBitPackedBoard::new(self.rows, self.cols, self.bits_per_cell)
+    }
+    
+    /// Generate all lines of specified length in all directions
+    fn generate_all_lines_of_length(&self, length: usize) -> Vec<DynamicBitPackedBoard> {
+        let mut patterns = Vec::new();
+        
+        // Direction vectors: horizontal, vertical, diagonal /, diagonal \
+        let directions = [(0, 1), (1, 0), (1, 1), (1, -1)];
+        
+        for start_row in 0..self.rows {
+            for start_col in 0..self.cols {
+                for &(dr, dc) in &directions {
+                    let end_row = start_row as i32 + (length as i32 - 1) * dr;
+                    let end_col = start_col as i32 + (length as i32 - 1) * dc;
+                    if !self.is_valid((end_row, end_col)) {
+                        continue;
+                    }
+                    
+                    let mut pattern = self.empty_board();
+                    for i in 0..length as i32 {
+                        let r = start_row as i32 + i * dr;
+                        let c = start_col as i32 + i * dc;
+                        if let Some(index) = self.to_index((r, c)) {
+                            pattern.set_bit(index, true);
+                        }
+                    }
+                    patterns.push(pattern);
+                }
+            }
+        }
+        
+        patterns
+    }
+    
+    /// Generate center control mask (middle columns/rows get higher weight)
+    fn generate_center_mask(&self) -> DynamicBitPackedBoard {
+        let mut mask = self.empty_board();
+        
+        let center_col = self.cols / 2;
+        let center_row = self.rows / 2;
+        
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                // Distance from center (Manhattan distance)
+                let distance = row.abs_diff(center_row) + col.abs_diff(center_col);
+                
+                // Center positions are more valuable
+                if distance <= 2 {
+                    mask.set_bit(row * self.cols + col, true);
+                }
+            }
+        }
+        
+        mask
+    }
+    
+    /// Generate edge control mask
+    fn generate_edge_mask(&self) -> DynamicBitPackedBoard {
+        let mut mask = self.empty_board();
+        
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                if row == 0 || row == self.rows - 1 || col == 0 || col == self.cols - 1 {
+                    mask.set_bit(row * self.cols + col, true);
+                }
+            }
+        }
+        
+        mask
+    }
+}
+
+impl BoardGeometry for DynamicQuadraticGrid {
+    fn to_index(&self, coord: (i32, i32)) -> Option<usize> {
+        if self.is_valid(coord) {
+            Some((coord.0 as usize) * self.cols + (coord.1 as usize))
+        } else {
+            None
+        }
+    }
+    
+    fn from_index(&self, index: usize) -> Option<(i32, i32)> {
+        if index < self.rows * self.cols {
+            Some(((index / self.cols) as i32, (index % self.cols) as i32))
+        } else {
+            None
+        }
+    }
+    
+    fn get_neighbors(&self, coord: (i32, i32)) -> Vec<(i32, i32)> {
+        let (row, col) = coord;
+        let mut neighbors = Vec::new();
+        
+        // 8-directional neighbors (including diagonals)
+        for dr in -1..=1 {
+            for dc in -1..=1 {
+                if (dr != 0 || dc != 0) && self.is_valid((row + dr, col + dc)) {
+                    neighbors.push((row + dr, col + dc));
+                }
+            }
+        }
+        
+        neighbors
+    }
+    
+    fn is_valid(&self, coord: (i32, i32)) -> bool {
+        let (row, col) = coord;
+        row >= 0 && row < self.rows as i32 && col >= 0 && col < self.cols as i32
+    }
+    
+    fn board_size(&self) -> usize {
+        self.rows * self.cols
+    }
+    
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Connect4Grid, PatternProvider};
+    
+    #[test]
+    fn test_matches_const_generic_grid() {
+        let fixed: Connect4Grid = Connect4Grid::new();
+        let dynamic = DynamicQuadraticGrid::new(6, 7, 2);
+        
+        for length in [3, 4, 5] {
+            let fixed_lines: Vec<&Vec<u64>> = fixed.get_winning_lines(length).iter().map(|l| &l.data).collect();
+            let dynamic_lines: Vec<&Vec<u64>> = dynamic.get_winning_lines(length).iter().map(|l| &l.data).collect();
+            assert_eq!(fixed_lines, dynamic_lines);
+        }
+        assert_eq!(fixed.get_center_mask().data, dynamic.get_center_mask().data);
+        assert_eq!(dynamic.get_winning_lines(6).len(), 0);
+    }
+    
+    #[test]
+    fn test_custom_line_length() {
+        let grid = DynamicQuadraticGrid::with_line_length(7, 10, 2, 6);
+        
+        // 10 wide, 7 high: 5 horizontal starts per row, 2 vertical starts per column
+        let lines = grid.get_winning_lines(6);
+        assert_eq!(lines.len(), 7 * 5 + 10 * 2 + 2 * 5 * 2);
+        assert!(lines.iter().all(|line| line.count_set_bits() == 6));
+        assert_eq!(grid.to_index((6, 9)), Some(69));
+        assert_eq!(grid.to_index((7, 0)), None);
+    }
+}
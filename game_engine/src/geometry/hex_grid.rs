@@ -200,6 +200,49 @@ impl<const ROWS: usize, const COLS: usize, const BITS_PER_CELL: usize> HexGrid<R
         }
     }
     
+    /// Find the winning chain of a player's stones (shortest connection between their edges)
+    /// Player 1 connects the top and bottom rows, Player 2 the left and right columns
+    /// Returns the chain as cell indices from one edge to the other, None if not connected
+    pub fn get_winning_chain(&self, stones: &BitPackedBoard<ROWS, COLS, BITS_PER_CELL>, player: u8) -> Option<Vec<usize>> {
+        // Position of a cell across the board in the player's connection direction
+        let depth = |index: usize| if player == 2 { index % COLS } else { index / COLS };
+        let last = if player == 2 { COLS - 1 } else { ROWS - 1 };
+        
+        // Breadth-first search from the starting edge keeps the chain as short as possible
+        let mut previous: Vec<Option<usize>> = vec![None; ROWS * COLS];
+        let mut visited = vec![false; ROWS * COLS];
+        let mut queue = std::collections::VecDeque::new();
+        for index in (0..ROWS * COLS).filter(|&index| depth(index) == 0 && stones.get_bit(index)) {
+            visited[index] = true;
+            queue.push_back(index);
+        }
+        
+        while let Some(index) = queue.pop_front() {
+            if depth(index) == last {
+                let mut chain = vec![index];
+                while let Some(prev) = previous[*chain.last().unwrap()] {
+                    chain.push(prev);
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            
+            let Some((q, r)) = self.index_to_axial(index) else { continue };
+            let neighbors = self.get_hex_neighbors(q, r)
+                .into_iter()
+                .filter_map(|(nq, nr)| self.axial_to_index(nq, nr));
+            for next in neighbors {
+                if !visited[next] && stones.get_bit(next) {
+                    visited[next] = true;
+                    previous[next] = Some(index);
+                    queue.push_back(next);
+                }
+            }
+        }
+        
+        None
+    }
+    
     /// Get specific edge mask
     pub fn get_edge_mask(&self, edge: HexEdge) -> &BitPackedBoard<ROWS, COLS, BITS_PER_CELL> {
         match edge {
@@ -270,10 +313,6 @@ impl<const ROWS: usize, const COLS: usize, const BITS_PER_CELL: usize> PatternPr
         self.winning_paths_player2 = self.generate_player2_paths();
         self.generate_edge_masks();
         self.generate_center_mask();
-        
-        println!("🔷 HexGrid patterns generated: {} Player1 paths, {} Player2 paths", 
-            self.winning_paths_player1.len(),
-            self.winning_paths_player2.len());
     }
 }
 
@@ -353,4 +392,25 @@ mod tests {
         assert!(top_positions > 0);
         assert!(bottom_positions > 0);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_winning_chain() {
+        let grid: StandardHexGrid = HexGrid::new(5);
+        let mut stones = BitPackedBoard::new();
+        
+        // A straight column of stones connects top and bottom (offset rows are hex neighbors)
+        for row in 0..11 {
+            stones.set_bit(row * 11 + 3, true);
+        }
+        stones.set_bit(5 * 11 + 8, true); // Stray stone off the chain
+        
+        let chain = grid.get_winning_chain(&stones, 1).unwrap();
+        assert_eq!(chain.len(), 11);
+        assert_eq!(chain[0], 3);
+        assert_eq!(chain[10], 10 * 11 + 3);
+        assert!(grid.get_winning_chain(&stones, 2).is_none());
+        
+        stones.set_bit(5 * 11 + 3, false);
+        assert!(grid.get_winning_chain(&stones, 1).is_none());
+    }
+}
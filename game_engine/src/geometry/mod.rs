@@ -1,10 +1,12 @@
 use crate::data::BitPackedBoard;
 
 pub mod quadratic_grid;
+pub mod dynamic_quadratic_grid;
 pub mod hex_grid;
 pub mod tests;
 
 pub use quadratic_grid::{QuadraticGrid, Connect4Grid, GomokuGrid};
+pub use dynamic_quadratic_grid::DynamicQuadraticGrid;
 pub use hex_grid::{HexGrid, StandardHexGrid, HexEdge};
 
 /// Defines the "shape" and navigation rules of a game board
@@ -221,10 +221,6 @@ impl<const ROWS: usize, const COLS: usize, const BITS_PER_CELL: usize> PatternPr
         // Generate positional masks
         self.center_mask = self.generate_center_mask();
         self.edge_mask = self.generate_edge_mask();
-        
-        println!("🎯 QuadraticGrid patterns generated: {} lines of 4, {} lines of 5", 
-            self.lines_of_4.len(),
-            self.lines_of_5.len());
     }
 }
 
@@ -1,3 +1,4 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use rand::seq::SliceRandom;
 use rand::seq::IteratorRandom;
@@ -9,11 +10,14 @@ pub mod data;
 pub mod geometry;
 pub mod games;
 pub mod ai;
+pub mod net;
+pub mod error;
 
 // Re-export key types for public API
-pub use geometry::{BoardGeometry, PatternProvider, QuadraticGrid, Connect4Grid, GomokuGrid, HexGrid, StandardHexGrid, HexEdge};
-pub use games::{Connect4Game, GomokuGame, LGame, TrioGame};
-pub use ai::{Connect4AI, GomokuAI, PatternEvaluator};
+pub use geometry::{BoardGeometry, PatternProvider, QuadraticGrid, DynamicQuadraticGrid, Connect4Grid, GomokuGrid, HexGrid, StandardHexGrid, HexEdge};
+pub use games::{Connect4Game, GomokuGame, LGame, MnkGame, TrioGame, TrioCompetition};
+pub use ai::{Connect4AI, EvaluatorParams, GomokuAI, MnkAI, PatternEvaluator};
+pub use error::{GameError, GameErrorInfo, Locale};
 
 // A macro to provide `println!(..)`-style syntax for `console.log` logging.
 #[cfg(feature = "web_sys")]
@@ -32,7 +36,7 @@ macro_rules! console_log {
 #[cfg(feature = "console_error_panic_hook")]
 extern crate console_error_panic_hook;
 
-#[wasm_bindgen(start)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(start))]
 pub fn main() {
     #[cfg(feature = "console_error_panic_hook")]
     console_error_panic_hook::set_once();
@@ -42,7 +46,7 @@ pub fn main() {
 }
 
 /// Game phase enumeration for strategic evaluation
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum GamePhase {
     Opening,
@@ -51,7 +55,7 @@ pub enum GamePhase {
 }
 
 /// Position analysis structure for AI decision making
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PositionAnalysis {
     pub current_player_threats: usize,
     pub opponent_threats: usize,
@@ -61,24 +65,24 @@ pub struct PositionAnalysis {
     pub evaluation_score: i32,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PositionAnalysis {
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_current_player_threats(&self) -> usize { self.current_player_threats }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_opponent_threats(&self) -> usize { self.opponent_threats }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_total_pieces(&self) -> usize { self.total_pieces }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_connectivity_score(&self) -> i32 { self.connectivity_score }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_game_phase(&self) -> GamePhase { self.game_phase }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_evaluation_score(&self) -> i32 { self.evaluation_score }
     
     /// Get threat advantage (positive = current player has more threats)
@@ -104,40 +108,72 @@ impl PositionAnalysis {
         )
     }
 }
-// Custom error types for robust error handling instead of String errors
-#[derive(Debug, Clone)]
-#[wasm_bindgen]
-pub enum GameError {
-    OutOfBounds,
-    PositionOccupied, 
-    GameAlreadyOver,
-    InvalidPlayer,
-    BoardError,
-    InvalidMove,
+
+/// A completed line on the board (used to highlight the cells that decided the game)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WinningLine {
+    player: Player,
+    cells: Vec<(usize, usize)>,
 }
 
-impl std::fmt::Display for GameError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            GameError::OutOfBounds => write!(f, "Position out of bounds"),
-            GameError::PositionOccupied => write!(f, "Position already occupied"),
-            GameError::GameAlreadyOver => write!(f, "Game is already over"),
-            GameError::InvalidPlayer => write!(f, "Invalid player"),
-            GameError::BoardError => write!(f, "Board operation failed"),
-            GameError::InvalidMove => write!(f, "Invalid move"),
-        }
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl WinningLine {
+    /// Player who owns the stones of the line
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn player(&self) -> Player { self.player }
+    
+    /// Cells of the line in order, flattened as (row, col) pairs
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn cells(&self) -> Vec<usize> {
+        self.cells.iter().flat_map(|&(row, col)| [row, col]).collect()
     }
+    
+    /// Number of stones in the line (more than the win length for overlines)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn length(&self) -> usize { self.cells.len() }
 }
 
-impl std::error::Error for GameError {}
-
-impl From<GameError> for String {
-    fn from(error: GameError) -> Self {
-        error.to_string()
+impl WinningLine {
+    /// Cells of the line as (row, col)
+    pub fn positions(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+    
+    /// Build lines from completed fixed-length windows (cells in line order)
+    /// Overlapping collinear windows of an overline are merged into a single line
+    pub fn from_windows(player: Player, windows: Vec<Vec<(usize, usize)>>) -> Vec<WinningLine> {
+        let direction = |cells: &[(usize, usize)]| {
+            (cells[1].0 as i32 - cells[0].0 as i32, cells[1].1 as i32 - cells[0].1 as i32)
+        };
+        
+        let mut lines: Vec<WinningLine> = Vec::new();
+        for window in windows.into_iter().filter(|window| window.len() >= 2) {
+            let merged = lines.iter_mut().find(|line| {
+                direction(&line.cells) == direction(&window) && window.iter().any(|cell| line.cells.contains(cell))
+            });
+            match merged {
+                Some(line) => {
+                    line.cells.extend(window);
+                    line.cells.sort_unstable();
+                    line.cells.dedup();
+                }
+                None => lines.push(WinningLine { player, cells: window }),
+            }
+        }
+        lines
+    }
+    
+    /// All cells of several lines, deduplicated and flattened as (row, col) pairs
+    pub fn flatten_cells(lines: &[WinningLine]) -> Vec<usize> {
+        let mut cells: Vec<(usize, usize)> = lines.iter().flat_map(|line| line.cells.iter().copied()).collect();
+        cells.sort_unstable();
+        cells.dedup();
+        cells.into_iter().flat_map(|(row, col)| [row, col]).collect()
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)] // Added Debug trait
 pub enum Player {
     Yellow = 1, // Assign integer values for easier conversion
@@ -170,29 +206,29 @@ impl From<Player> for i8 {
 }
 
 impl TryFrom<i8> for Player {
-    type Error = String; // Changed from JsValue to String
+    type Error = GameError;
     fn try_from(value: i8) -> Result<Self, Self::Error> {
         match value {
             1 => Ok(Player::Yellow),
             2 => Ok(Player::Red),
             3 => Ok(Player::Black),
             4 => Ok(Player::White),
-            _ => Err("Invalid player value".to_string()), // Convert to String
+            _ => Err(GameError::InvalidPlayer),
         }
     }
 }
 
 // New Board structure
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Board {
     rows: usize,
     cols: usize,
     cells: Vec<i8>, // Renamed from 'board' to 'cells' for clarity
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Board {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new(rows: usize, cols: usize) -> Self {
         let board_size = rows * cols;
         let cells = vec![0; board_size]; // 0 for empty
@@ -284,7 +320,7 @@ impl Board {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Game {
     board: Board, // Use the new Board structure
     win_condition: usize,
@@ -293,9 +329,9 @@ pub struct Game {
     starting_player: Player, // Track who should start the game
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Game {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new(rows: usize, cols: usize, win_condition: usize, gravity_enabled: bool) -> Self {
         let board = Board::new(rows, cols);
         Game {
@@ -307,13 +343,13 @@ impl Game {
         }
     }
 
-    // This function will be exposed to JS, so we need to convert the String error to JsValue
-    pub fn make_move_connect4_js(&mut self, col: usize) -> Result<(), JsValue> {
-        self._make_move_connect4(col).map_err(|e| JsValue::from_str(&e.to_string()))
+    // Exposed to JS, where the GameError is thrown as its message
+    pub fn make_move_connect4_js(&mut self, col: usize) -> Result<(), GameError> {
+        self._make_move_connect4(col)
     }
 
-    pub fn make_move_gobang_js(&mut self, row: usize, col: usize) -> Result<(), JsValue> {
-        self._make_move_gobang(row, col).map_err(|e| JsValue::from_str(&e.to_string()))
+    pub fn make_move_gobang_js(&mut self, row: usize, col: usize) -> Result<(), GameError> {
+        self._make_move_gobang(row, col)
     }
 
     // Internal make_move for Connect4 that returns a GameError
@@ -493,6 +529,34 @@ impl Game {
         Some(player_to_check) // Win condition met for this player
     }
 
+    /// Cells of every completed line, flattened as (row, col) pairs (empty while the game is running)
+    pub fn get_winning_cells(&self) -> Vec<usize> {
+        WinningLine::flatten_cells(&self.get_completed_lines())
+    }
+
+    /// Every completed line on the board (one move may complete several)
+    pub fn get_completed_lines(&self) -> Vec<WinningLine> {
+        let mut lines = Vec::new();
+        for player in [Player::Yellow, Player::Red, Player::Black, Player::White] {
+            let mut windows = Vec::new();
+            for (dr, dc) in [(0, 1), (1, 0), (1, 1), (1, -1)] {
+                for r in 0..self.board.rows {
+                    for c in 0..self.board.cols {
+                        if self._check_direction(r, c, dr, dc, player).is_some() {
+                            windows.push(
+                                (0..self.win_condition as isize)
+                                    .map(|i| ((r as isize + dr * i) as usize, (c as isize + dc * i) as usize))
+                                    .collect(),
+                            );
+                        }
+                    }
+                }
+            }
+            lines.extend(WinningLine::from_windows(player, windows));
+        }
+        lines
+    }
+
     pub fn is_game_over(&self) -> bool {
         self.check_win().is_some() || self.board.is_full()
     }
@@ -565,14 +629,14 @@ impl Game {
     }
     
     /// Simulate a move efficiently (for AI tree search)
-    pub fn simulate_move_connect4(&self, col: usize) -> Result<Game, String> {
+    pub fn simulate_move_connect4(&self, col: usize) -> Result<Game, GameError> {
         let mut cloned = self.fast_clone();
         cloned._make_move_connect4(col)?;
         Ok(cloned)
     }
     
     /// Simulate a move efficiently (WASM-compatible version)
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn simulate_move_connect4_js(&self, col: usize) -> Option<Game> {
         self.simulate_move_connect4(col).ok()
     }
@@ -786,7 +850,7 @@ impl Game {
     }
 
     /// Simulate a Gobang move without mutating the current game state
-    pub fn simulate_move_gobang(&self, row: usize, col: usize) -> Result<Game, String> {
+    pub fn simulate_move_gobang(&self, row: usize, col: usize) -> Result<Game, GameError> {
         let mut cloned_game = self.fast_clone();
         cloned_game._make_move_gobang(row, col)?;
         Ok(cloned_game)
@@ -1566,15 +1630,15 @@ impl Game {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TrioGameLegacy {
     board: Board,
     target_number: u8,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TrioGameLegacy {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new(difficulty: u8) -> Self {
         let mut board = Board::new(7, 7);
         let mut rng = thread_rng();
@@ -1751,7 +1815,7 @@ impl TrioGameLegacy {
 }
 
 // Trio Gap Analysis and Reachability Structures
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug)]
 pub struct ReachabilityAnalysis {
     reachable_targets: Vec<i16>,
@@ -1762,34 +1826,34 @@ pub struct ReachabilityAnalysis {
     max_reachable: i16,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl ReachabilityAnalysis {
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_reachable_targets(&self) -> Vec<i16> {
         self.reachable_targets.clone()
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_unreachable_targets(&self) -> Vec<i16> {
         self.unreachable_targets.clone()
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_total_reachable(&self) -> usize {
         self.total_reachable
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_coverage_percentage(&self) -> f32 {
         self.coverage_percentage
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_min_reachable(&self) -> i16 {
         self.min_reachable
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_max_reachable(&self) -> i16 {
         self.max_reachable
     }
@@ -1807,7 +1871,7 @@ impl ReachabilityAnalysis {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug)]
 pub struct SolutionAnalysis {
     target: i16,
@@ -1818,34 +1882,34 @@ pub struct SolutionAnalysis {
     difficulty_score: f32,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl SolutionAnalysis {
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_target(&self) -> i16 {
         self.target
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_total_solutions(&self) -> usize {
         self.total_solutions
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_unique_formulas(&self) -> Vec<String> {
         self.unique_formulas.clone()
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_add_operations(&self) -> usize {
         self.add_operations
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_subtract_operations(&self) -> usize {
         self.subtract_operations
     }
     
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn get_difficulty_score(&self) -> f32 {
         self.difficulty_score
     }
@@ -1862,7 +1926,7 @@ impl SolutionAnalysis {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum TrioDifficulty {
     Impossible,  // 0 solutions
@@ -1872,7 +1936,7 @@ pub enum TrioDifficulty {
     VeryEasy,    // 11+ solutions
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum TrioDistribution {
     Balanced,    // Equal distribution 1-9
@@ -2132,7 +2196,7 @@ impl TrioGameLegacy {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TrioGameLegacy {
     /// Create new game with specific distribution (WASM-exposed)
     pub fn new_with_distribution_wasm(distribution: TrioDistribution) -> Self {
@@ -0,0 +1,9 @@
+pub mod peer;
+pub mod protocol;
+pub mod transport;
+
+pub use peer::{LockstepGame, LockstepPeer};
+#[cfg(feature = "wasm")]
+pub use peer::{Connect4LockstepPeer, GomokuLockstepPeer, LGameLockstepPeer};
+pub use protocol::{MessageBody, ProtocolMessage, StateHasher, PROTOCOL_VERSION};
+pub use transport::{LoopbackTransport, Transport};
@@ -0,0 +1,525 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::{GameError, Player};
+use crate::games::{Connect4Game, GomokuGame, LGame};
+use crate::net::protocol::{MessageBody, ProtocolMessage, StateHasher};
+use crate::net::transport::Transport;
+
+/// Games playable over the lockstep protocol
+pub trait LockstepGame: Clone {
+    /// Deterministic hash of everything that affects play (board, player to move, outcome)
+    fn state_hash(&self) -> u64;
+    fn current_player(&self) -> Player;
+    /// Apply a move in the protocol encoding through the rule engine
+    fn apply_move(&mut self, game_move: &[usize]) -> Result<bool, GameError>;
+    fn resign(&mut self, player: Player) -> bool;
+    fn offer_draw(&mut self, player: Player) -> bool;
+    fn accept_draw(&mut self, player: Player) -> bool;
+    /// Claim a draw for the player to move; false where the rules don't allow it
+    fn claim_draw(&mut self) -> bool;
+}
+
+impl LockstepGame for Connect4Game {
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        hasher.write_usize(self.get_rows());
+        hasher.write_usize(self.get_cols());
+        for row in 0..self.get_rows() {
+            for col in 0..self.get_cols() {
+                hasher.write_u8(self.get_cell(row, col));
+            }
+        }
+        hasher.write_player(Some(self.current_player()));
+        hasher.write_player(self.winner());
+        hasher.write_u8(self.is_game_over() as u8);
+        hasher.finish()
+    }
+    
+    fn current_player(&self) -> Player { self.current_player() }
+    
+    /// Moves are Connect4 move codes (`column`, or `cols + column` for a PopOut pop)
+    fn apply_move(&mut self, game_move: &[usize]) -> Result<bool, GameError> {
+        match *game_move {
+            [code] => self.make_move_code_internal(code),
+            _ => Err(GameError::InvalidMove),
+        }
+    }
+    
+    fn resign(&mut self, player: Player) -> bool { self.resign(player) }
+    fn offer_draw(&mut self, player: Player) -> bool { self.offer_draw(player) }
+    fn accept_draw(&mut self, player: Player) -> bool { self.accept_draw(player) }
+    fn claim_draw(&mut self) -> bool { self.claim_draw() }
+}
+
+impl LockstepGame for GomokuGame {
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        for row in 0..15 {
+            for col in 0..15 {
+                hasher.write_u8(self.get_cell(row, col));
+            }
+        }
+        hasher.write_player(Some(self.current_player()));
+        hasher.write_player(self.winner());
+        hasher.write_u8(self.is_game_over() as u8);
+        hasher.finish()
+    }
+    
+    fn current_player(&self) -> Player { self.current_player() }
+    
+    /// Moves are (row, col)
+    fn apply_move(&mut self, game_move: &[usize]) -> Result<bool, GameError> {
+        match *game_move {
+            [row, col] => self.make_move_internal(row, col),
+            _ => Err(GameError::InvalidMove),
+        }
+    }
+    
+    fn resign(&mut self, player: Player) -> bool { self.resign(player) }
+    fn offer_draw(&mut self, player: Player) -> bool { self.offer_draw(player) }
+    fn accept_draw(&mut self, player: Player) -> bool { self.accept_draw(player) }
+    fn claim_draw(&mut self) -> bool { self.claim_draw() }
+}
+
+impl LockstepGame for LGame {
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        for row in 0..4 {
+            for col in 0..4 {
+                hasher.write_u8(self.get_cell(row, col));
+            }
+        }
+        hasher.write_player(Some(self.current_player()));
+        hasher.write_player(self.winner());
+        hasher.write_u8(self.is_game_over() as u8);
+        hasher.finish()
+    }
+    
+    fn current_player(&self) -> Player { self.current_player() }
+    
+    /// Moves are (row, col, orientation) of the L-piece, optionally followed by
+    /// (from row, from col, to row, to col) of a neutral piece
+    fn apply_move(&mut self, game_move: &[usize]) -> Result<bool, GameError> {
+        match *game_move {
+            [row, col, orientation] => self.make_move_internal(row, col, orientation as u8),
+            [row, col, orientation, from_row, from_col, to_row, to_col] => {
+                // Try both parts on a copy first, so a bad neutral move leaves the game untouched
+                let mut trial = self.clone();
+                if !trial.make_move_internal(row, col, orientation as u8)? {
+                    trial.move_neutral_piece(from_row, from_col, to_row, to_col)?;
+                }
+                if self.make_move_internal(row, col, orientation as u8)? {
+                    return Ok(true);
+                }
+                self.move_neutral_piece(from_row, from_col, to_row, to_col)?;
+                Ok(self.is_game_over())
+            },
+            _ => Err(GameError::InvalidMove),
+        }
+    }
+    
+    fn resign(&mut self, player: Player) -> bool { self.resign(player) }
+    fn offer_draw(&mut self, player: Player) -> bool { self.offer_draw(player) }
+    fn accept_draw(&mut self, player: Player) -> bool { self.accept_draw(player) }
+    fn claim_draw(&mut self) -> bool { false } // The L-Game has no claimable draws
+}
+
+/// One side of a lockstep game
+/// Both peers run the rule engine: local moves are validated before sending, remote moves before applying,
+/// and every message's move index and state hash is checked against the local state to detect desyncs.
+/// On a desync the authority (host) sends its full move list; the other peer asks for it and rebuilds.
+#[derive(Clone)]
+pub struct LockstepPeer<G: LockstepGame> {
+    initial: G,
+    game: G,
+    local_player: Player,
+    authority: bool,
+    moves: Vec<Vec<usize>>,
+    hashes: Vec<u64>,           // State hash after each move count (index 0: initial state)
+    awaiting_ack: Option<usize>, // Move index of our last move until the opponent confirms it
+    desynced: bool,
+    remote_clock: Option<(u64, u64)>,
+}
+
+impl<G: LockstepGame> LockstepPeer<G> {
+    /// Start a session from `game` (both peers must start from the same position)
+    pub fn new(game: G, local_player: Player, authority: bool) -> Self {
+        Self {
+            hashes: vec![game.state_hash()],
+            initial: game.clone(),
+            game,
+            local_player,
+            authority,
+            moves: Vec::new(),
+            awaiting_ack: None,
+            desynced: false,
+            remote_clock: None,
+        }
+    }
+    
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+    
+    pub fn local_player(&self) -> Player {
+        self.local_player
+    }
+    
+    pub fn is_authority(&self) -> bool {
+        self.authority
+    }
+    
+    /// Moves played so far
+    pub fn move_index(&self) -> usize {
+        self.moves.len()
+    }
+    
+    pub fn moves(&self) -> &[Vec<usize>] {
+        &self.moves
+    }
+    
+    pub fn state_hash(&self) -> u64 {
+        self.game.state_hash()
+    }
+    
+    /// True after a desync until a resync restores the authority's state
+    pub fn is_desynced(&self) -> bool {
+        self.desynced
+    }
+    
+    /// True while our last move has not been acknowledged
+    pub fn is_awaiting_ack(&self) -> bool {
+        self.awaiting_ack.is_some()
+    }
+    
+    /// Remaining main time (first color, second color) from the last clock sync
+    pub fn remote_clock(&self) -> Option<(u64, u64)> {
+        self.remote_clock
+    }
+    
+    /// Validate and play a local move; returns the message to send
+    pub fn play_move(&mut self, game_move: &[usize]) -> Result<ProtocolMessage, GameError> {
+        self.check_in_sync()?;
+        if self.game.current_player() != self.local_player {
+            return Err(GameError::NotYourTurn);
+        }
+        self.game.apply_move(game_move)?;
+        self.record_move(game_move.to_vec());
+        self.awaiting_ack = Some(self.moves.len());
+        Ok(self.message(MessageBody::Move(game_move.to_vec())))
+    }
+    
+    pub fn resign(&mut self) -> Result<ProtocolMessage, GameError> {
+        self.check_in_sync()?;
+        if !self.game.resign(self.local_player) {
+            return Err(GameError::GameAlreadyOver);
+        }
+        Ok(self.message(MessageBody::Resign(self.local_player)))
+    }
+    
+    pub fn offer_draw(&mut self) -> Result<ProtocolMessage, GameError> {
+        self.check_in_sync()?;
+        if !self.game.offer_draw(self.local_player) {
+            return Err(GameError::DrawOfferUnavailable);
+        }
+        Ok(self.message(MessageBody::DrawOffer(self.local_player)))
+    }
+    
+    pub fn accept_draw(&mut self) -> Result<ProtocolMessage, GameError> {
+        self.check_in_sync()?;
+        if !self.game.accept_draw(self.local_player) {
+            return Err(GameError::NoDrawOffer);
+        }
+        Ok(self.message(MessageBody::DrawAccept(self.local_player)))
+    }
+    
+    /// Share the main time left for the first and second color
+    pub fn clock_sync(&self, first_ms: u64, second_ms: u64) -> ProtocolMessage {
+        self.message(MessageBody::ClockSync { first_ms, second_ms })
+    }
+    
+    /// Handle a message from the opponent; returns the reply to send, if any
+    /// Errors are protocol violations (wrong version, the opponent acting for our color, a failed resync)
+    pub fn receive(&mut self, message: &ProtocolMessage) -> Result<Option<ProtocolMessage>, GameError> {
+        if message.version != crate::net::protocol::PROTOCOL_VERSION {
+            return Err(GameError::UnsupportedProtocolVersion(message.version));
+        }
+    
+        match &message.body {
+            MessageBody::ResyncRequest => Ok(self.authority.then(|| self.resync_message())),
+            MessageBody::Resync(moves) => {
+                if !self.authority {
+                    self.rebuild(moves, message.state_hash)?;
+                }
+                Ok(None)
+            },
+            // Anything else is ignored until the resync arrives
+            _ if self.desynced => Ok(None),
+            MessageBody::Ack => {
+                let confirmed = self.hashes.get(message.move_index) == Some(&message.state_hash);
+                if !confirmed {
+                    return Ok(self.desync());
+                }
+                if self.awaiting_ack == Some(message.move_index) {
+                    self.awaiting_ack = None;
+                }
+                Ok(None)
+            },
+            MessageBody::Move(game_move) => {
+                if message.move_index != self.moves.len() + 1
+                    || self.game.current_player() == self.local_player
+                    || self.game.apply_move(game_move).is_err()
+                {
+                    return Ok(self.desync());
+                }
+                self.record_move(game_move.clone());
+                if self.state_hash() != message.state_hash {
+                    return Ok(self.desync());
+                }
+                Ok(Some(self.message(MessageBody::Ack)))
+            },
+            MessageBody::Resign(player) | MessageBody::DrawOffer(player) | MessageBody::DrawAccept(player) => {
+                if *player == self.local_player {
+                    return Err(GameError::ImpersonatedPlayer);
+                }
+                let applied = message.move_index == self.moves.len()
+                    && match message.body {
+                        MessageBody::Resign(_) => self.game.resign(*player),
+                        MessageBody::DrawOffer(_) => self.game.offer_draw(*player),
+                        _ => self.game.accept_draw(*player),
+                    };
+                if !applied || self.state_hash() != message.state_hash {
+                    return Ok(self.desync());
+                }
+                Ok(None)
+            },
+            MessageBody::ClockSync { first_ms, second_ms } => {
+                if message.move_index != self.moves.len() || self.state_hash() != message.state_hash {
+                    return Ok(self.desync());
+                }
+                self.remote_clock = Some((*first_ms, *second_ms));
+                Ok(None)
+            },
+        }
+    }
+    
+    /// Receive and answer every pending message; returns how many were handled
+    pub fn pump(&mut self, transport: &mut impl Transport) -> Result<usize, GameError> {
+        let mut handled = 0;
+        while let Some(line) = transport.receive() {
+            let message = ProtocolMessage::decode(&line)?;
+            if let Some(reply) = self.receive(&message)? {
+                transport.send(&reply.encode());
+            }
+            handled += 1;
+        }
+        Ok(handled)
+    }
+    
+    /// Message for the current move count and state
+    fn message(&self, body: MessageBody) -> ProtocolMessage {
+        ProtocolMessage::new(self.moves.len(), self.state_hash(), body)
+    }
+    
+    fn resync_message(&self) -> ProtocolMessage {
+        self.message(MessageBody::Resync(self.moves.clone()))
+    }
+    
+    fn record_move(&mut self, game_move: Vec<usize>) {
+        self.moves.push(game_move);
+        self.hashes.push(self.game.state_hash());
+    }
+    
+    fn check_in_sync(&self) -> Result<(), GameError> {
+        if self.desynced {
+            return Err(GameError::OutOfSync);
+        }
+        Ok(())
+    }
+    
+    /// The authority's state is the truth, so it pushes it; the other peer asks for it
+    fn desync(&mut self) -> Option<ProtocolMessage> {
+        if self.authority {
+            return Some(self.resync_message());
+        }
+        self.desynced = true;
+        self.awaiting_ack = None;
+        Some(self.message(MessageBody::ResyncRequest))
+    }
+    
+    /// Replay the authority's move list from the initial position
+    fn rebuild(&mut self, moves: &[Vec<usize>], expected_hash: u64) -> Result<(), GameError> {
+        let mut rebuilt = Self::new(self.initial.clone(), self.local_player, self.authority);
+        for (index, game_move) in moves.iter().enumerate() {
+            rebuilt.game.apply_move(game_move).map_err(|_| GameError::ResyncMoveRejected(index + 1))?;
+            rebuilt.record_move(game_move.clone());
+        }
+        if rebuilt.state_hash() != expected_hash {
+            return Err(GameError::ResyncMismatch);
+        }
+        *self = Self { remote_clock: self.remote_clock, ..rebuilt };
+        Ok(())
+    }
+}
+
+/// wasm classes wrapping `LockstepPeer` for each game; messages cross the JS boundary as encoded lines
+#[cfg(feature = "wasm")]
+macro_rules! lockstep_peer_bindings {
+    ($($name:ident => $game:ty),* $(,)?) => {$(
+        #[wasm_bindgen]
+        pub struct $name(LockstepPeer<$game>);
+        
+        #[wasm_bindgen]
+        impl $name {
+            #[wasm_bindgen(constructor)]
+            pub fn new(game: &$game, local_player: Player, authority: bool) -> $name {
+                $name(LockstepPeer::new(game.clone(), local_player, authority))
+            }
+            
+            /// Snapshot of the synchronized game
+            pub fn game(&self) -> $game {
+                self.0.game().clone()
+            }
+            
+            pub fn move_index(&self) -> usize {
+                self.0.move_index()
+            }
+            
+            pub fn is_desynced(&self) -> bool {
+                self.0.is_desynced()
+            }
+            
+            pub fn play_move(&mut self, game_move: Vec<usize>) -> Result<String, GameError> {
+                self.0.play_move(&game_move).map(|message| message.encode())
+            }
+            
+            pub fn resign(&mut self) -> Result<String, GameError> {
+                self.0.resign().map(|message| message.encode())
+            }
+            
+            pub fn offer_draw(&mut self) -> Result<String, GameError> {
+                self.0.offer_draw().map(|message| message.encode())
+            }
+            
+            pub fn accept_draw(&mut self) -> Result<String, GameError> {
+                self.0.accept_draw().map(|message| message.encode())
+            }
+            
+            pub fn clock_sync(&self, first_ms: u64, second_ms: u64) -> String {
+                self.0.clock_sync(first_ms, second_ms).encode()
+            }
+            
+            /// Handle an encoded message; returns the encoded reply to send, if any
+            pub fn receive(&mut self, message: &str) -> Result<Option<String>, GameError> {
+                let message = ProtocolMessage::decode(message)?;
+                let reply = self.0.receive(&message)?;
+                Ok(reply.map(|reply| reply.encode()))
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "wasm")]
+lockstep_peer_bindings!(
+    Connect4LockstepPeer => Connect4Game,
+    GomokuLockstepPeer => GomokuGame,
+    LGameLockstepPeer => LGame,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::transport::LoopbackTransport;
+    
+    #[test]
+    fn test_lockstep_over_loopback() {
+        let (mut host_link, mut guest_link) = LoopbackTransport::pair();
+        let mut host = LockstepPeer::new(Connect4Game::new(), Player::Yellow, true);
+        let mut guest = LockstepPeer::new(Connect4Game::new(), Player::Red, false);
+    
+        assert!(guest.play_move(&[3]).is_err()); // Yellow moves first
+        host_link.send(&host.play_move(&[3]).unwrap().encode());
+        assert!(host.is_awaiting_ack());
+        assert_eq!(guest.pump(&mut guest_link), Ok(1));
+        assert_eq!(host.pump(&mut host_link), Ok(1)); // The ack
+        assert!(!host.is_awaiting_ack());
+    
+        guest_link.send(&guest.play_move(&[4]).unwrap().encode());
+        guest_link.send(&guest.clock_sync(58_000, 59_500).encode());
+        host.pump(&mut host_link).unwrap();
+        guest.pump(&mut guest_link).unwrap();
+        assert_eq!(host.remote_clock(), Some((58_000, 59_500)));
+        assert_eq!(host.state_hash(), guest.state_hash());
+        assert_eq!(host.move_index(), 2);
+    
+        host_link.send(&host.offer_draw().unwrap().encode());
+        guest.pump(&mut guest_link).unwrap();
+        guest_link.send(&guest.accept_draw().unwrap().encode());
+        host.pump(&mut host_link).unwrap();
+        assert!(host.game().is_draw() && guest.game().is_draw());
+        assert!(!host.is_desynced() && !guest.is_desynced());
+    }
+    
+    #[test]
+    fn test_desync_and_resync() {
+        let (mut host_link, mut guest_link) = LoopbackTransport::pair();
+        let mut host = LockstepPeer::new(GomokuGame::new(), Player::Black, true);
+        let mut guest = LockstepPeer::new(GomokuGame::new(), Player::White, false);
+    
+        host_link.send(&host.play_move(&[7, 7]).unwrap().encode());
+        guest.pump(&mut guest_link).unwrap();
+        guest_link.send(&guest.play_move(&[0, 0]).unwrap().encode());
+        host.pump(&mut host_link).unwrap();
+        guest.pump(&mut guest_link).unwrap();
+    
+        // The guest's board drifts (e.g. a corrupted message was applied), so the host's next move no longer fits
+        guest.game.apply_move(&[1, 1]).unwrap();
+        host_link.send(&host.play_move(&[7, 8]).unwrap().encode());
+    
+        guest.pump(&mut guest_link).unwrap();
+        assert!(guest.is_desynced());
+        assert!(guest.play_move(&[8, 8]).is_err());
+        host.pump(&mut host_link).unwrap(); // Answers the resync request
+        guest.pump(&mut guest_link).unwrap();
+        assert!(!guest.is_desynced());
+        assert_eq!(guest.state_hash(), host.state_hash());
+        assert_eq!(guest.moves(), host.moves());
+    
+        // The opponent may not act for the local player
+        let forged = ProtocolMessage::new(guest.move_index(), guest.state_hash(), MessageBody::Resign(Player::White));
+        assert!(guest.receive(&forged).is_err());
+    }
+    
+    #[test]
+    fn test_l_game_moves_with_neutral_piece() {
+        let mut host = LockstepPeer::new(LGame::new(), Player::Yellow, true);
+        let mut guest = LockstepPeer::new(LGame::new(), Player::Red, false);
+        let (row, col, orientation) = host.game().get_all_valid_moves()[0];
+        let neutral = host.game().get_neutral_positions();
+        let (from_row, from_col) = (neutral[0] as usize, neutral[1] as usize);
+        let target = (0..16).map(|cell| (cell / 4, cell % 4)).find(|&(r, c)| {
+            let mut probe = host.game().clone();
+            probe.make_move_internal(row, col, orientation).is_ok() && probe.move_neutral_piece(from_row, from_col, r, c).is_ok()
+        });
+    
+        let (to_row, to_col) = target.unwrap();
+        let message = host.play_move(&[row, col, orientation as usize, from_row, from_col, to_row, to_col]).unwrap();
+        let ack = guest.receive(&message).unwrap().unwrap();
+        assert_eq!(ack.body, MessageBody::Ack);
+        assert_eq!(host.receive(&ack), Ok(None));
+        assert_eq!(guest.state_hash(), host.state_hash());
+    }
+    
+    #[test]
+    fn test_l_game_bad_neutral_move_leaves_game_untouched() {
+        let mut game = LGame::new();
+        let (row, col, orientation) = game.get_all_valid_moves()[0];
+        let before = game.state_hash();
+        
+        // (0, 0) holds no neutral piece
+        let result = LockstepGame::apply_move(&mut game, &[row, col, orientation as usize, 0, 0, 1, 1]);
+        assert_eq!(result, Err(GameError::NeutralNotFound));
+        assert_eq!(game.state_hash(), before);
+        assert_eq!(game.get_move_count(), 0);
+    }
+}
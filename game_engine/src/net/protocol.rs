@@ -0,0 +1,168 @@
+use crate::{GameError, Player};
+
+/// Wire protocol version; peers reject messages of any other version
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// What a message says
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MessageBody {
+    Move(Vec<usize>),       // Move in the game's encoding (Connect4 move code, (row, col), L-Game (row, col, orientation))
+    Ack,                    // Confirms the move and state hash at `move_index`
+    Resign(Player),
+    DrawOffer(Player),
+    DrawAccept(Player),
+    ClockSync { first_ms: u64, second_ms: u64 }, // Main time left for the first and second color
+    ResyncRequest,
+    Resync(Vec<Vec<usize>>), // Full move list from the authority
+}
+
+/// Lockstep message: every message carries the sender's move count and state hash after its action
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtocolMessage {
+    pub version: u32,
+    pub move_index: usize,
+    pub state_hash: u64,
+    pub body: MessageBody,
+}
+
+impl ProtocolMessage {
+    pub fn new(move_index: usize, state_hash: u64, body: MessageBody) -> Self {
+        Self { version: PROTOCOL_VERSION, move_index, state_hash, body }
+    }
+    
+    /// Text encoding, one message per line: `v1 <move index> <hash> <kind> [payload]`
+    /// e.g. `v1 5 00c0ffee00c0ffee move 3` or `v1 4 ... resync 3;3;4;2`
+    pub fn encode(&self) -> String {
+        let (kind, payload) = match &self.body {
+            MessageBody::Move(game_move) => ("move", encode_move(game_move)),
+            MessageBody::Ack => ("ack", String::new()),
+            MessageBody::Resign(player) => ("resign", format!("{:?}", player)),
+            MessageBody::DrawOffer(player) => ("draw_offer", format!("{:?}", player)),
+            MessageBody::DrawAccept(player) => ("draw_accept", format!("{:?}", player)),
+            MessageBody::ClockSync { first_ms, second_ms } => ("clock", format!("{},{}", first_ms, second_ms)),
+            MessageBody::ResyncRequest => ("resync_request", String::new()),
+            MessageBody::Resync(moves) => ("resync", moves.iter().map(|game_move| encode_move(game_move)).collect::<Vec<_>>().join(";")),
+        };
+        let line = format!("v{} {} {:016x} {}", self.version, self.move_index, self.state_hash, kind);
+        if payload.is_empty() { line } else { format!("{} {}", line, payload) }
+    }
+    
+    /// Parse an encoded message
+    pub fn decode(line: &str) -> Result<ProtocolMessage, GameError> {
+        let malformed = || GameError::MalformedMessage;
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (version, move_index, state_hash, kind, payload) = match fields[..] {
+            [version, move_index, state_hash, kind] => (version, move_index, state_hash, kind, ""),
+            [version, move_index, state_hash, kind, payload] => (version, move_index, state_hash, kind, payload),
+            _ => return Err(malformed()),
+        };
+    
+        let version: u32 = version.strip_prefix('v').and_then(|version| version.parse().ok()).ok_or_else(malformed)?;
+        if version != PROTOCOL_VERSION {
+            return Err(GameError::UnsupportedProtocolVersion(version));
+        }
+        let move_index = move_index.parse().map_err(|_| malformed())?;
+        let state_hash = u64::from_str_radix(state_hash, 16).map_err(|_| malformed())?;
+    
+        let body = match (kind, payload) {
+            ("move", payload) => MessageBody::Move(decode_move(payload).ok_or_else(malformed)?),
+            ("ack", "") => MessageBody::Ack,
+            ("resign", payload) => MessageBody::Resign(decode_player(payload).ok_or_else(malformed)?),
+            ("draw_offer", payload) => MessageBody::DrawOffer(decode_player(payload).ok_or_else(malformed)?),
+            ("draw_accept", payload) => MessageBody::DrawAccept(decode_player(payload).ok_or_else(malformed)?),
+            ("clock", payload) => {
+                let (first_ms, second_ms) = payload.split_once(',').ok_or_else(malformed)?;
+                MessageBody::ClockSync {
+                    first_ms: first_ms.parse().map_err(|_| malformed())?,
+                    second_ms: second_ms.parse().map_err(|_| malformed())?,
+                }
+            },
+            ("resync_request", "") => MessageBody::ResyncRequest,
+            ("resync", "") => MessageBody::Resync(Vec::new()),
+            ("resync", payload) => MessageBody::Resync(
+                payload.split(';').map(decode_move).collect::<Option<Vec<_>>>().ok_or_else(malformed)?,
+            ),
+            _ => return Err(malformed()),
+        };
+        Ok(ProtocolMessage { version, move_index, state_hash, body })
+    }
+}
+
+fn encode_move(game_move: &[usize]) -> String {
+    game_move.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn decode_move(payload: &str) -> Option<Vec<usize>> {
+    payload.split(',').map(|value| value.parse().ok()).collect()
+}
+
+fn decode_player(name: &str) -> Option<Player> {
+    match name {
+        "Yellow" => Some(Player::Yellow),
+        "Red" => Some(Player::Red),
+        "Black" => Some(Player::Black),
+        "White" => Some(Player::White),
+        _ => None,
+    }
+}
+
+/// FNV-1a hash for game states
+/// Unlike `DefaultHasher` its output is fixed, so peers built with different toolchains or targets agree
+#[derive(Clone, Copy, Debug)]
+pub struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl StateHasher {
+    pub fn write_u8(&mut self, value: u8) {
+        self.0 ^= value as u64;
+        self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    
+    pub fn write_usize(&mut self, value: usize) {
+        for byte in (value as u64).to_le_bytes() {
+            self.write_u8(byte);
+        }
+    }
+    
+    pub fn write_player(&mut self, player: Option<Player>) {
+        self.write_u8(player.map_or(0, |player| player as u8));
+    }
+    
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_message_round_trip() {
+        let messages = [
+            ProtocolMessage::new(1, 0xdead_beef, MessageBody::Move(vec![3])),
+            ProtocolMessage::new(1, 0xdead_beef, MessageBody::Ack),
+            ProtocolMessage::new(6, 42, MessageBody::Resign(Player::White)),
+            ProtocolMessage::new(6, 42, MessageBody::DrawOffer(Player::Yellow)),
+            ProtocolMessage::new(6, 42, MessageBody::DrawAccept(Player::Red)),
+            ProtocolMessage::new(2, 7, MessageBody::ClockSync { first_ms: 59_000, second_ms: 61_500 }),
+            ProtocolMessage::new(2, 7, MessageBody::ResyncRequest),
+            ProtocolMessage::new(2, 7, MessageBody::Resync(vec![vec![7, 7], vec![7, 8]])),
+            ProtocolMessage::new(0, 7, MessageBody::Resync(Vec::new())),
+        ];
+        for message in messages {
+            assert_eq!(ProtocolMessage::decode(&message.encode()), Ok(message));
+        }
+    
+        assert_eq!(ProtocolMessage::new(1, 255, MessageBody::Move(vec![7, 7])).encode(), "v1 1 00000000000000ff move 7,7");
+        assert_eq!(ProtocolMessage::decode("v2 1 ff ack"), Err(GameError::UnsupportedProtocolVersion(2)));
+        assert!(ProtocolMessage::decode("v1 1 ff move").is_err());
+        assert!(ProtocolMessage::decode("v1 1 zz ack").is_err());
+        assert!(ProtocolMessage::decode("v1 1 ff resign Green").is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Carries encoded protocol messages between two peers (WebSocket, WebRTC data channel, ...)
+pub trait Transport {
+    fn send(&mut self, message: &str);
+    
+    /// Next received message, if any
+    fn receive(&mut self) -> Option<String>;
+}
+
+/// In-process transport: one end of a connected pair of message queues
+#[derive(Clone, Debug, Default)]
+pub struct LoopbackTransport {
+    outgoing: Rc<RefCell<VecDeque<String>>>,
+    incoming: Rc<RefCell<VecDeque<String>>>,
+}
+
+impl LoopbackTransport {
+    /// Two connected ends: what one sends, the other receives
+    pub fn pair() -> (LoopbackTransport, LoopbackTransport) {
+        let (left, right) = (Rc::default(), Rc::default());
+        (
+            Self { outgoing: Rc::clone(&left), incoming: Rc::clone(&right) },
+            Self { outgoing: right, incoming: left },
+        )
+    }
+    
+    /// Messages sent to this end and not received yet
+    pub fn pending(&self) -> usize {
+        self.incoming.borrow().len()
+    }
+}
+
+impl Transport for LoopbackTransport {
+    fn send(&mut self, message: &str) {
+        self.outgoing.borrow_mut().push_back(message.to_string());
+    }
+    
+    fn receive(&mut self) -> Option<String> {
+        self.incoming.borrow_mut().pop_front()
+    }
+}